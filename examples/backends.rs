@@ -0,0 +1,50 @@
+//! Compare the speed of the stepping backends, and of the sparse backend's
+//! step algorithms, on random boards and on the `GameOfLife::stress` boards.
+//!
+//! Run with `cargo run --release --example backends`.
+
+use std::time::Instant;
+
+use conway::{Backend, GameOfLife, StepAlgorithm, StressKind};
+
+const GENERATIONS: usize = 50;
+
+fn main() {
+    println!(
+        "{:>10} {:>8} {:>12} {:>12} {:>12} {:>8}",
+        "board", "workload", "sparse", "scan-memo", "dense", "speedup"
+    );
+    for (rows, cols) in [(100, 100), (500, 500), (1000, 1000)] {
+        let mut workloads = Vec::new();
+        for density in [0.01, 0.1, 0.35] {
+            let mut soup = GameOfLife::from_grid(&vec![vec![false; cols]; rows]).unwrap();
+            soup.mutate(density, 1);
+            workloads.push((density.to_string(), soup));
+        }
+        workloads.push(("blinkers".to_string(), GameOfLife::stress(rows, cols, StressKind::Blinkers, 1)));
+        workloads.push(("soup".to_string(), GameOfLife::stress(rows, cols, StressKind::Soup, 1)));
+
+        for (workload, start_game) in workloads {
+            let time = |backend: Backend, step_algorithm: StepAlgorithm| {
+                let mut game = start_game.clone();
+                game.set_backend(backend);
+                game.set_step_algorithm(step_algorithm);
+                let start = Instant::now();
+                game.run(GENERATIONS);
+                start.elapsed()
+            };
+            let sparse = time(Backend::Sparse, StepAlgorithm::NeighborCount);
+            let scan_memo = time(Backend::Sparse, StepAlgorithm::ScanMemo);
+            let dense = time(Backend::Dense, StepAlgorithm::default());
+            println!(
+                "{:>10} {:>8} {:>10.1}ms {:>10.1}ms {:>10.1}ms {:>7.1}x",
+                format!("{}x{}", rows, cols),
+                workload,
+                sparse.as_secs_f64() * 1000.0,
+                scan_memo.as_secs_f64() * 1000.0,
+                dense.as_secs_f64() * 1000.0,
+                sparse.as_secs_f64() / dense.as_secs_f64(),
+            );
+        }
+    }
+}
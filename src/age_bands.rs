@@ -0,0 +1,100 @@
+use std::fmt;
+use std::str::FromStr;
+
+use sdl2::pixels::Color;
+
+/// An error produced when age bands are invalid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseAgeBandsError(String);
+
+impl fmt::Display for ParseAgeBandsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid age bands: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseAgeBandsError {}
+
+/// Colors for live cells by how many generations they have been alive, in
+/// discrete bands rather than a continuous fade. Each band starts at an age
+/// and covers every age up to the start of the next band; the last band
+/// covers every older age.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgeBands {
+    bands: Vec<(u32, Color)>,  // first age of each band and its color, by increasing age
+}
+
+impl AgeBands {
+    /// Create bands from the first age of each band and its color. The first
+    /// band must start at age 1, the age of newborn cells, and each band must
+    /// start at an older age than the one before it.
+    pub fn new(bands: &[(u32, Color)]) -> Result<AgeBands, ParseAgeBandsError> {
+        match bands.first() {
+            None => return Err(ParseAgeBandsError(String::from("at least one band is needed"))),
+            Some((start, _)) if *start != 1 => {
+                return Err(ParseAgeBandsError(format!("the first band must start at age 1, not {}", start)));
+            },
+            Some(_) => {},
+        }
+        for pair in bands.windows(2) {
+            let (previous, start) = (pair[0].0, pair[1].0);
+            if start <= previous {
+                return Err(ParseAgeBandsError(format!(
+                    "band starting at age {} must come after age {}", start, previous
+                )));
+            }
+        }
+        Ok(AgeBands { bands: bands.to_vec() })
+    }
+
+    /// The first age of each band and its color, by increasing age.
+    pub fn bands(&self) -> &[(u32, Color)] {
+        &self.bands
+    }
+
+    /// The color of a live cell of the given age.
+    pub fn color(&self, age: u32) -> Color {
+        let band = self.bands.partition_point(|(start, _)| *start <= age).max(1);
+        self.bands[band - 1].1
+    }
+}
+
+impl Default for AgeBands {
+    /// White for cells 1 and 2 generations old, light blue up to 9
+    /// generations, and deep blue from 10 generations on.
+    fn default() -> AgeBands {
+        AgeBands::new(&[
+            (1, Color::RGB(255, 255, 255)),
+            (3, Color::RGB(120, 170, 255)),
+            (10, Color::RGB(40, 70, 220)),
+        ]).unwrap()
+    }
+}
+
+impl FromStr for AgeBands {
+    type Err = ParseAgeBandsError;
+
+    /// Parse bands separated by commas, each the first age of the band and
+    /// its color as `#rrggbb`, like `1:#ffffff,3:#78aaff,10:#2846dc`.
+    fn from_str(s: &str) -> Result<AgeBands, ParseAgeBandsError> {
+        let bands = s.split(',')
+            .map(str::trim)
+            .filter(|band| !band.is_empty())
+            .map(|band| {
+                let invalid = || ParseAgeBandsError(format!("`{}` is not like `3:#78aaff`", band));
+                let (start, color) = band.split_once(':').ok_or_else(invalid)?;
+                let start = start.trim().parse::<u32>().map_err(|_| invalid())?;
+                let color = parse_color(color.trim()).ok_or_else(invalid)?;
+                Ok((start, color))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        AgeBands::new(&bands)
+    }
+}
+
+/// Parse a color written as `#rrggbb`.
+fn parse_color(value: &str) -> Option<Color> {
+    let hex = value.strip_prefix('#').filter(|hex| hex.len() == 6 && hex.is_ascii())?;
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Color::RGB(channel(0)?, channel(2)?, channel(4)?))
+}
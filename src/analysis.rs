@@ -0,0 +1,368 @@
+use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::{BoundaryMode, Cell, GameOfLife, Region};
+
+const OBJECT_SPACING: usize = 2;  // greatest distance between neighboring cells of one object
+const MAX_PERIOD: usize = 30;  // longest period looked for when identifying objects
+const MAX_OBJECT_SPAN: usize = 64;  // largest object, in rows or columns, that is identified
+const GROWTH_FRACTION: f64 = 0.2;  // fitted growth over the second half of a run, relative to its mean population, that counts as unbounded
+
+/// Common still lifes, oscillators, and spaceships, each given by one of its
+/// phases with `O` for live cells.
+const LEXICON: [(&str, &[&str]); 9] = [
+    ("block", &["OO", "OO"]),
+    ("beehive", &[".OO.", "O..O", ".OO."]),
+    ("loaf", &[".OO.", "O..O", ".O.O", "..O."]),
+    ("boat", &["OO.", "O.O", ".O."]),
+    ("tub", &[".O.", "O.O", ".O."]),
+    ("blinker", &["OOO"]),
+    ("toad", &[".OOO", "OOO."]),
+    ("beacon", &["OO..", "OO..", "..OO", "..OO"]),
+    ("glider", &[".O.", "..O", "OOO"]),
+];
+
+/// Live cells as sorted `(row, col)` offsets from the top left of their
+/// bounding box.
+type Shape = Vec<(isize, isize)>;
+
+/// The generations until a group of cells repeats its shape, and the rows
+/// and columns it moved in that time.
+type Repeat = (usize, (isize, isize));
+
+/// The long-term behavior of a game, see `GameOfLife::classify`.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Behavior {
+    /// Every cell died.
+    Died,
+    /// The board stopped changing.
+    Stable,
+    /// The board repeats with the given period, more than 1.
+    Oscillating { period: usize },
+    /// The population keeps growing, as with guns and breeders.
+    Growing,
+    /// None of the above within the generations run, e.g. a soup that is
+    /// still settling.
+    Unsettled,
+}
+
+impl fmt::Display for Behavior {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Behavior::Died => write!(f, "died"),
+            Behavior::Stable => write!(f, "stable"),
+            Behavior::Oscillating { period } => write!(f, "oscillating (period {})", period),
+            Behavior::Growing => write!(f, "growing"),
+            Behavior::Unsettled => write!(f, "unsettled"),
+        }
+    }
+}
+
+/// How a game ends up, see `GameOfLife::fate`.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub struct Fate {
+    pub behavior: Behavior,  // long-term behavior of the game
+    pub settled_at: Option<usize>,  // generations run before every cell died or the board first showed a repeating state, if it did
+    pub population: usize,  // live cells when the verdict was reached
+}
+
+/// A group of live cells that lie close together, named if it matches a
+/// common object.
+#[derive(Clone, Debug)]
+pub struct Object {
+    pub cells: Vec<Cell>,  // live cells of the object, sorted by row then column
+    pub name: Option<&'static str>,  // lexicon name of the object, if it is a known one
+    pub period: Option<usize>,  // generations until the object repeats its shape, if found
+    pub displacement: Option<(isize, isize)>,  // rows and columns the object moves each period, if found
+}
+
+impl Object {
+    /// Whether the object repeats its shape in a new position, so it travels
+    /// across the board.
+    pub fn is_spaceship(&self) -> bool {
+        self.displacement.is_some_and(|displacement| displacement != (0, 0))
+    }
+}
+
+impl fmt::Display for Object {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.name, self.period) {
+            (Some(name), _) => write!(f, "{}", name),
+            (None, Some(period)) => write!(f, "unknown ({} cells, period {})", self.cells.len(), period),
+            (None, None) => write!(f, "unknown ({} cells, period ?)", self.cells.len()),
+        }
+    }
+}
+
+impl GameOfLife {
+    /// Split the live cells into groups, where cells at most two rows and
+    /// columns apart are in the same group. Two cells is the spacing at which
+    /// live cells can still affect the same dead cell, and it keeps objects
+    /// such as the beacon whole in phases where their cells do not touch.
+    pub fn components(&self) -> Vec<Vec<Cell>> {
+        let mut unvisited: HashSet<Cell> = self.live_cells().into_iter().collect();
+        let mut components = Vec::new();
+        while let Some(start) = unvisited.iter().next().copied() {
+            unvisited.remove(&start);
+            let mut component = vec![start];
+            let mut next = 0;
+            while next < component.len() {
+                let cell = component[next];
+                next += 1;
+                for near in self.cells_near(cell) {
+                    if unvisited.remove(&near) {
+                        component.push(near);
+                    }
+                }
+            }
+            component.sort_by_key(|cell| (cell.r, cell.c));
+            components.push(component);
+        }
+        components.sort_by_key(|component| (component[0].r, component[0].c));
+        components
+    }
+
+    /// Identify each group of live cells, see `components`. A group is named
+    /// if, evolved on its own, it repeats its shape (in any position) and one
+    /// of its phases matches a common object in any rotation or reflection.
+    pub fn objects(&self) -> Vec<Object> {
+        self.components().into_iter().map(|cells| self.identify(cells).0).collect()
+    }
+
+    /// Find the spaceships that lie wholly outside a region, e.g. the
+    /// gliders a gun inside the region has fired, see `objects`. Given a
+    /// `signature`, the live cells of any one phase of a spaceship in any
+    /// position and orientation, only spaceships of that kind are found.
+    pub fn escaped_spaceships(&self, region: Region, signature: Option<&[Cell]>) -> Vec<Object> {
+        let signature = signature.map(|cells| canonical(&translated(
+            cells.iter().map(|cell| (cell.r as isize, cell.c as isize))
+        )));
+        self.components().into_iter()
+            .filter(|cells| !cells.iter().any(|cell| region.contains(*cell)))
+            .map(|cells| self.identify(cells))
+            .filter(|(object, phases)| {
+                object.is_spaceship() && signature.as_ref().is_none_or(|signature| phases.contains(signature))
+            })
+            .map(|(object, _)| object)
+            .collect()
+    }
+
+    /// Identify a group of live cells, returning it with the canonical
+    /// shapes of the phases it was seen in.
+    fn identify(&self, cells: Vec<Cell>) -> (Object, Vec<Shape>) {
+        let (repeat, phases) = self.isolated_phases(&cells);
+        let name = repeat.and_then(|_| LEXICON.iter()
+            .find(|(_, rows)| phases.contains(&canonical(&parse_shape(rows))))
+            .map(|(name, _)| *name));
+        let (period, displacement) = (repeat.map(|(period, _)| period), repeat.map(|(_, displacement)| displacement));
+        (Object { cells, name, period, displacement }, phases)
+    }
+
+    /// Classify the long-term behavior of the game by running it for up to
+    /// `generations` generations, then put the game back as it was.
+    ///
+    /// The first of these to happen decides the verdict: every cell dies
+    /// (`Died`), or the board exactly repeats an earlier generation, with
+    /// period 1 being `Stable` and longer periods `Oscillating`. If neither
+    /// happens, a line is fitted to the population over the second half of
+    /// the run, and the game is `Growing` if the fitted growth over that half
+    /// is at least a fifth of its mean population, or else `Unsettled`.
+    /// Spaceships on a torus eventually come back around, so they classify as
+    /// oscillating given enough generations.
+    pub fn classify(&mut self, generations: usize) -> Behavior {
+        self.fate(generations).behavior
+    }
+
+    /// Classify the long-term behavior of the game like `classify`, also
+    /// finding when it settled and its population once the verdict was
+    /// reached, then put the game back as it was. Running stops as soon as
+    /// the game dies or repeats, so the population is that of the first
+    /// repeated generation, or of the last generation run when the game
+    /// neither died nor repeated.
+    pub fn fate(&mut self, generations: usize) -> Fate {
+        let start = self.clone();
+        let fate = self.run_classify(generations);
+        *self = start;
+        fate
+    }
+
+    fn run_classify(&mut self, generations: usize) -> Fate {
+        // Boards seen so far by their hash, each with when it was seen. Boards
+        // with the same hash are compared cell by cell, so a hash collision is
+        // never taken for a repeat.
+        let mut seen: HashMap<u64, Vec<(Vec<Cell>, usize)>> = HashMap::new();
+        let mut populations = Vec::with_capacity(generations + 1);
+        for generation in 0..=generations {
+            if self.is_extinct() {
+                return Fate { behavior: Behavior::Died, settled_at: Some(generation), population: 0 };
+            }
+            let board = self.live_cells_sorted();
+            let mut hasher = DefaultHasher::new();
+            board.hash(&mut hasher);
+            let candidates = seen.entry(hasher.finish()).or_default();
+            let earlier = candidates.iter().find(|(seen_board, _)| *seen_board == board).map(|(_, earlier)| *earlier);
+            if let Some(earlier) = earlier {
+                let behavior = match generation - earlier {
+                    1 => Behavior::Stable,
+                    period => Behavior::Oscillating { period },
+                };
+                return Fate { behavior, settled_at: Some(earlier), population: self.population() };
+            }
+            candidates.push((board, generation));
+            populations.push(self.population() as f64);
+            if generation < generations {
+                self.step();
+            }
+        }
+
+        let recent = &populations[populations.len() / 2..];
+        let n = recent.len() as f64;
+        let mean_x = (n - 1.0) / 2.0;
+        let mean_y = recent.iter().sum::<f64>() / n;
+        let (covariance, variance) = recent.iter().enumerate().fold((0.0, 0.0), |(cov, var), (x, y)| {
+            let dx = x as f64 - mean_x;
+            (cov + dx * (y - mean_y), var + dx * dx)
+        });
+        let slope = if variance > 0.0 { covariance / variance } else { 0.0 };
+        let behavior = match slope * n >= GROWTH_FRACTION * mean_y {
+            true => Behavior::Growing,
+            false => Behavior::Unsettled,
+        };
+        Fate { behavior, settled_at: None, population: self.population() }
+    }
+
+    /// Get the cells within `OBJECT_SPACING` rows and columns of a cell.
+    fn cells_near(&self, cell: Cell) -> Vec<Cell> {
+        let spacing = OBJECT_SPACING as isize;
+        let wrap = |i: usize, offset: isize, len: usize, boundary: BoundaryMode| -> Option<usize> {
+            let i = isize::try_from(i).unwrap() + offset;
+            let len = isize::try_from(len).unwrap();
+            match boundary {
+                BoundaryMode::Toroidal => usize::try_from(i.rem_euclid(len)).ok(),
+                BoundaryMode::Dead | BoundaryMode::Reflect => usize::try_from(i).ok().filter(|i| (*i as isize) < len),
+            }
+        };
+        let mut near = Vec::new();
+        for dr in -spacing..=spacing {
+            for dc in -spacing..=spacing {
+                let r = wrap(cell.r, dr, self.rows, self.boundary_rows());
+                if let (Some(r), Some(c)) = (r, wrap(cell.c, dc, self.cols, self.boundary_cols())) {
+                    near.push(Cell { r, c });
+                }
+            }
+        }
+        near
+    }
+
+    /// Get the shape of a group of cells. On a toroidal axis, a group that
+    /// crosses an edge is joined back together across it.
+    fn unwrapped(&self, cells: &[Cell]) -> Shape {
+        let shift = |positions: Vec<usize>, len: usize, boundary: BoundaryMode| match boundary {
+            BoundaryMode::Toroidal => wrap_shift(positions.into_iter(), len),
+            BoundaryMode::Dead | BoundaryMode::Reflect => 0,
+        };
+        let shift_r = shift(cells.iter().map(|cell| cell.r).collect(), self.rows, self.boundary_rows());
+        let shift_c = shift(cells.iter().map(|cell| cell.c).collect(), self.cols, self.boundary_cols());
+        translated(cells.iter().map(|cell| (
+            ((cell.r + shift_r) % self.rows) as isize,
+            ((cell.c + shift_c) % self.cols) as isize,
+        )))
+    }
+
+    /// Evolve a group of cells alone on an empty board, returning its period
+    /// and how far it moved in that time if it repeats its shape within
+    /// `MAX_PERIOD` generations, and the canonical shapes of the phases seen
+    /// along the way.
+    fn isolated_phases(&self, cells: &[Cell]) -> (Option<Repeat>, Vec<Shape>) {
+        let shape = self.unwrapped(cells);
+        let (rows, cols) = shape.iter().fold((0, 0), |(rows, cols), (r, c)| (rows.max(r + 1), cols.max(c + 1)));
+        let mut phases = vec![canonical(&shape)];
+        if rows as usize > MAX_OBJECT_SPAN || cols as usize > MAX_OBJECT_SPAN {
+            // Large groups are most likely still evolving.
+            return (None, phases);
+        }
+
+        // Leave room for the group to travel or grow for `MAX_PERIOD`
+        // generations without reaching the edges.
+        let margin = MAX_PERIOD + OBJECT_SPACING;
+        let live = shape.iter()
+            .map(|(r, c)| Cell { r: *r as usize + margin, c: *c as usize + margin })
+            .collect();
+        let mut game = GameOfLife::with_live(rows as usize + 2 * margin, cols as usize + 2 * margin, live);
+        game.set_rule(self.rule());
+        game.set_boundary(BoundaryMode::Dead);
+
+        for generation in 1..=MAX_PERIOD {
+            game.step();
+            if game.population() == 0 {
+                break;
+            }
+            let phase = translated(game.live_cells().iter().map(|cell| (cell.r as isize, cell.c as isize)));
+            if phase == shape {
+                let region = game.bounding_box().unwrap();
+                let displacement = (region.r as isize - margin as isize, region.c as isize - margin as isize);
+                return (Some((generation, displacement)), phases);
+            }
+            phases.push(canonical(&phase));
+        }
+        (None, phases)
+    }
+}
+
+/// Get the shift along a wrapping axis of length `len` that moves the longest
+/// run of unused positions to the ends of the axis, so that positions that
+/// cross the edge become contiguous.
+fn wrap_shift(positions: impl Iterator<Item = usize>, len: usize) -> usize {
+    let mut positions: Vec<usize> = positions.collect();
+    positions.sort_unstable();
+    positions.dedup();
+    let (first, last) = (positions[0], positions[positions.len() - 1]);
+    let mut gap = (first + len - last, first);  // gap across the edge, and where it ends
+    for pair in positions.windows(2) {
+        if pair[1] - pair[0] > gap.0 {
+            gap = (pair[1] - pair[0], pair[1]);
+        }
+    }
+    len - gap.1
+}
+
+/// Move cells so their bounding box starts at row and column 0.
+fn translated(cells: impl Iterator<Item = (isize, isize)>) -> Shape {
+    let mut shape: Shape = cells.collect();
+    let min_r = shape.iter().map(|(r, _)| *r).min().unwrap_or(0);
+    let min_c = shape.iter().map(|(_, c)| *c).min().unwrap_or(0);
+    for (r, c) in shape.iter_mut() {
+        *r -= min_r;
+        *c -= min_c;
+    }
+    shape.sort();
+    shape
+}
+
+/// The smallest of a shape's rotations and reflections, so that shapes that
+/// only differ by orientation compare equal.
+fn canonical(shape: &Shape) -> Shape {
+    // Each combination of swapping rows with columns and flipping each axis.
+    (0..8)
+        .map(|transform| translated(shape.iter().map(|(r, c)| {
+            let (r, c) = if transform & 4 == 0 { (*r, *c) } else { (*c, *r) };
+            (if transform & 1 == 0 { r } else { -r }, if transform & 2 == 0 { c } else { -c })
+        })))
+        .min()
+        .unwrap()
+}
+
+/// Read a shape drawn with `O` for live cells.
+fn parse_shape(rows: &[&str]) -> Shape {
+    let mut shape = Shape::new();
+    for (r, row) in rows.iter().enumerate() {
+        for (c, char) in row.chars().enumerate() {
+            if char == 'O' {
+                shape.push((r as isize, c as isize));
+            }
+        }
+    }
+    shape
+}
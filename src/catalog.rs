@@ -0,0 +1,92 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::{GameOfLife, PatternError, PatternOptions};
+
+/// Every pattern file in a directory, loaded into games of the same size to
+/// browse through one at a time.
+pub struct Catalog {
+    entries: Vec<(PathBuf, GameOfLife)>,  // loaded files and their games, sorted by file name
+    skipped: Vec<(PathBuf, PatternError)>,  // files that could not be loaded, and why
+    current: usize,  // index of the entry being shown
+}
+
+impl Catalog {
+    /// Load every file in a directory as a pattern centered in a game of
+    /// `rows` by `cols`, in order of file name. Files starting with `.` are
+    /// ignored. Files that cannot be loaded, including patterns too large
+    /// for the game, are skipped and kept in `skipped`; games are never
+    /// grown to fit, so the autofit setting of `options` is ignored. Patterns
+    /// without a name are named after their file.
+    pub fn load(dir: &Path, rows: usize, cols: usize, options: &PatternOptions) -> io::Result<Catalog> {
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            if entry.file_type()?.is_file() && !hidden {
+                paths.push(entry.path());
+            }
+        }
+        paths.sort();
+
+        let options = PatternOptions { autofit: None, ..*options };
+        let (mut entries, mut skipped) = (Vec::new(), Vec::new());
+        for path in paths {
+            match GameOfLife::from_file_with(&path.to_string_lossy(), rows, cols, &options) {
+                Ok(mut game) => {
+                    if game.meta().name.is_none() {
+                        let mut meta = game.meta().clone();
+                        meta.name = path.file_stem().map(|stem| stem.to_string_lossy().into_owned());
+                        game.set_meta(meta);
+                    }
+                    entries.push((path, game));
+                },
+                Err(error) => skipped.push((path, error)),
+            }
+        }
+        Ok(Catalog { entries, skipped, current: 0 })
+    }
+
+    /// The number of patterns loaded.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no patterns were loaded.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The files that could not be loaded, with the reason for each.
+    pub fn skipped(&self) -> &[(PathBuf, PatternError)] {
+        &self.skipped
+    }
+
+    /// The position of the pattern being shown, from 0.
+    pub fn position(&self) -> usize {
+        self.current
+    }
+
+    /// The file and game of the pattern being shown, or `None` if no
+    /// patterns were loaded.
+    pub fn current(&self) -> Option<(&Path, &GameOfLife)> {
+        self.entries.get(self.current).map(|(path, game)| (path.as_path(), game))
+    }
+
+    /// Move to the next pattern, wrapping around to the first.
+    pub fn next_pattern(&mut self) -> Option<(&Path, &GameOfLife)> {
+        if !self.entries.is_empty() {
+            self.current = (self.current + 1) % self.entries.len();
+        }
+        self.current()
+    }
+
+    /// Move to the previous pattern, wrapping around to the last.
+    pub fn previous_pattern(&mut self) -> Option<(&Path, &GameOfLife)> {
+        if !self.entries.is_empty() {
+            self.current = (self.current + self.entries.len() - 1) % self.entries.len();
+        }
+        self.current()
+    }
+}
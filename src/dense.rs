@@ -0,0 +1,175 @@
+use std::collections::HashSet;
+
+use crate::{BoundaryMode, Cell, Neighborhood, RuleString};
+
+/// A grid stored as one bit per cell, for stepping whole boards with bitwise
+/// operations on 64 cells at a time.
+///
+/// Each row is stored in its own run of words, so that neighboring rows line
+/// up: cell `(r, c)` is bit `c % 64` of word `r * words_per_row + c / 64`.
+/// Bits past the last column of a row are always zero.
+pub(crate) struct DenseGrid {
+    rows: usize,
+    cols: usize,
+    words_per_row: usize,
+    bits: Vec<u64>,
+}
+
+impl DenseGrid {
+    pub(crate) fn from_live<'a>(rows: usize, cols: usize, live: impl Iterator<Item = &'a Cell>) -> DenseGrid {
+        let words_per_row = cols.div_ceil(64);
+        let mut bits = vec![0; rows * words_per_row];
+        for cell in live {
+            bits[cell.r * words_per_row + cell.c / 64] |= 1 << (cell.c % 64);
+        }
+        DenseGrid { rows, cols, words_per_row, bits }
+    }
+
+    pub(crate) fn live_cells(&self) -> HashSet<Cell> {
+        let mut live = HashSet::new();
+        for (w, word) in self.bits.iter().enumerate() {
+            let (r, first_c) = (w / self.words_per_row, (w % self.words_per_row) * 64);
+            let mut bits = *word;
+            while bits != 0 {
+                live.insert(Cell { r, c: first_c + usize::try_from(bits.trailing_zeros()).unwrap() });
+                bits &= bits - 1;
+            }
+        }
+        live
+    }
+
+    /// Compute the next generation. Each cell's live neighbors are counted in
+    /// parallel for a whole word of cells, as a 4-bit count spread across
+    /// four words, and the rule is applied to the counts with bit masks.
+    /// Rows above the top and below the bottom follow `boundary_rows`, and
+    /// columns beyond the left and right edges follow `boundary_cols`.
+    pub(crate) fn step(
+        &self,
+        rule: RuleString,
+        boundary_rows: BoundaryMode,
+        boundary_cols: BoundaryMode,
+        neighborhood: Neighborhood,
+    ) -> DenseGrid {
+        let mut next = vec![0; self.bits.len()];
+        let empty = vec![0; self.words_per_row];
+        let last = self.rows - 1;
+        for r in 0..self.rows {
+            let above = match boundary_rows {
+                _ if r > 0 => self.row(r - 1),
+                BoundaryMode::Toroidal => self.row(last),
+                BoundaryMode::Reflect if self.rows > 1 => self.row(1),
+                _ => &empty,
+            };
+            let below = match boundary_rows {
+                _ if r < last => self.row(r + 1),
+                BoundaryMode::Toroidal => self.row(0),
+                BoundaryMode::Reflect if self.rows > 1 => self.row(last - 1),
+                _ => &empty,
+            };
+            let row = self.row(r);
+            let boundary = boundary_cols;
+            let (above_east, above_west) = (self.shifted_east(above, boundary), self.shifted_west(above, boundary));
+            let (row_east, row_west) = (self.shifted_east(row, boundary), self.shifted_west(row, boundary));
+            let (below_east, below_west) = (self.shifted_east(below, boundary), self.shifted_west(below, boundary));
+            let mut neighbors: Vec<&[u64]> = vec![
+                &above_east, above,
+                &row_east, &row_west,
+                below, &below_west,
+            ];
+            if neighborhood == Neighborhood::Moore {
+                // The up-right and down-left diagonals, which `Hex` leaves out.
+                neighbors.extend([&above_west[..], &below_east[..]]);
+            }
+
+            for w in 0..self.words_per_row {
+                let mut count = [0u64; 4];
+                for neighbor in neighbors.iter() {
+                    add(&mut count, neighbor[w]);
+                }
+                let (mut born, mut survives) = (0, 0);
+                for n in 0..=8 {
+                    let matches = count_is(&count, n);
+                    if rule.born(n) {
+                        born |= matches;
+                    }
+                    if rule.survives(n) {
+                        survives |= matches;
+                    }
+                }
+                let alive = row[w];
+                next[r * self.words_per_row + w] = ((alive & survives) | (!alive & born)) & self.word_mask(w);
+            }
+        }
+        DenseGrid { bits: next, ..*self }
+    }
+
+    fn row(&self, r: usize) -> &[u64] {
+        &self.bits[r * self.words_per_row..(r + 1) * self.words_per_row]
+    }
+
+    /// The bits of a row that hold cells.
+    fn word_mask(&self, w: usize) -> u64 {
+        match self.cols - w * 64 {
+            cols if cols >= 64 => !0,
+            cols => (1 << cols) - 1,
+        }
+    }
+
+    /// The state of the cell just beyond the west or east edge of a row, as 0
+    /// or 1.
+    fn beyond_edge(&self, row: &[u64], boundary: BoundaryMode, west: bool) -> u64 {
+        let last = self.cols - 1;
+        match boundary {
+            BoundaryMode::Toroidal => bit(row, if west { last } else { 0 }),
+            BoundaryMode::Reflect if self.cols > 1 => bit(row, if west { 1 } else { last - 1 }),
+            _ => 0,
+        }
+    }
+
+    /// A row moved one column east, so each cell holds its west neighbor.
+    fn shifted_east(&self, row: &[u64], boundary: BoundaryMode) -> Vec<u64> {
+        let mut shifted = vec![0; row.len()];
+        let mut carry = self.beyond_edge(row, boundary, true);
+        for (w, word) in row.iter().enumerate() {
+            shifted[w] = (word << 1) | carry;
+            carry = word >> 63;
+        }
+        shifted
+    }
+
+    /// A row moved one column west, so each cell holds its east neighbor.
+    fn shifted_west(&self, row: &[u64], boundary: BoundaryMode) -> Vec<u64> {
+        let mut shifted = vec![0; row.len()];
+        let mut carry = 0;
+        for (w, word) in row.iter().enumerate().rev() {
+            shifted[w] = (word >> 1) | (carry << 63);
+            carry = word & 1;
+        }
+        let last = self.cols - 1;
+        shifted[last / 64] |= self.beyond_edge(row, boundary, false) << (last % 64);
+        shifted
+    }
+}
+
+/// The bit of a row for column `c`, as 0 or 1.
+fn bit(row: &[u64], c: usize) -> u64 {
+    (row[c / 64] >> (c % 64)) & 1
+}
+
+/// Add one bit to each of 64 4-bit counts, stored with bit `k` of every count
+/// in `count[k]`.
+fn add(count: &mut [u64; 4], bits: u64) {
+    let mut carry = bits;
+    for plane in count.iter_mut() {
+        let sum = *plane ^ carry;
+        carry &= *plane;
+        *plane = sum;
+    }
+}
+
+/// A mask of which of 64 4-bit counts equal `n`.
+fn count_is(count: &[u64; 4], n: usize) -> u64 {
+    count.iter().enumerate().fold(!0, |matches, (k, plane)| {
+        matches & if (n >> k) & 1 == 1 { *plane } else { !*plane }
+    })
+}
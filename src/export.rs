@@ -0,0 +1,221 @@
+use std::io::{self, Write};
+
+use crate::GameOfLife;
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+const MAX_STORED_BLOCK: usize = 65_535;  // largest uncompressed deflate block
+
+const BACKGROUND: [u8; 4] = [0, 0, 0, 255];  // dead cells, as RGBA
+const FOREGROUND: [u8; 4] = [255, 255, 255, 255];  // live cells, as RGBA
+const RLE_LINE_LENGTH: usize = 70;  // longest line of RLE cell runs, as other programs expect
+
+/// Draw a game into an RGBA image without SDL, with each cell drawn as a
+/// square of `cell_size` pixels, using the same colors as `SdlRender`.
+///
+/// The image is `cols * cell_size` pixels wide and `rows * cell_size` pixels
+/// tall, stored in row-major order with 4 bytes per pixel.
+pub fn render_to_rgba(game: &GameOfLife, cell_size: usize) -> Vec<u8> {
+    let (width, height) = (game.cols * cell_size, game.rows * cell_size);
+    let mut pixels = BACKGROUND.repeat(width * height);
+    for cell in game.live_cells() {
+        for y in cell.r * cell_size..(cell.r + 1) * cell_size {
+            let row = y * width;
+            for x in cell.c * cell_size..(cell.c + 1) * cell_size {
+                pixels[(row + x) * 4..(row + x + 1) * 4].copy_from_slice(&FOREGROUND);
+            }
+        }
+    }
+    pixels
+}
+
+/// Encode an RGBA image as a PNG file. The image data is stored without
+/// compression, which keeps the encoder simple at the cost of file size.
+pub fn write_png<W: Write>(writer: &mut W, width: usize, height: usize, rgba: &[u8]) -> io::Result<()> {
+    assert_eq!(rgba.len(), width * height * 4, "image data does not match dimensions");
+
+    writer.write_all(&PNG_SIGNATURE)?;
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&u32::try_from(width).unwrap().to_be_bytes());
+    header.extend_from_slice(&u32::try_from(height).unwrap().to_be_bytes());
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);  // 8 bit RGBA, no interlacing
+    write_chunk(writer, b"IHDR", &header)?;
+
+    // Each scanline is prefixed with filter type 0 (none).
+    let mut scanlines = Vec::with_capacity(height * (width * 4 + 1));
+    for row in rgba.chunks(width * 4).take(height) {
+        scanlines.push(0);
+        scanlines.extend_from_slice(row);
+    }
+    write_chunk(writer, b"IDAT", &zlib_stored(&scanlines))?;
+
+    write_chunk(writer, b"IEND", &[])
+}
+
+/// The cells of a game as rows of whether each cell is alive, trimmed to the
+/// smallest rectangle holding the live cells. Empty if every cell is dead.
+fn live_rows(game: &GameOfLife) -> Vec<Vec<bool>> {
+    let Some(region) = game.bounding_box() else {
+        return Vec::new();
+    };
+    let mut rows = vec![vec![false; region.cols]; region.rows];
+    for cell in game.live_cells() {
+        rows[cell.r - region.r][cell.c - region.c] = true;
+    }
+    rows
+}
+
+/// Write the live cells of a game as a chars pattern file, drawing dead
+/// cells as `.` and live cells as `#`.
+pub(crate) fn write_chars(game: &GameOfLife, writer: &mut dyn Write) -> io::Result<()> {
+    writeln!(writer, "chars")?;
+    writeln!(writer, "{{.#}}")?;
+    for row in live_rows(game) {
+        let line: String = row.iter().map(|alive| if *alive { '#' } else { '.' }).collect();
+        writeln!(writer, "{}", line)?;
+    }
+    Ok(())
+}
+
+/// Write the live cells of a game as a coords pattern file, in `row,col`
+/// order.
+pub(crate) fn write_coords(game: &GameOfLife, writer: &mut dyn Write) -> io::Result<()> {
+    writeln!(writer, "coords")?;
+    let Some(region) = game.bounding_box() else {
+        return Ok(());
+    };
+    for cell in game.live_cells_sorted() {
+        writeln!(writer, "{},{}", cell.r - region.r, cell.c - region.c)?;
+    }
+    Ok(())
+}
+
+/// Write the live cells of a game as an RLE pattern file, with the game's
+/// rule and metadata.
+pub(crate) fn write_rle(game: &GameOfLife, writer: &mut dyn Write) -> io::Result<()> {
+    let meta = game.meta();
+    if let Some(name) = &meta.name {
+        writeln!(writer, "#N {}", name)?;
+    }
+    if let Some(author) = &meta.author {
+        writeln!(writer, "#O {}", author)?;
+    }
+    for comment in meta.comments.iter() {
+        writeln!(writer, "#C {}", comment)?;
+    }
+    let rows = live_rows(game);
+    let cols = rows.first().map_or(0, Vec::len);
+    writeln!(writer, "x = {}, y = {}, rule = {}", cols, rows.len(), game.rule())?;
+
+    // Runs of dead (`b`) and live (`o`) cells, with dead cells at the end of
+    // a row left out, and the ends of rows (`$`) counted together when rows
+    // are empty.
+    let mut runs: Vec<(usize, char)> = Vec::new();
+    let mut row_ends = 0;  // ends of rows not yet written
+    for (r, row) in rows.iter().enumerate() {
+        if r > 0 {
+            row_ends += 1;
+        }
+        let Some(last) = row.iter().rposition(|alive| *alive) else {
+            continue;
+        };
+        if row_ends > 0 {
+            runs.push((row_ends, '$'));
+            row_ends = 0;
+        }
+        for alive in &row[..=last] {
+            let tag = if *alive { 'o' } else { 'b' };
+            match runs.last_mut() {
+                Some((count, last_tag)) if *last_tag == tag => *count += 1,
+                _ => runs.push((1, tag)),
+            }
+        }
+    }
+
+    let mut line = String::new();
+    for (count, tag) in runs.into_iter().chain([(1, '!')]) {
+        let run = match count {
+            1 => tag.to_string(),
+            count => format!("{}{}", count, tag),
+        };
+        if line.len() + run.len() > RLE_LINE_LENGTH {
+            writeln!(writer, "{}", line)?;
+            line.clear();
+        }
+        line.push_str(&run);
+    }
+    writeln!(writer, "{}", line)
+}
+
+/// Write the live cells of a game as a plaintext (`.cells`) pattern file,
+/// with the game's name and comments.
+pub(crate) fn write_cells(game: &GameOfLife, writer: &mut dyn Write) -> io::Result<()> {
+    let meta = game.meta();
+    if let Some(name) = &meta.name {
+        writeln!(writer, "!Name: {}", name)?;
+    }
+    if let Some(author) = &meta.author {
+        writeln!(writer, "!Author: {}", author)?;
+    }
+    for comment in meta.comments.iter() {
+        writeln!(writer, "!{}", comment)?;
+    }
+    for row in live_rows(game) {
+        let line: String = row.iter().map(|alive| if *alive { 'O' } else { '.' }).collect();
+        writeln!(writer, "{}", line)?;
+    }
+    Ok(())
+}
+
+fn write_chunk<W: Write>(writer: &mut W, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    writer.write_all(&u32::try_from(data.len()).unwrap().to_be_bytes())?;
+    writer.write_all(kind)?;
+    writer.write_all(data)?;
+    let crc = crc32(kind.iter().chain(data.iter()).copied());
+    writer.write_all(&crc.to_be_bytes())
+}
+
+/// Wrap data in a zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut stream = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_STORED_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        stream.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = u16::try_from(block.len()).unwrap();
+        stream.push(u8::from(last));
+        stream.extend_from_slice(&len.to_le_bytes());
+        stream.extend_from_slice(&(!len).to_le_bytes());
+        stream.extend_from_slice(block);
+    }
+    stream.extend_from_slice(&adler32(data).to_be_bytes());
+    stream
+}
+
+fn crc32(bytes: impl Iterator<Item = u8>) -> u32 {
+    let mut table = [0u32; 256];
+    for (n, entry) in table.iter_mut().enumerate() {
+        let mut c = u32::try_from(n).unwrap();
+        for _ in 0..8 {
+            c = if c & 1 == 1 { 0xedb8_8320 ^ (c >> 1) } else { c >> 1 };
+        }
+        *entry = c;
+    }
+
+    let mut crc = 0xffff_ffff;
+    for byte in bytes {
+        crc = table[usize::from((crc as u8) ^ byte)] ^ (crc >> 8);
+    }
+    crc ^ 0xffff_ffff
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in bytes {
+        a = (a + u32::from(*byte)) % 65_521;
+        b = (b + a) % 65_521;
+    }
+    (b << 16) | a
+}
@@ -0,0 +1,48 @@
+use crate::GameOfLife;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;  // starting state of a 64-bit FNV-1a hash
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;  // multiplier of a 64-bit FNV-1a hash
+
+/// A 64-bit FNV-1a hash, which only depends on the bytes hashed, so it is the
+/// same on every platform and in every build.
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Fnv {
+        Fnv(FNV_OFFSET)
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        for byte in value.to_le_bytes() {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(FNV_PRIME);
+        }
+    }
+}
+
+impl GameOfLife {
+    /// A hash of the board: its dimensions and live cells. Games with the
+    /// same board have the same hash on every platform, whatever their
+    /// generation, rule, or how their live cells are stored, so hashes of a
+    /// run can be compared across machines to check they behave the same.
+    pub fn state_hash(&self) -> u64 {
+        let mut hash = Fnv::new();
+        hash.write_u64(self.rows as u64);
+        hash.write_u64(self.cols as u64);
+        for cell in self.live_cells_sorted() {
+            hash.write_u64(cell.r as u64);
+            hash.write_u64(cell.c as u64);
+        }
+        hash.0
+    }
+}
+
+/// Add the hash of the next generation's board to a chain of hashes, giving
+/// one hash that changes if any generation of a run does. Chains start from
+/// 0, so the chain of a run is `chain_hash(chain_hash(0, first), second)`
+/// and so on.
+pub fn chain_hash(chain: u64, state: u64) -> u64 {
+    let mut hash = Fnv::new();
+    hash.write_u64(chain);
+    hash.write_u64(state);
+    hash.0
+}
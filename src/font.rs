@@ -0,0 +1,132 @@
+use std::collections::HashSet;
+
+use crate::{Cell, Corner, GameError, GameOfLife};
+
+const GLYPH_ROWS: usize = 7;  // height of each character in cells
+const GLYPH_COLS: usize = 5;  // width of each character in cells
+const GLYPH_GAP: usize = 1;  // dead columns between characters
+const LABEL_PADDING: usize = 1;  // dots of backing around label text
+const LABEL_BACKING: [u8; 4] = [0, 0, 0, 255];  // box behind label text, as RGBA
+const LABEL_COLOR: [u8; 4] = [255, 200, 0, 255];  // label text, as RGBA, unlike live cells
+
+/// The rows of a character of the built-in font, top to bottom, each with
+/// its leftmost cell in the highest of the low 5 bits, or `None` if the font
+/// has no such character. Letters are upper case only.
+fn glyph(c: char) -> Option<[u8; GLYPH_ROWS]> {
+    let rows = match c {
+        'A' => [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'B' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110],
+        'C' => [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110],
+        'D' => [0b11100, 0b10010, 0b10001, 0b10001, 0b10001, 0b10010, 0b11100],
+        'E' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111],
+        'F' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000],
+        'G' => [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111],
+        'H' => [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'I' => [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        'J' => [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100],
+        'K' => [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001],
+        'L' => [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111],
+        'M' => [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001],
+        'N' => [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001],
+        'O' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'P' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000],
+        'Q' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101],
+        'R' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001],
+        'S' => [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110],
+        'T' => [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100],
+        'U' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'V' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100],
+        'W' => [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010],
+        'X' => [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001],
+        'Y' => [0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100],
+        'Z' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111],
+        '0' => [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110],
+        '1' => [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        '2' => [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111],
+        '3' => [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110],
+        '4' => [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010],
+        '5' => [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110],
+        '6' => [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110],
+        '7' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000],
+        '8' => [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110],
+        '9' => [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100],
+        ' ' => [0; GLYPH_ROWS],
+        _ => return None,
+    };
+    Some(rows)
+}
+
+impl GameOfLife {
+    /// Generate a game of a given size with a line of text written in live
+    /// cells, centered on the board, e.g. to open a demo. Characters are 5
+    /// cells wide and 7 tall with a dead column between them, from a built-in
+    /// font of the letters A to Z, the digits, and space; lower case letters
+    /// are written in upper case.
+    pub fn from_text(text: &str, rows: usize, cols: usize) -> Result<GameOfLife, GameError> {
+        if rows == 0 || cols == 0 {
+            return Err(GameError::EmptyGrid);
+        }
+        let glyphs = text.chars()
+            .map(|c| glyph(c.to_ascii_uppercase()).ok_or(GameError::UnsupportedChar(c)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let width = (glyphs.len() * (GLYPH_COLS + GLYPH_GAP)).saturating_sub(GLYPH_GAP);
+        let height = if glyphs.is_empty() { 0 } else { GLYPH_ROWS };
+        if height > rows || width > cols {
+            return Err(GameError::TextTooLarge { size: (height, width), game: (rows, cols) });
+        }
+
+        let (top, left) = ((rows - height) / 2, (cols - width) / 2);
+        let mut cells = Vec::new();
+        for (i, glyph) in glyphs.iter().enumerate() {
+            for (r, bits) in glyph.iter().enumerate() {
+                for c in (0..GLYPH_COLS).filter(|c| bits & (1 << (GLYPH_COLS - 1 - c)) != 0) {
+                    cells.push(Cell { r: top + r, c: left + i * (GLYPH_COLS + GLYPH_GAP) + c });
+                }
+            }
+        }
+        let mut game = GameOfLife::with_live(rows, cols, HashSet::new());
+        game.set_cells(&cells, true);
+        Ok(game)
+    }
+}
+
+/// Draw a line of text in the built-in font into a corner of an RGBA image
+/// `width` pixels wide and `height` tall, like `render_to_rgba` makes, e.g.
+/// to label recorded frames with their generation. Each dot of the font is
+/// a `scale` by `scale` square of pixels, and the text sits on a black box
+/// a dot wider on every side so it shows over live cells. Characters the
+/// font lacks are left blank, and whatever does not fit in the image is cut
+/// off.
+pub fn draw_label(rgba: &mut [u8], width: usize, height: usize, text: &str, corner: Corner, scale: usize) {
+    assert_eq!(rgba.len(), width * height * 4, "image data does not match dimensions");
+    let glyphs: Vec<_> = text.chars().map(|c| glyph(c.to_ascii_uppercase()).unwrap_or([0; GLYPH_ROWS])).collect();
+    let dots_wide = (glyphs.len() * (GLYPH_COLS + GLYPH_GAP)).saturating_sub(GLYPH_GAP) + 2 * LABEL_PADDING;
+    let dots_tall = GLYPH_ROWS + 2 * LABEL_PADDING;
+    let (box_width, box_height) = (dots_wide * scale, dots_tall * scale);
+    let left = match corner {
+        Corner::TopLeft | Corner::BottomLeft => 0,
+        Corner::TopRight | Corner::BottomRight => width.saturating_sub(box_width),
+    };
+    let top = match corner {
+        Corner::TopLeft | Corner::TopRight => 0,
+        Corner::BottomLeft | Corner::BottomRight => height.saturating_sub(box_height),
+    };
+
+    let lit = |dot_r: usize, dot_c: usize| {
+        let (Some(r), Some(c)) = (dot_r.checked_sub(LABEL_PADDING), dot_c.checked_sub(LABEL_PADDING)) else {
+            return false;
+        };
+        let (i, c) = (c / (GLYPH_COLS + GLYPH_GAP), c % (GLYPH_COLS + GLYPH_GAP));
+        r < GLYPH_ROWS && c < GLYPH_COLS && glyphs.get(i).is_some_and(|glyph| glyph[r] & (1 << (GLYPH_COLS - 1 - c)) != 0)
+    };
+    for y in top..(top + box_height).min(height) {
+        for x in left..(left + box_width).min(width) {
+            let color = match lit((y - top) / scale, (x - left) / scale) {
+                true => LABEL_COLOR,
+                false => LABEL_BACKING,
+            };
+            let pixel = (y * width + x) * 4;
+            rgba[pixel..pixel + 4].copy_from_slice(&color);
+        }
+    }
+}
@@ -1,22 +1,497 @@
-use rand::Rng;
-use std::collections::HashSet;
-use std::string::ToString;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use rand::seq::index;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::ops::Index;
 use std::fs;
+use std::io::{self, Write};
 use std::cmp::max;
+use std::path::Path;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 
-/// Represents a cell in the Game of Life board.
-#[derive(PartialEq, Eq, Hash, Copy, Clone)]
+use crate::{RuleString, ParseRuleError};
+use crate::dense::DenseGrid;
+use crate::live_set::{LiveCells, LiveSet};
+use crate::export;
+
+const ACTIVE_REGION_GROWTH: usize = 8;  // cells an active region grows by at its edges
+const AUTOFIT_MARGIN: usize = 8;  // dead cells kept around a pattern that a game grows to fit
+
+/// Represents a cell in the Game of Life board. Cells are ordered by row,
+/// then by column.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone, Debug, Serialize, Deserialize)]
 pub struct Cell {
     pub r: usize,  // cell row
     pub c: usize,  // cell column
 }
 
+/// A rectangular region of cells in a game.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct Region {
+    pub r: usize,  // top row of the region
+    pub c: usize,  // left column of the region
+    pub rows: usize,  // height of the region in cells
+    pub cols: usize,  // width of the region in cells
+}
+
+impl Region {
+    /// Whether a cell lies inside the region.
+    pub fn contains(&self, cell: Cell) -> bool {
+        (self.r..self.r + self.rows).contains(&cell.r) && (self.c..self.c + self.cols).contains(&cell.c)
+    }
+}
+
+/// The cells that changed state during a single step.
+#[derive(Default)]
+pub struct StepDelta {
+    pub born: Vec<Cell>,  // cells that came alive
+    pub died: Vec<Cell>,  // cells that died
+}
+
+/// The result of asking a game to evolve one generation.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum StepOutcome {
+    /// The game evolved a generation.
+    Stepped,
+    /// Every cell is dead, so there was nothing to evolve.
+    Extinct,
+}
+
+/// Descriptive information about a pattern loaded from a file.
+#[derive(PartialEq, Eq, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PatternMeta {
+    pub name: Option<String>,  // pattern name (RLE `#N`)
+    pub author: Option<String>,  // pattern author (RLE `#O`)
+    pub comments: Vec<String>,  // comment lines in file order (RLE `#C`)
+}
+
+/// Errors produced when constructing a game.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum GameError {
+    /// The game would have no rows or no columns.
+    EmptyGrid,
+    /// A row of a grid has a different length than the first row.
+    RaggedGrid { row: usize, expected: usize, found: usize },
+    /// A mask does not have the same dimensions as the game.
+    MaskSize { expected: (usize, usize), found: (usize, usize) },
+    /// A bitvector has the wrong number of words for the game's dimensions.
+    BitvecLength { expected: usize, found: usize },
+    /// More live cells were asked for than the game has cells.
+    TooManyCells { count: usize, cells: usize },
+    /// Text has a character the built-in font cannot write.
+    UnsupportedChar(char),
+    /// Text written in the built-in font, as rows by columns, does not fit
+    /// in the game.
+    TextTooLarge { size: (usize, usize), game: (usize, usize) },
+    /// The game is smaller than the given rows and columns needed.
+    TooSmall { min: (usize, usize), found: (usize, usize) },
+    /// Two games to combine have different dimensions.
+    SizeMismatch { expected: (usize, usize), found: (usize, usize) },
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameError::EmptyGrid => write!(f, "grid must have at least one row and column"),
+            GameError::RaggedGrid { row, expected, found } => write!(
+                f, "row {} has {} cells, expected {}", row, found, expected
+            ),
+            GameError::MaskSize { expected, found } => write!(
+                f, "mask is {}x{}, expected {}x{}", found.0, found.1, expected.0, expected.1
+            ),
+            GameError::BitvecLength { expected, found } => write!(
+                f, "bitvector has {} words, expected {}", found, expected
+            ),
+            GameError::TooManyCells { count, cells } => write!(
+                f, "cannot place {} live cells in a game of {} cells", count, cells
+            ),
+            GameError::UnsupportedChar(c) => write!(f, "the font has no character `{}`", c),
+            GameError::TextTooLarge { size, game } => write!(
+                f, "text is {}x{}, too large for a {}x{} game", size.0, size.1, game.0, game.1
+            ),
+            GameError::TooSmall { min, found } => write!(
+                f, "game is {}x{}, but must be at least {}x{}", found.0, found.1, min.0, min.1
+            ),
+            GameError::SizeMismatch { expected, found } => write!(
+                f, "game is {}x{}, expected {}x{}", found.0, found.1, expected.0, expected.1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+/// Errors produced when loading a game from a pattern file.
+#[derive(Debug)]
+pub enum PatternError {
+    /// The file could not be read.
+    Io(io::Error),
+    /// The file is not in any supported pattern format.
+    UnknownFormat,
+    /// A chars file does not declare its dead and live characters.
+    MissingChars,
+    /// A coordinate in a coords file is not a valid cell index.
+    InvalidCoord(String),
+    /// An RLE file has no `x = ...` header line.
+    MissingHeader,
+    /// An RLE file declares a rule that cannot be parsed.
+    InvalidRule(ParseRuleError),
+    /// The pattern does not fit in the game; the pattern's size is given.
+    TooLarge { rows: usize, cols: usize },
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PatternError::Io(error) => write!(f, "error reading file: {}", error),
+            PatternError::UnknownFormat => write!(f, "file is not in a supported pattern format"),
+            PatternError::MissingChars => write!(f, "chars file has no `{{<dead><live>}}` declaration"),
+            PatternError::InvalidCoord(coord) => write!(f, "invalid coordinate `{}`", coord),
+            PatternError::MissingHeader => write!(f, "RLE file has no `x = ...` header"),
+            PatternError::InvalidRule(error) => write!(f, "{}", error),
+            PatternError::TooLarge { rows, cols } => write!(
+                f, "{}x{} pattern does not fit in the game", rows, cols
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+impl From<io::Error> for PatternError {
+    fn from(error: io::Error) -> PatternError {
+        PatternError::Io(error)
+    }
+}
+
+/// A pattern read from a file, before it is placed in a game.
+struct Pattern {
+    live: HashSet<Cell>,  // live cells, relative to the top left of the pattern
+    drawn: (usize, usize),  // rows and columns drawn by the file, including dead cells
+    rule: Option<RuleString>,  // rule declared by the file, if any
+    meta: PatternMeta,  // metadata declared by the file
+}
+
+impl Pattern {
+    /// The number of rows and columns spanned by the pattern, counted from
+    /// row and column 0: the live cells, or the cells drawn by the file if
+    /// those span more.
+    fn size(&self) -> (usize, usize) {
+        let (mut max_r, mut max_c) = (0, 0);
+        for cell in self.live.iter() {
+            max_r = max(max_r, cell.r);
+            max_c = max(max_c, cell.c);
+        }
+        (max(max_r + 1, self.drawn.0), max(max_c + 1, self.drawn.1))
+    }
+
+    /// Scale the pattern up so that each cell becomes a `scale` by `scale`
+    /// square of cells, or fail if the scaled pattern would be larger than
+    /// `max_rows` by `max_cols`.
+    fn scaled(self, scale: usize, max_rows: usize, max_cols: usize) -> Result<Pattern, PatternError> {
+        let (rows, cols) = self.size();
+        let (rows, cols) = (rows.saturating_mul(scale), cols.saturating_mul(scale));
+        if rows > max_rows || cols > max_cols {
+            return Err(PatternError::TooLarge { rows, cols });
+        }
+
+        let mut live = HashSet::with_capacity(self.live.len() * scale * scale);
+        for cell in self.live.iter() {
+            for r in cell.r * scale..(cell.r + 1) * scale {
+                for c in cell.c * scale..(cell.c + 1) * scale {
+                    live.insert(Cell { r, c });
+                }
+            }
+        }
+        let drawn = (self.drawn.0 * scale, self.drawn.1 * scale);
+        Ok(Pattern { live, drawn, ..self })
+    }
+
+    /// Create a game of the given size with the pattern in the middle.
+    fn into_game(self, rows: usize, cols: usize) -> Result<GameOfLife, PatternError> {
+        let (pattern_rows, pattern_cols) = self.size();
+        if pattern_rows > rows || pattern_cols > cols {
+            return Err(PatternError::TooLarge { rows: pattern_rows, cols: pattern_cols });
+        }
+
+        let r_shift = (rows - (pattern_rows - 1)) / 2;
+        let c_shift = (cols - (pattern_cols - 1)) / 2;
+
+        let mut centered = HashSet::new();
+        for cell in self.live.iter() {
+            centered.insert(Cell { r: cell.r + r_shift, c: cell.c + c_shift });
+        }
+
+        let mut game = GameOfLife::with_live(rows, cols, centered);
+        if let Some(rule) = self.rule {
+            game.rule = rule;
+        }
+        game.meta = self.meta;
+        Ok(game)
+    }
+}
+
+/// The order of the two numbers of each coordinate in a coords file.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Default)]
+pub enum CoordOrder {
+    /// `row,col`.
+    #[default]
+    RowCol,
+    /// `x,y`, i.e. `col,row`.
+    XY,
+}
+
+/// Settings for loading a pattern file.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Default)]
+pub struct PatternOptions {
+    pub coord_order: CoordOrder,  // order of the numbers in coords files
+    pub autofit: Option<(usize, usize)>,  // largest rows and columns to grow the game to, if growing
+    pub scale: Option<usize>,  // side length of the square of cells each pattern cell becomes, if scaling up
+}
+
+/// A pattern file format that games can be loaded from and written to.
+pub struct PatternFormat {
+    pub name: &'static str,  // name of the format
+    pub description: &'static str,  // one-line description of the format
+    pub example: &'static str,  // a glider written in the format
+    pub extensions: &'static [&'static str],  // file extensions of the format, the usual one first
+    detect: fn(&str) -> bool,  // whether file contents are in the format
+    parse: fn(&str, &PatternOptions) -> Result<Pattern, PatternError>,  // reads file contents in the format
+    write: fn(&GameOfLife, &mut dyn Write) -> io::Result<()>,  // writes the live cells of a game in the format
+}
+
+impl PatternFormat {
+    /// Write the live cells of a game in this format, trimmed to the
+    /// smallest rectangle holding them, so that loading the file gives back
+    /// the same pattern. Formats with metadata also get the game's name,
+    /// author, and comments, and RLE gets its rule.
+    pub fn write(&self, game: &GameOfLife, writer: &mut dyn Write) -> io::Result<()> {
+        (self.write)(game, writer)
+    }
+}
+
+/// Every format pattern files can be read from, in the order they are tried.
+static PATTERN_FORMATS: [PatternFormat; 4] = [
+    PatternFormat {
+        name: "chars",
+        description: "a picture of the pattern, with one character for dead cells and one for live cells",
+        example: "chars\n{.#}\n.#.\n..#\n###",
+        extensions: &["txt", "chars"],
+        detect: |contents| contents.starts_with("chars"),
+        parse: |contents, _| GameOfLife::parse_chars(contents),
+        write: export::write_chars,
+    },
+    PatternFormat {
+        name: "coords",
+        description: "a list of live cells as `row,col` coordinates, or `x,y` with --coord-order xy",
+        example: "coords\n0,1\n1,2\n2,0\n2,1\n2,2",
+        extensions: &["coords"],
+        detect: |contents| contents.starts_with("coords"),
+        parse: |contents, options| GameOfLife::parse_coords(contents, options.coord_order),
+        write: export::write_coords,
+    },
+    PatternFormat {
+        name: "rle",
+        description: "run length encoded rows of dead (`b`) and live (`o`) cells, as patterns are shared online",
+        example: "#N Glider\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!",
+        extensions: &["rle"],
+        detect: |contents| Regex::new(r"\A\s*(#|x\s*=)").unwrap().is_match(contents),
+        parse: |contents, _| GameOfLife::parse_rle(contents),
+        write: export::write_rle,
+    },
+    PatternFormat {
+        name: "cells",
+        description: "plaintext rows of dead (`.`) and live (`O`) cells, after `!` comment lines",
+        example: "!Name: Glider\n.O.\n..O\nOOO",
+        extensions: &["cells"],
+        detect: |contents| Regex::new(r"\A(!|[.O]+\r?\n)").unwrap().is_match(contents),
+        parse: |contents, _| GameOfLife::parse_cells(contents),
+        write: export::write_cells,
+    },
+];
+
+/// The formats pattern files can be read from and written to.
+pub fn pattern_formats() -> &'static [PatternFormat] {
+    &PATTERN_FORMATS
+}
+
+/// The format to write a pattern file in, inferred from the extension of
+/// its path, ignoring case.
+pub fn pattern_format_for_path(path: &Path) -> Option<&'static PatternFormat> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    PATTERN_FORMATS.iter().find(|format| format.extensions.contains(&extension.as_str()))
+}
+
+/// The format of pattern file contents, detected from the contents alone.
+fn detect_format(contents: &str) -> Result<&'static PatternFormat, PatternError> {
+    PATTERN_FORMATS.iter()
+        .find(|format| (format.detect)(contents))
+        .ok_or(PatternError::UnknownFormat)
+}
+
+/// A summary of a pattern file, read without loading it into a game.
+pub struct PatternInfo {
+    pub format: &'static PatternFormat,  // format the file was detected to be in
+    pub rule: Option<RuleString>,  // rule declared by the file, if any
+    pub rows: usize,  // rows spanned by the live cells, 0 if there are none
+    pub cols: usize,  // columns spanned by the live cells, 0 if there are none
+    pub population: usize,  // number of live cells
+    pub meta: PatternMeta,  // metadata declared by the file
+}
+
+/// Read a pattern file and summarize it: its format, declared rule, the
+/// bounding box and number of its live cells, and its metadata. The
+/// autofit and scale settings of `options` are ignored.
+pub fn pattern_info(path: &str, options: &PatternOptions) -> Result<PatternInfo, PatternError> {
+    let contents = fs::read_to_string(path)?;
+    let format = detect_format(&contents)?;
+    let pattern = (format.parse)(&contents, options)?;
+    let span = |coord: fn(&Cell) -> usize| match (pattern.live.iter().map(coord).min(), pattern.live.iter().map(coord).max()) {
+        (Some(min), Some(max)) => max - min + 1,
+        _ => 0,
+    };
+    Ok(PatternInfo {
+        format,
+        rule: pattern.rule,
+        rows: span(|cell| cell.r),
+        cols: span(|cell| cell.c),
+        population: pattern.live.len(),
+        meta: pattern.meta,
+    })
+}
+
+/// How neighbors are found for cells on the edges of the board.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Default, Serialize, Deserialize)]
+pub enum BoundaryMode {
+    /// Opposite edges are connected, so the board is a torus.
+    #[default]
+    Toroidal,
+    /// Cells beyond the edges are always dead.
+    Dead,
+    /// The edges are mirrors: a cell beyond an edge has the state of the
+    /// cell it mirrors, reflected about the center of the edge cell, so the
+    /// cell one past the edge mirrors the cell one inside it. Boards one cell
+    /// wide have nothing to mirror on that axis, so behave like `Dead`.
+    Reflect,
+}
+
+/// Which of the eight surrounding cells count as a cell's neighbors.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Default, Serialize, Deserialize)]
+pub enum Neighborhood {
+    /// All eight surrounding cells.
+    #[default]
+    Moore,
+    /// Six of the eight surrounding cells, approximating a hexagonal grid:
+    /// the up-right diagonal `(r - 1, c + 1)` and the down-left diagonal
+    /// `(r + 1, c - 1)` are left out. Shearing each row half a cell left of
+    /// the row below it turns the remaining six into the six cells around a
+    /// hexagon.
+    Hex,
+}
+
+impl Neighborhood {
+    /// Whether the neighbor at the given row and column offsets, each from
+    /// -1 to 1, is part of the neighborhood.
+    fn includes(self, dr: isize, dc: isize) -> bool {
+        match self {
+            Neighborhood::Moore => true,
+            Neighborhood::Hex => !matches!((dr, dc), (-1, 1) | (1, -1)),
+        }
+    }
+}
+
+/// How the next generation is computed. Both backends give the same result.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Default, Serialize, Deserialize)]
+pub enum Backend {
+    /// Only visit live cells and their neighbors. Fastest for sparse boards.
+    #[default]
+    Sparse,
+    /// Compute every cell of the board with bitwise operations, 64 cells at
+    /// a time. Fastest for busy boards.
+    Dense,
+}
+
+/// How the sparse backend finds the next generation. Both algorithms give
+/// the same result; they trade memory and speed differently.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Default, Serialize, Deserialize)]
+pub enum StepAlgorithm {
+    /// Each live cell adds one to a count for each of its neighbors, kept in
+    /// one map from cell to count, and every counted cell is then judged by
+    /// its count. Each live cell's neighbors are visited once, and the map
+    /// holds one entry per cell next to a live cell.
+    ///
+    /// On reflecting boards and toroidal boards less than 3 cells across,
+    /// a cell is not always a neighbor of each of its neighbors, so counts
+    /// can't be pushed from live cells and `ScanMemo` is used instead.
+    #[default]
+    NeighborCount,
+    /// Each live cell counts its own live neighbors, and each dead neighbor
+    /// found along the way counts its live neighbors in turn. Dead cells
+    /// already judged are remembered in a set so they are not judged again;
+    /// the set grows with the number of dead cells next to live ones, which
+    /// on busy boards is most of the board. Each cell near a live cell looks
+    /// up all of its neighbors, so this is slower than `NeighborCount`.
+    ScanMemo,
+}
+
+/// An edge of the board.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Edge {
+    /// Row 0.
+    Top,
+    /// The last row.
+    Bottom,
+    /// Column 0.
+    Left,
+    /// The last column.
+    Right,
+}
+
+/// How `GameOfLife::combine` merges the live cells of two games.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum SetOp {
+    /// Cells alive in either game.
+    Union,
+    /// Cells alive in both games.
+    Intersection,
+    /// Cells alive in the first game but not the second.
+    Difference,
+    /// Cells alive in exactly one of the games.
+    Xor,
+}
+
+/// Kinds of heavy, busy boards generated by `GameOfLife::stress`.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum StressKind {
+    /// Blinkers packed four cells apart across the whole board, each starting
+    /// in a random orientation. Every blinker oscillates forever without
+    /// interfering with the others, so activity never dies down.
+    Blinkers,
+    /// Every cell is alive with probability 1/2. Very busy at first, then
+    /// settles like any random soup.
+    Soup,
+}
+
 /// Represents a Game of Life.
+#[derive(Clone)]
 pub struct GameOfLife {
     pub rows: usize,
     pub cols: usize,
-    live: HashSet<Cell>,
+    live: LiveCells,  // live cells, in the kind of set chosen for the game
+    ages: HashMap<Cell, u32>,  // generations each live cell has been alive
+    frozen: HashSet<Cell>,  // cells that are always dead
+    rule: RuleString,
+    boundary_rows: BoundaryMode,  // how the top and bottom edges are treated
+    boundary_cols: BoundaryMode,  // how the left and right edges are treated
+    neighborhood: Neighborhood,  // which surrounding cells are neighbors
+    meta: PatternMeta,
+    active_region: Option<Region>,
+    generation: u128,
+    halt_when_extinct: bool,  // whether the generation stops counting once every cell is dead
+    backend: Backend,  // how the next generation is computed
+    step_algorithm: StepAlgorithm,  // how the sparse backend finds the next generation
 }
 
 impl GameOfLife {
@@ -31,177 +506,1386 @@ impl GameOfLife {
                 }
             }
         }
-        GameOfLife { rows, cols, live }
+        Self::with_live(rows, cols, live)
+    }
+
+    /// Generate a game of a given size with exactly `count` live cells, each
+    /// set of `count` distinct cells being equally likely. The same `seed`
+    /// always places the same cells.
+    pub fn random_count(rows: usize, cols: usize, count: usize, seed: u64) -> Result<GameOfLife, GameError> {
+        if rows == 0 || cols == 0 {
+            return Err(GameError::EmptyGrid);
+        }
+        let cells = rows.saturating_mul(cols);
+        if count > cells {
+            return Err(GameError::TooManyCells { count, cells });
+        }
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let live = index::sample(&mut rng, cells, count)
+            .into_iter()
+            .map(|i| Cell { r: i / cols, c: i % cols })
+            .collect();
+        Ok(Self::with_live(rows, cols, live))
+    }
+
+    /// Generate a game of a given size with a single glider next to the
+    /// middle of an edge, heading off the board across it, to check how the
+    /// edges behave: on a toroidal board the glider comes back in from the
+    /// opposite edge, while on a dead edge it breaks up. The glider crosses
+    /// the edge within 8 generations. The game must be at least 5x5.
+    pub fn wrap_demo(rows: usize, cols: usize, edge: Edge) -> Result<GameOfLife, GameError> {
+        if rows < 5 || cols < 5 {
+            return Err(GameError::TooSmall { min: (5, 5), found: (rows, cols) });
+        }
+        // The glider heads down and right; each edge flips it to head across
+        // that edge, and places it one cell from the edge.
+        let glider = [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)];
+        let (up, left, top, left_col) = match edge {
+            Edge::Top => (true, false, 1, (cols - 3) / 2),
+            Edge::Bottom => (false, false, rows - 4, (cols - 3) / 2),
+            Edge::Left => (false, true, (rows - 3) / 2, 1),
+            Edge::Right => (false, false, (rows - 3) / 2, cols - 4),
+        };
+        let live = glider.iter()
+            .map(|&(r, c)| Cell {
+                r: top + if up { 2 - r } else { r },
+                c: left_col + if left { 2 - c } else { c },
+            })
+            .collect();
+        Ok(Self::with_live(rows, cols, live))
+    }
+
+    /// Generate a game of a given size with a board that keeps the stepper
+    /// busy, for consistent heavy workloads when measuring performance. The
+    /// same `seed` always generates the same board; see `StressKind` for what
+    /// each kind looks like.
+    pub fn stress(rows: usize, cols: usize, kind: StressKind, seed: u64) -> GameOfLife {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut live = HashSet::new();
+        match kind {
+            StressKind::Blinkers => {
+                // Each blinker fits in a 3x3 box with a dead border on a 4x4
+                // pitch; incomplete boxes at the bottom and right are left empty
+                // so blinkers never touch, even across the edges of a torus.
+                for box_r in (0..rows.saturating_sub(3)).step_by(4) {
+                    for box_c in (0..cols.saturating_sub(3)).step_by(4) {
+                        let vertical = rng.gen_bool(0.5);
+                        for i in 0..3 {
+                            let (r, c) = if vertical { (box_r + i, box_c + 1) } else { (box_r + 1, box_c + i) };
+                            live.insert(Cell { r, c });
+                        }
+                    }
+                }
+            },
+            StressKind::Soup => {
+                for r in 0..rows {
+                    for c in 0..cols {
+                        if rng.gen_bool(0.5) {
+                            live.insert(Cell { r, c });
+                        }
+                    }
+                }
+            },
+        }
+        Self::with_live(rows, cols, live)
+    }
+
+    /// Generate a game from a grid of cells in row-major order, where `true`
+    /// cells are alive. The dimensions of the game are those of the grid, so
+    /// every row must have the same nonzero length.
+    pub fn from_grid<R: AsRef<[bool]>>(grid: &[R]) -> Result<GameOfLife, GameError> {
+        let rows = grid.len();
+        let cols = grid.first().map_or(0, |row| row.as_ref().len());
+        if rows == 0 || cols == 0 {
+            return Err(GameError::EmptyGrid);
+        }
+
+        let mut live = HashSet::new();
+        for (r, row) in grid.iter().enumerate() {
+            let row = row.as_ref();
+            if row.len() != cols {
+                return Err(GameError::RaggedGrid { row: r, expected: cols, found: row.len() });
+            }
+            for (c, alive) in row.iter().enumerate() {
+                if *alive {
+                    live.insert(Cell { r, c });
+                }
+            }
+        }
+        Ok(Self::with_live(rows, cols, live))
+    }
+
+    /// Generate a game of a given size from a bitvector in the format written
+    /// by `to_bitvec`. Padding bits after the last cell are ignored.
+    pub fn from_bitvec(rows: usize, cols: usize, words: &[u64]) -> Result<GameOfLife, GameError> {
+        if rows == 0 || cols == 0 {
+            return Err(GameError::EmptyGrid);
+        }
+        let cells = rows * cols;
+        if words.len() != cells.div_ceil(64) {
+            return Err(GameError::BitvecLength { expected: cells.div_ceil(64), found: words.len() });
+        }
+
+        let mut live = HashSet::new();
+        for (w, word) in words.iter().enumerate() {
+            let mut bits = *word;
+            while bits != 0 {
+                let index = w * 64 + usize::try_from(bits.trailing_zeros()).unwrap();
+                if index < cells {
+                    live.insert(Cell { r: index / cols, c: index % cols });
+                }
+                bits &= bits - 1;
+            }
+        }
+        Ok(Self::with_live(rows, cols, live))
+    }
+
+    /// Create a game with the standard rule and boundary from a set of live
+    /// cells.
+    pub(crate) fn with_live(rows: usize, cols: usize, live: HashSet<Cell>) -> GameOfLife {
+        let ages = live.iter().map(|cell| (*cell, 1)).collect();
+        GameOfLife {
+            rows, cols, ages,
+            live: LiveCells::new(LiveSet::default(), live),
+            frozen: HashSet::new(),
+            rule: RuleString::default(),
+            boundary_rows: BoundaryMode::default(),
+            boundary_cols: BoundaryMode::default(),
+            neighborhood: Neighborhood::default(),
+            meta: PatternMeta::default(),
+            active_region: None,
+            generation: 0,
+            halt_when_extinct: false,
+            backend: Backend::default(),
+            step_algorithm: StepAlgorithm::default(),
+        }
+    }
+
+    /// The number of generations the game has evolved.
+    pub fn generation(&self) -> u128 {
+        self.generation
+    }
+
+    pub(crate) fn set_generation(&mut self, generation: u128) {
+        self.generation = generation;
+    }
+
+    /// Whether stepping a game in which every cell is dead leaves the
+    /// generation unchanged.
+    pub fn halt_when_extinct(&self) -> bool {
+        self.halt_when_extinct
+    }
+
+    /// Set whether stepping a game in which every cell is dead leaves the
+    /// generation unchanged. By default, the generation keeps counting, as if
+    /// the empty board were evolving.
+    pub fn set_halt_when_extinct(&mut self, halt: bool) {
+        self.halt_when_extinct = halt;
+    }
+
+    /// How the next generation is computed.
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
+
+    /// Set how the next generation is computed.
+    pub fn set_backend(&mut self, backend: Backend) {
+        self.backend = backend;
+    }
+
+    /// The kind of set the live cells are stored in.
+    pub fn live_set(&self) -> LiveSet {
+        self.live.kind()
+    }
+
+    /// Store the live cells in another kind of set, e.g. `Ordered` right
+    /// after creating a game whose live cells must come out in order. The
+    /// live cells and everything else about the game stay the same.
+    pub fn set_live_set(&mut self, kind: LiveSet) {
+        if kind != self.live.kind() {
+            self.live = LiveCells::new(kind, self.live.iter().copied().collect());
+        }
+    }
+
+    /// How the sparse backend finds the next generation.
+    pub fn step_algorithm(&self) -> StepAlgorithm {
+        self.step_algorithm
+    }
+
+    /// Set how the sparse backend finds the next generation. Has no effect
+    /// on the dense backend.
+    pub fn set_step_algorithm(&mut self, step_algorithm: StepAlgorithm) {
+        self.step_algorithm = step_algorithm;
+    }
+
+    /// Whether every cell in the game is dead. An empty board stays empty, so
+    /// stepping it changes nothing but the generation.
+    pub fn is_extinct(&self) -> bool {
+        self.live.is_empty()
+    }
+
+    /// Count a generation of an extinct game, unless configured to halt.
+    fn step_extinct(&mut self) -> StepOutcome {
+        if !self.halt_when_extinct {
+            self.generation += 1;
+        }
+        StepOutcome::Extinct
+    }
+
+    /// Descriptive information about the pattern the game was loaded from.
+    pub fn meta(&self) -> &PatternMeta {
+        &self.meta
+    }
+
+    /// Replace the descriptive information about the game's pattern, e.g.
+    /// to name a pattern whose file gave no name.
+    pub fn set_meta(&mut self, meta: PatternMeta) {
+        self.meta = meta;
+    }
+
+    /// The rule used to evolve the game.
+    pub fn rule(&self) -> RuleString {
+        self.rule
+    }
+
+    /// Change the rule used to evolve the game.
+    pub fn set_rule(&mut self, rule: RuleString) {
+        self.rule = rule;
+    }
+
+    /// How neighbors are found for cells on the edges of the board, or
+    /// `None` if the axes have different modes, e.g. on a cylinder; see
+    /// `boundary_rows` and `boundary_cols`.
+    pub fn boundary(&self) -> Option<BoundaryMode> {
+        Some(self.boundary_rows).filter(|rows| *rows == self.boundary_cols)
+    }
+
+    /// Change how neighbors are found for cells on every edge of the board.
+    pub fn set_boundary(&mut self, boundary: BoundaryMode) {
+        self.boundary_rows = boundary;
+        self.boundary_cols = boundary;
+    }
+
+    /// How neighbors are found past the top and bottom edges of the board,
+    /// i.e. along each column.
+    pub fn boundary_rows(&self) -> BoundaryMode {
+        self.boundary_rows
+    }
+
+    /// How neighbors are found past the left and right edges of the board,
+    /// i.e. along each row.
+    pub fn boundary_cols(&self) -> BoundaryMode {
+        self.boundary_cols
+    }
+
+    /// Change how neighbors are found past the top and bottom edges only,
+    /// e.g. `Dead` with toroidal columns for a cylinder that wraps from left
+    /// to right.
+    pub fn set_boundary_rows(&mut self, boundary: BoundaryMode) {
+        self.boundary_rows = boundary;
+    }
+
+    /// Change how neighbors are found past the left and right edges only.
+    pub fn set_boundary_cols(&mut self, boundary: BoundaryMode) {
+        self.boundary_cols = boundary;
+    }
+
+    /// Which of the surrounding cells count as a cell's neighbors.
+    pub fn neighborhood(&self) -> Neighborhood {
+        self.neighborhood
+    }
+
+    /// Change which of the surrounding cells count as a cell's neighbors.
+    pub fn set_neighborhood(&mut self, neighborhood: Neighborhood) {
+        self.neighborhood = neighborhood;
+    }
+
+    /// The region that stepping is restricted to, if any.
+    pub fn active_region(&self) -> Option<Region> {
+        self.active_region
+    }
+
+    /// Restrict stepping to a region of the board, or remove the restriction
+    /// with `None`. Cells outside the region are treated as static: they keep
+    /// their state and are never born.
+    ///
+    /// This is only an approximation of the full game, valid as long as no
+    /// activity escapes the region. To keep it valid, whenever a live cell
+    /// reaches the edge of the region after a step, the region grows on that
+    /// side, up to the edge of the board (or across the whole board on that
+    /// axis, if the axis is toroidal).
+    pub fn set_active_region(&mut self, region: Option<Region>) {
+        self.active_region = region.map(|region| {
+            let (r, c) = (region.r.min(self.rows), region.c.min(self.cols));
+            Region { r, c, rows: region.rows.min(self.rows - r), cols: region.cols.min(self.cols - c) }
+        });
+        self.grow_active_region();
+    }
+
+    /// Grow the active region on each side where a live cell reached its edge.
+    fn grow_active_region(&mut self) {
+        let Some(region) = self.active_region else {
+            return;
+        };
+        let (bottom, right) = (region.r + region.rows, region.c + region.cols);
+        let (mut top_edge, mut bottom_edge, mut left_edge, mut right_edge) = (false, false, false, false);
+        for cell in self.live.iter().filter(|cell| region.contains(**cell)) {
+            top_edge |= cell.r == region.r;
+            bottom_edge |= cell.r + 1 == bottom;
+            left_edge |= cell.c == region.c;
+            right_edge |= cell.c + 1 == right;
+        }
+
+        let mut r = if top_edge { region.r.saturating_sub(ACTIVE_REGION_GROWTH) } else { region.r };
+        let mut c = if left_edge { region.c.saturating_sub(ACTIVE_REGION_GROWTH) } else { region.c };
+        let mut bottom = if bottom_edge { (bottom + ACTIVE_REGION_GROWTH).min(self.rows) } else { bottom };
+        let mut right = if right_edge { (right + ACTIVE_REGION_GROWTH).min(self.cols) } else { right };
+
+        // On a toroidal axis, activity at an edge of the board wraps to the
+        // opposite edge, so the region must span the whole axis.
+        if self.boundary_rows == BoundaryMode::Toroidal
+            && ((top_edge && region.r == 0) || (bottom_edge && region.r + region.rows == self.rows))
+        {
+            (r, bottom) = (0, self.rows);
+        }
+        if self.boundary_cols == BoundaryMode::Toroidal
+            && ((left_edge && region.c == 0) || (right_edge && region.c + region.cols == self.cols))
+        {
+            (c, right) = (0, self.cols);
+        }
+        self.active_region = Some(Region { r, c, rows: bottom - r, cols: right - c });
+    }
+
+    fn in_active_region(&self, cell: Cell) -> bool {
+        self.active_region.is_none_or(|region| region.contains(cell))
     }
 
     /// Generate a game of a given size from a pattern file, centering the
     /// pattern in the middle of the game space.
-    pub fn from_file(path: &str, rows: usize, cols: usize) -> GameOfLife {
-        let contents = fs::read_to_string(path).expect("error reading file");
-
-        let chars = "chars";
-        let coords = "coords";
-        if contents.starts_with(chars) {
-            Self::parse_chars(&contents, rows, cols)
-        } else if contents.starts_with(coords) {
-            Self::parse_coords(&contents, rows, cols)
-        } else {
-            panic!("error parsing file");
+    pub fn from_file(path: &str, rows: usize, cols: usize) -> Result<GameOfLife, PatternError> {
+        Self::from_file_with(path, rows, cols, &PatternOptions::default())
+    }
+
+    /// Generate a game from a pattern file that is exactly as large as the
+    /// pattern, e.g. to convert it to another format. The autofit setting
+    /// of `options` is ignored.
+    pub fn from_file_fitted(path: &str, options: &PatternOptions) -> Result<GameOfLife, PatternError> {
+        let mut pattern = Self::read_pattern(path, options)?;
+        if let Some(scale) = options.scale.filter(|scale| *scale > 1) {
+            pattern = pattern.scaled(scale, usize::MAX, usize::MAX)?;
+        }
+        let (rows, cols) = pattern.size();
+        pattern.into_game(rows, cols)
+    }
+
+    /// Generate a game from a pattern file like `from_file`, but if the
+    /// pattern plus a margin of dead cells does not fit in `rows` by `cols`,
+    /// grow the game to fit it instead of failing. The game grows to at most
+    /// `max_rows` by `max_cols`; patterns that do not fit even then are still
+    /// too large.
+    pub fn from_file_autofit(
+        path: &str, rows: usize, cols: usize, max_rows: usize, max_cols: usize
+    ) -> Result<GameOfLife, PatternError> {
+        let options = PatternOptions { autofit: Some((max_rows, max_cols)), ..PatternOptions::default() };
+        Self::from_file_with(path, rows, cols, &options)
+    }
+
+    /// Generate a game from a pattern file like `from_file`, with settings
+    /// for how the file is read and how the game is sized.
+    pub fn from_file_with(
+        path: &str, rows: usize, cols: usize, options: &PatternOptions
+    ) -> Result<GameOfLife, PatternError> {
+        let pattern = Self::read_pattern(path, options)?;
+        Self::place_pattern(pattern, rows, cols, options)
+    }
+
+    /// Generate a game from live cells written out as text, like `0,1 1,1
+    /// 2,1`, centering them like a coords file, e.g. to try a small pattern
+    /// without writing a file. Cells are separated by whitespace, and each
+    /// must be two whole numbers separated by a comma, in the coordinate
+    /// order of `options`.
+    pub fn from_coords(
+        coords: &str, rows: usize, cols: usize, options: &PatternOptions
+    ) -> Result<GameOfLife, PatternError> {
+        let re = Regex::new(r"^\d+,\d+$").unwrap();
+        if let Some(invalid) = coords.split_whitespace().find(|coord| !re.is_match(coord)) {
+            return Err(PatternError::InvalidCoord(invalid.to_string()));
+        }
+        let pattern = Self::parse_coords(coords, options.coord_order)?;
+        Self::place_pattern(pattern, rows, cols, options)
+    }
+
+    /// Scale a pattern and place it in the middle of a game, growing the game
+    /// to fit if `options` allow.
+    fn place_pattern(
+        mut pattern: Pattern, rows: usize, cols: usize, options: &PatternOptions
+    ) -> Result<GameOfLife, PatternError> {
+        if let Some(scale) = options.scale.filter(|scale| *scale > 1) {
+            let (max_rows, max_cols) = match options.autofit {
+                Some((max_rows, max_cols)) => (rows.max(max_rows), cols.max(max_cols)),
+                None => (rows, cols),
+            };
+            pattern = pattern.scaled(scale, max_rows, max_cols)?;
+        }
+        match options.autofit {
+            Some((max_rows, max_cols)) => {
+                let (pattern_rows, pattern_cols) = pattern.size();
+                let fit = |len: usize, pattern_len: usize, max_len: usize| {
+                    len.max((pattern_len + 2 * AUTOFIT_MARGIN).min(max_len))
+                };
+                pattern.into_game(fit(rows, pattern_rows, max_rows), fit(cols, pattern_cols, max_cols))
+            },
+            None => pattern.into_game(rows, cols),
         }
     }
 
-    fn parse_chars(file_contents: &str, rows: usize, cols: usize) -> GameOfLife {
+    fn read_pattern(path: &str, options: &PatternOptions) -> Result<Pattern, PatternError> {
+        let contents = fs::read_to_string(path)?;
+        (detect_format(&contents)?.parse)(&contents, options)
+    }
+
+    fn parse_chars(file_contents: &str) -> Result<Pattern, PatternError> {
         let re = Regex::new(r"\{(?P<dead>.)(?P<alive>.)\}").unwrap();
-        let chars = re.captures(file_contents).unwrap();
+        let chars = re.captures(file_contents).ok_or(PatternError::MissingChars)?;
         let dead = chars.name("dead").unwrap().as_str().chars().next().unwrap();
         let alive = chars.name("alive").unwrap().as_str().chars().next().unwrap();
 
-        let re = Regex::new(&format!("(?m)^[{}{}]+", dead, alive)).unwrap();
-        let lines = re.find_iter(file_contents);
+        // Rows may be ragged, with missing cells on the right dead; blank lines
+        // between rows are rows of dead cells.
+        let (dead_class, alive_class) = (regex::escape(&dead.to_string()), regex::escape(&alive.to_string()));
+        let re = Regex::new(&format!("^[{}{}]+", dead_class, alive_class)).unwrap();
+        let mut rows = Vec::new();
+        let mut blank_lines = 0;  // blank lines since the last row
+        for line in file_contents.lines() {
+            if let Some(row) = re.find(line) {
+                if !rows.is_empty() {
+                    rows.resize(rows.len() + blank_lines, "");
+                }
+                rows.push(row.as_str());
+                blank_lines = 0;
+            } else if line.trim().is_empty() {
+                blank_lines += 1;
+            }
+        }
+
         let mut live = HashSet::new();
-        for (r, line) in lines.enumerate() {
-            for (c, char) in line.as_str().chars().enumerate() {
+        for (r, row) in rows.iter().enumerate() {
+            for (c, char) in row.chars().enumerate() {
                 if char == alive {
                     live.insert(Cell { r, c });
                 }
             }
         }
+        let cols = rows.iter().map(|row| row.chars().count()).max().unwrap_or(0);
 
-        live = Self::center_pattern(&live, rows, cols);
+        Ok(Pattern { live, drawn: (rows.len(), cols), rule: None, meta: PatternMeta::default() })
+    }
 
-        GameOfLife { rows, cols, live }
+    /// Read a plaintext pattern, as used by the LifeWiki: `!` lines are
+    /// comments, the first starting with `!Name:` naming the pattern and the
+    /// first starting with `!Author:` giving its author, and every other line
+    /// is a row of dead (`.`) and live (`O`) cells.
+    fn parse_cells(file_contents: &str) -> Result<Pattern, PatternError> {
+        let mut meta = PatternMeta::default();
+        let mut live = HashSet::new();
+        let (mut rows, mut cols) = (0, 0);
+        for line in file_contents.lines() {
+            if let Some(comment) = line.strip_prefix('!') {
+                let comment = comment.trim();
+                if let (Some(name), None) = (comment.strip_prefix("Name:"), &meta.name) {
+                    meta.name = Some(name.trim().to_string());
+                } else if let (Some(author), None) = (comment.strip_prefix("Author:"), &meta.author) {
+                    meta.author = Some(author.trim().to_string());
+                } else if !comment.is_empty() {
+                    meta.comments.push(comment.to_string());
+                }
+                continue;
+            }
+            let row = line.trim_end();
+            for (c, char) in row.chars().enumerate() {
+                if char == 'O' {
+                    live.insert(Cell { r: rows, c });
+                }
+            }
+            cols = cols.max(row.chars().count());
+            rows += 1;
+        }
+
+        Ok(Pattern { live, drawn: (rows, cols), rule: None, meta })
     }
 
-    fn parse_coords(file_contents: &str, rows: usize, cols: usize) -> GameOfLife {
+    fn parse_coords(file_contents: &str, order: CoordOrder) -> Result<Pattern, PatternError> {
         let re = Regex::new(r"\d+,\d+").unwrap();
         let coords = re.find_iter(file_contents);
         
         let mut live = HashSet::new();
         for coord in coords {
-            let mut coord_iter = coord.as_str().split(",");
-            let (r, c) = (coord_iter.next().unwrap(), coord_iter.next().unwrap());
-            let (r, c) = (r.parse::<usize>().unwrap(), c.parse::<usize>().unwrap());
+            let invalid = || PatternError::InvalidCoord(coord.as_str().to_string());
+            let (r, c) = coord.as_str().split_once(',').unwrap();
+            let (r, c) = (r.parse::<usize>().map_err(|_| invalid())?, c.parse::<usize>().map_err(|_| invalid())?);
+            let (r, c) = match order {
+                CoordOrder::RowCol => (r, c),
+                CoordOrder::XY => (c, r),
+            };
             live.insert(Cell { r, c });
         }
 
-        live = Self::center_pattern(&live, rows, cols);
+        Ok(Pattern { live, drawn: (0, 0), rule: None, meta: PatternMeta::default() })
+    }
+
+    fn parse_rle(file_contents: &str) -> Result<Pattern, PatternError> {
+        let mut meta = PatternMeta::default();
+        let mut lines = file_contents.lines().map(str::trim);
 
-        GameOfLife { rows, cols, live }
+        // Collect metadata from comment lines until the header is reached.
+        let header = loop {
+            let line = lines.next().ok_or(PatternError::MissingHeader)?;
+            match line.get(..2) {
+                Some("#N") => meta.name = Some(line[2..].trim().to_string()),
+                Some("#O") => meta.author = Some(line[2..].trim().to_string()),
+                Some("#C" | "#c") => meta.comments.push(line[2..].trim().to_string()),
+                _ if line.starts_with('#') || line.is_empty() => (),
+                _ => break line,
+            }
+        };
+        if !Regex::new(r"\Ax\s*=").unwrap().is_match(header) {
+            return Err(PatternError::MissingHeader);
+        }
+        let re = Regex::new(r"rule\s*=\s*(?P<rule>[^,\s]+)").unwrap();
+        let rule = match re.captures(header) {
+            Some(captures) => Some(captures["rule"].parse::<RuleString>().map_err(PatternError::InvalidRule)?),
+            None => None,
+        };
+
+        // Decode runs of cells, e.g. `3o` for three live cells.
+        let mut live = HashSet::new();
+        let (mut r, mut c) = (0, 0);
+        let mut run = String::new();
+        'body: for line in lines {
+            for char in line.chars() {
+                let count = || run.parse::<usize>().unwrap_or(1);
+                match char {
+                    '0'..='9' => { run.push(char); continue; },
+                    'b' | '.' => c += count(),
+                    '$' => { r += count(); c = 0; },
+                    '!' => break 'body,
+                    char if char.is_ascii_alphabetic() => {
+                        for _ in 0..count() {
+                            live.insert(Cell { r, c });
+                            c += 1;
+                        }
+                    },
+                    _ => continue,
+                }
+                run.clear();
+            }
+        }
+
+        Ok(Pattern { live, drawn: (0, 0), rule, meta })
     }
 
-    fn center_pattern(pattern: &HashSet<Cell>, rows: usize, cols: usize) -> HashSet<Cell> {
-        let (mut max_r, mut max_c) = (0, 0);
-        for cell in pattern.iter() {
-            max_r = max(max_r, cell.r);
-            max_c = max(max_c, cell.c);
+    /// Evolve one generation in the game. Stepping an extinct game does
+    /// nothing, and returns `StepOutcome::Extinct` so callers can tell;
+    /// see `set_halt_when_extinct` for whether the generation still counts.
+    pub fn step(&mut self) -> StepOutcome {
+        if self.is_extinct() {
+            return self.step_extinct();
         }
+        let next_live = self.next_live();
+        self.advance(next_live);
+        StepOutcome::Stepped
+    }
 
-        let r_shift = (rows - max_r) / 2;
-        let c_shift = (cols - max_c) / 2;
+    /// Get the next generation of the game as a new game, leaving this game
+    /// unchanged.
+    pub fn stepped(&self) -> GameOfLife {
+        if self.is_extinct() {
+            let mut next = self.clone();
+            next.step_extinct();
+            return next;
+        }
+        let next_live = self.next_live();
+        let mut next = GameOfLife {
+            rows: self.rows,
+            cols: self.cols,
+            ages: self.next_ages(&next_live),
+            live: LiveCells::new(self.live.kind(), next_live),
+            frozen: self.frozen.clone(),
+            rule: self.rule,
+            boundary_rows: self.boundary_rows,
+            boundary_cols: self.boundary_cols,
+            neighborhood: self.neighborhood,
+            meta: self.meta.clone(),
+            active_region: self.active_region,
+            generation: self.generation + 1,
+            halt_when_extinct: self.halt_when_extinct,
+            backend: self.backend,
+            step_algorithm: self.step_algorithm,
+        };
+        next.grow_active_region();
+        next
+    }
 
-        let mut centered = HashSet::new();
-        for cell in pattern.iter() {
-            centered.insert(Cell { r: cell.r + r_shift, c: cell.c + c_shift });
+    /// Flip the state of each cell with probability `rate`, clamped to
+    /// between 0 and 1; NaN flips nothing. The same `seed` always flips the
+    /// same cells. Frozen cells are never flipped.
+    pub fn mutate(&mut self, rate: f64, seed: u64) {
+        let rate = probability(rate);
+        let mut rng = StdRng::seed_from_u64(seed);
+        for r in 0..self.rows {
+            for c in 0..self.cols {
+                let cell = Cell { r, c };
+                if rng.gen_bool(rate) && !self.frozen.contains(&cell) {
+                    if self.live.remove(&cell) {
+                        self.ages.remove(&cell);
+                    } else {
+                        self.live.insert(cell);
+                        self.ages.insert(cell, 1);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Randomly bring cells to life where `mask` is `true`, each with
+    /// probability `density`, clamped like `mutate`'s rate. Cells where the
+    /// mask is `false` are left as they are, so the mask gives the shape of
+    /// the random soup, e.g. a circle. The same `seed` always seeds the same
+    /// cells. Frozen cells stay dead.
+    ///
+    /// The mask is given in row-major order and must have the same
+    /// dimensions as the game.
+    pub fn random_masked(&mut self, mask: &[Vec<bool>], density: f64, seed: u64) -> Result<(), GameError> {
+        let cols = mask.first().map_or(0, Vec::len);
+        if mask.len() != self.rows || cols != self.cols {
+            return Err(GameError::MaskSize { expected: (self.rows, self.cols), found: (mask.len(), cols) });
         }
-        centered
+        if let Some((row, found)) = mask.iter().map(Vec::len).enumerate().find(|(_, len)| *len != cols) {
+            return Err(GameError::RaggedGrid { row, expected: cols, found });
+        }
+
+        let density = probability(density);
+        let mut rng = StdRng::seed_from_u64(seed);
+        for (r, row) in mask.iter().enumerate() {
+            for (c, seedable) in row.iter().enumerate() {
+                let cell = Cell { r, c };
+                if *seedable && rng.gen_bool(density) && !self.frozen.contains(&cell) && self.live.insert(cell) {
+                    self.ages.insert(cell, 1);
+                }
+            }
+        }
+        Ok(())
     }
 
-    /// Evolve one generation in the game.
-    pub fn step(&mut self) {
+    /// Evolve `n` generations in the game. Since an empty board stays empty,
+    /// stepping stops early if every cell dies.
+    pub fn step_n(&mut self, n: usize) {
+        for i in 0..n {
+            if self.is_extinct() {
+                if !self.halt_when_extinct {
+                    self.generation += u128::try_from(n - i).unwrap();
+                }
+                break;
+            }
+            self.step();
+        }
+    }
+
+    /// Evolve `generations` generations without any rendering or other I/O,
+    /// returning the final population. Intended for benchmarking and for
+    /// embedding the simulation in other programs.
+    pub fn run(&mut self, generations: usize) -> usize {
+        self.step_n(generations);
+        self.population()
+    }
+
+    /// Evolve one generation in the game, returning the cells that changed.
+    pub fn step_delta(&mut self) -> StepDelta {
+        if self.is_extinct() {
+            self.step_extinct();
+            return StepDelta::default();
+        }
+        let next_live = self.next_live();
+        let delta = StepDelta {
+            born: next_live.iter().copied().filter(|cell| !self.live.contains(cell)).collect(),
+            died: self.live.iter().copied().filter(|cell| !next_live.contains(cell)).collect(),
+        };
+        self.advance(next_live);
+        delta
+    }
+
+    /// The cells the next step would bring to life and kill, each sorted by
+    /// row then column, without changing the game. These are exactly the
+    /// cells `step` would change, under the same rule, boundary,
+    /// neighborhood, backend, and active region; an extinct game changes no
+    /// cells.
+    pub fn births_and_deaths(&self) -> (Vec<Cell>, Vec<Cell>) {
+        if self.is_extinct() {
+            return (Vec::new(), Vec::new());
+        }
+        let next_live = self.next_live();
+        let mut births: Vec<Cell> = next_live.iter().copied().filter(|cell| !self.live.contains(cell)).collect();
+        let mut deaths: Vec<Cell> = self.live.iter().copied().filter(|cell| !next_live.contains(cell)).collect();
+        births.sort_unstable_by_key(|cell| (cell.r, cell.c));
+        deaths.sort_unstable_by_key(|cell| (cell.r, cell.c));
+        (births, deaths)
+    }
+
+    /// Advance one generation by applying the cells that changed, e.g. as
+    /// recorded from `step_delta`. The cells born must be dead and the cells
+    /// that died must be alive.
+    pub(crate) fn apply_delta(&mut self, delta: &StepDelta) {
+        let mut next_live: HashSet<Cell> = self.live.iter().copied().collect();
+        for cell in delta.died.iter() {
+            next_live.remove(cell);
+        }
+        next_live.extend(delta.born.iter().copied());
+        self.advance(next_live);
+    }
+
+    /// Replace the live cells with those of the next generation.
+    fn advance(&mut self, next_live: HashSet<Cell>) {
+        self.ages = self.next_ages(&next_live);
+        self.live = LiveCells::new(self.live.kind(), next_live);
+        self.grow_active_region();
+        self.generation += 1;
+    }
+
+    /// The ages of the cells of the next generation: surviving cells grow one
+    /// generation older, and newborn cells start at 1.
+    fn next_ages(&self, next_live: &HashSet<Cell>) -> HashMap<Cell, u32> {
+        next_live.iter()
+            .map(|cell| (*cell, self.ages.get(cell).map_or(1, |age| age.saturating_add(1))))
+            .collect()
+    }
+
+    fn next_live(&self) -> HashSet<Cell> {
+        // The dense stepper counts a cell that is reached by wrapping in more
+        // than one direction more than once, so boards narrower than 3 cells
+        // on a toroidal axis always use the sparse stepper.
+        let narrow_wrap = (self.rows < 3 && self.boundary_rows == BoundaryMode::Toroidal)
+            || (self.cols < 3 && self.boundary_cols == BoundaryMode::Toroidal);
+        let mut next = match self.backend {
+            Backend::Dense if !narrow_wrap => self.next_live_dense(),
+            _ => self.next_live_sparse(),
+        };
+        // Frozen cells still count as dead neighbors, but are never born.
+        if !self.frozen.is_empty() {
+            next.retain(|cell| !self.frozen.contains(cell));
+        }
+        next
+    }
+
+    fn next_live_dense(&self) -> HashSet<Cell> {
+        let next = DenseGrid::from_live(self.rows, self.cols, self.live.iter())
+            .step(self.rule, self.boundary_rows, self.boundary_cols, self.neighborhood)
+            .live_cells();
+        match self.active_region {
+            // Cells outside the active region keep their state.
+            Some(region) => next.into_iter()
+                .filter(|cell| region.contains(*cell))
+                .chain(self.live.iter().copied().filter(|cell| !region.contains(*cell)))
+                .collect(),
+            None => next,
+        }
+    }
+
+    fn next_live_sparse(&self) -> HashSet<Cell> {
+        // Counting out from live cells needs each cell to be a neighbor of
+        // its neighbors, which mirrored and repeated cells break.
+        let symmetric = |boundary: BoundaryMode, len: usize| match boundary {
+            BoundaryMode::Toroidal => len >= 3,
+            BoundaryMode::Dead => true,
+            BoundaryMode::Reflect => false,
+        };
+        let symmetric = symmetric(self.boundary_rows, self.rows) && symmetric(self.boundary_cols, self.cols);
+        match self.step_algorithm {
+            StepAlgorithm::NeighborCount if symmetric => self.next_live_counted(),
+            _ => self.next_live_scanned(),
+        }
+    }
+
+    /// Find the next generation by counting neighbors out from every live
+    /// cell, see `StepAlgorithm::NeighborCount`.
+    fn next_live_counted(&self) -> HashSet<Cell> {
+        let mut counts: HashMap<Cell, u8> = HashMap::with_capacity(self.live.len() * 4);
+        for cell in self.live.iter() {
+            for neighbor in self.neighbor_cells(*cell) {
+                *counts.entry(neighbor).or_insert(0) += 1;
+            }
+        }
+
+        let mut next_live: HashSet<Cell> = self.live.iter()
+            .copied()
+            .filter(|cell| {
+                !self.in_active_region(*cell) || self.rule.survives(counts.get(cell).map_or(0, |count| usize::from(*count)))
+            })
+            .collect();
+        for (cell, count) in counts {
+            if !self.live.contains(&cell) && self.in_active_region(cell) && self.rule.born(usize::from(count)) {
+                next_live.insert(cell);
+            }
+        }
+        next_live
+    }
+
+    /// Find the next generation by scanning around every live cell, see
+    /// `StepAlgorithm::ScanMemo`.
+    fn next_live_scanned(&self) -> HashSet<Cell> {
         let mut next_live = HashSet::new();
         let mut dead_memo = HashSet::new();
 
-        for cell in self.live.iter().copied() {
-            self.scan_live(&cell, &mut next_live, &mut dead_memo);
+        for cell in self.live.iter() {
+            if self.in_active_region(*cell) {
+                self.scan_live(cell, &mut next_live, &mut dead_memo);
+            } else {
+                next_live.insert(*cell);
+            }
         }
 
-        self.live = next_live;
+        next_live
     }
 
     fn scan_live(&self, cell: &Cell, next_live: &mut HashSet<Cell>, dead_memo: &mut HashSet<Cell>) {
         let mut live_neighbors = 0;
 
-        let (neighbor_r, neighbor_c) = self.range_wrap(cell.r, cell.c);
-        for r in neighbor_r.iter().copied() {
-            for c in neighbor_c.iter().copied() {
-                let neighbor = Cell { r, c };
-                if *cell == neighbor {
-                    continue;
-                }
-                if self.is_live(&neighbor) {
+        // Dead cells are looked for among all eight surrounding cells: on a
+        // reflecting board, a dead cell can count a live cell as a `Hex`
+        // neighbor without being one of the live cell's `Hex` neighbors.
+        for (dr, dc, neighbor) in self.surrounding_cells(*cell) {
+            if self.is_alive(neighbor) {
+                if self.neighborhood.includes(dr, dc) {
                     live_neighbors += 1;
-                } else if !dead_memo.contains(&neighbor) {
-                    self.scan_dead(&neighbor, next_live);
-                    dead_memo.insert(neighbor);
                 }
+            } else if !dead_memo.contains(&neighbor) && self.in_active_region(neighbor) {
+                self.scan_dead(&neighbor, next_live);
+                dead_memo.insert(neighbor);
             }
         }
 
-        match live_neighbors {
-            2 | 3 => {next_live.insert(*cell);},
-            _ => (),
+        if self.rule.survives(live_neighbors) {
+            next_live.insert(*cell);
         }
     }
 
     fn scan_dead(&self, cell: &Cell, next_live: &mut HashSet<Cell>) {
-        let mut live_neighbors = 0;
+        if self.rule.born(usize::from(self.count_live_neighbors(*cell))) {
+            next_live.insert(*cell);
+        }
+    }
 
-        let (neighbor_r, neighbor_c) = self.range_wrap(cell.r, cell.c);
-        for r in neighbor_r.iter().copied() {
-            for c in neighbor_c.iter().copied() {
-                let neighbor = Cell { r, c };
-                if *cell == neighbor {
-                    continue;
+    /// Get the rows and columns adjacent to (and including) a cell. Entries are
+    /// `None` where the neighbor lies beyond an edge of a dead board.
+    fn neighbor_range(&self, r: usize, c: usize) -> ([Option<usize>; 3], [Option<usize>; 3]) {
+        (
+            self.axis_range(r, self.rows, self.boundary_rows),
+            self.axis_range(c, self.cols, self.boundary_cols),
+        )
+    }
+
+    /// Get the indices adjacent to (and including) `i` on an axis of length
+    /// `len` with the given boundary mode. On axes shorter than 3 that wrap
+    /// around, the indices before and after `i` can coincide with each other
+    /// or with `i`, so repeats are left out to avoid counting a neighbor more
+    /// than once. Mirrored indices are kept even if they repeat, since each
+    /// stands in for a different cell beyond the edge.
+    fn axis_range(&self, i: usize, len: usize, boundary: BoundaryMode) -> [Option<usize>; 3] {
+        let last = len - 1;
+        match boundary {
+            BoundaryMode::Toroidal => {
+                let before = Some(if i == 0 { last } else { i - 1 }).filter(|before| *before != i);
+                let mut after = Some(if i == last { 0 } else { i + 1 });
+                if after == Some(i) || after == before {
+                    after = None;
                 }
-                if self.is_live(&neighbor) {
-                    live_neighbors += 1;
+                [before, Some(i), after]
+            },
+            BoundaryMode::Dead => [
+                if i == 0 { None } else { Some(i - 1) },
+                Some(i),
+                if i == last { None } else { Some(i + 1) },
+            ],
+            BoundaryMode::Reflect if len == 1 => [None, Some(i), None],
+            BoundaryMode::Reflect => [
+                Some(if i == 0 { 1 } else { i - 1 }),
+                Some(i),
+                Some(if i == last { last - 1 } else { i + 1 }),
+            ],
+        }
+    }
+
+    /// The cells surrounding a cell, not including the cell itself, with the
+    /// row and column offsets each was reached by.
+    fn surrounding_cells(&self, cell: Cell) -> impl Iterator<Item = (isize, isize, Cell)> {
+        let (neighbor_r, neighbor_c) = self.neighbor_range(cell.r, cell.c);
+        (-1..=1).zip(neighbor_r)
+            .flat_map(move |(dr, r)| (-1..=1).zip(neighbor_c).map(move |(dc, c)| (dr, r, dc, c)))
+            .filter_map(|(dr, r, dc, c)| Some((dr, dc, Cell { r: r?, c: c? })))
+            .filter(move |(_, _, neighbor)| *neighbor != cell)
+    }
+
+    /// The cells in a cell's neighborhood, not including the cell itself.
+    fn neighbor_cells(&self, cell: Cell) -> impl Iterator<Item = Cell> + '_ {
+        self.surrounding_cells(cell)
+            .filter(|(dr, dc, _)| self.neighborhood.includes(*dr, *dc))
+            .map(|(_, _, neighbor)| neighbor)
+    }
+
+    /// Get the cells adjacent to a cell, not including the cell itself. Each
+    /// axis follows its own boundary mode. On a toroidal axis, neighbors wrap
+    /// around the edges; on small boards, a cell reached by wrapping in more
+    /// than one direction is only included once. On a reflecting axis, a cell
+    /// is included once for each neighbor beyond the edge that mirrors it, as
+    /// well as for itself if adjacent.
+    /// With the `Hex` neighborhood, the two dropped diagonals are left out.
+    pub fn neighbors(&self, cell: Cell) -> Vec<Cell> {
+        self.neighbor_cells(cell).collect()
+    }
+
+    /// Count the live neighbors of a cell, as the rule sees them: following
+    /// the boundary mode and neighborhood, and counting a mirrored cell once
+    /// for each neighbor it stands in for on a reflecting board.
+    pub fn count_live_neighbors(&self, cell: Cell) -> u8 {
+        let count = self.neighbor_cells(cell).filter(|neighbor| self.is_alive(*neighbor)).count();
+        u8::try_from(count).unwrap()
+    }
+
+    /// Whether the given cell is currently alive.
+    pub fn is_alive(&self, cell: Cell) -> bool {
+        self.live.contains(&cell)
+    }
+
+    /// Merge the live cells of this game with those of another game of the
+    /// same dimensions, e.g. to layer patterns. The result has this game's
+    /// rule, boundaries, and other settings, and its generation; live cells
+    /// keep their age from this game, or from `other` if only alive there.
+    /// Frozen cells of this game stay dead.
+    pub fn combine(&self, other: &GameOfLife, op: SetOp) -> Result<GameOfLife, GameError> {
+        if (other.rows, other.cols) != (self.rows, self.cols) {
+            return Err(GameError::SizeMismatch {
+                expected: (self.rows, self.cols),
+                found: (other.rows, other.cols),
+            });
+        }
+        let ours: HashSet<Cell> = self.live.iter().copied().collect();
+        let theirs: HashSet<Cell> = other.live.iter().copied().collect();
+        let live: HashSet<Cell> = match op {
+            SetOp::Union => ours.union(&theirs).copied().collect(),
+            SetOp::Intersection => ours.intersection(&theirs).copied().collect(),
+            SetOp::Difference => ours.difference(&theirs).copied().collect(),
+            SetOp::Xor => ours.symmetric_difference(&theirs).copied().collect(),
+        };
+        let live: HashSet<Cell> = live.into_iter().filter(|cell| !self.frozen.contains(cell)).collect();
+        let ages = live.iter()
+            .map(|cell| (*cell, self.ages.get(cell).or_else(|| other.ages.get(cell)).copied().unwrap_or(1)))
+            .collect();
+
+        let mut combined = self.clone();
+        combined.live = LiveCells::new(self.live.kind(), live);
+        combined.ages = ages;
+        Ok(combined)
+    }
+
+    /// The cells that differ between this game and another of the same
+    /// dimensions, as the change from this board to the other: cells alive
+    /// only in `other` are `born`, and cells alive only in this game `died`.
+    /// Both lists are sorted by row, then column.
+    pub fn diff(&self, other: &GameOfLife) -> Result<StepDelta, GameError> {
+        let died = self.combine(other, SetOp::Difference)?.live_cells_sorted();
+        let born = other.combine(self, SetOp::Difference)?.live_cells_sorted();
+        Ok(StepDelta { born, died })
+    }
+
+    /// Move every live cell `dr` rows down and `dc` columns right, scrolling
+    /// the whole world; negative amounts move up and left. On a toroidal axis
+    /// cells wrap around the edges, so none are lost. On bounded axes there
+    /// is nothing beyond the edges to scroll in, and cells moved past an edge
+    /// are lost. Frozen cells stay where they are, so cells moved onto them
+    /// are lost too. Cells keep their ages.
+    pub fn shift(&mut self, dr: isize, dc: isize) {
+        let moved = |i: usize, d: isize, len: usize, boundary: BoundaryMode| -> Option<usize> {
+            let (i, len) = (isize::try_from(i).unwrap(), isize::try_from(len).unwrap());
+            match boundary == BoundaryMode::Toroidal {
+                true => usize::try_from((i + d.rem_euclid(len)) % len).ok(),
+                false => i.checked_add(d).filter(|i| (0..len).contains(i)).map(|i| i as usize),
+            }
+        };
+
+        let mut live = HashSet::with_capacity(self.live.len());
+        let mut ages = HashMap::with_capacity(self.ages.len());
+        for cell in self.live.iter() {
+            let r = moved(cell.r, dr, self.rows, self.boundary_rows);
+            if let (Some(r), Some(c)) = (r, moved(cell.c, dc, self.cols, self.boundary_cols)) {
+                if !self.frozen.contains(&Cell { r, c }) {
+                    live.insert(Cell { r, c });
+                    ages.insert(Cell { r, c }, self.ages.get(cell).copied().unwrap_or(1));
                 }
             }
         }
+        self.live = LiveCells::new(self.live.kind(), live);
+        self.ages = ages;
+    }
 
-        match live_neighbors {
-            3 => {next_live.insert(*cell);},
-            _ => (),
+    /// Bring a cell to life or kill it, e.g. when drawing on the board. A cell
+    /// brought to life starts at age 1; a cell that is already in the given
+    /// state is unchanged, and a frozen cell cannot be brought to life.
+    pub fn set_alive(&mut self, cell: Cell, alive: bool) {
+        if alive && self.frozen.contains(&cell) {
+            return;
+        }
+        if alive {
+            if self.live.insert(cell) {
+                self.ages.insert(cell, 1);
+            }
+        } else if self.live.remove(&cell) {
+            self.ages.remove(&cell);
         }
     }
 
-    fn range_wrap(&self, r: usize, c: usize) -> ([usize; 3], [usize; 3]) {
-        let (r_max, c_max) = (self.rows - 1, self.cols - 1);
-        (
-            if r == 0 {
-                [r_max, 0, 1]
-            } else if r == r_max {
-                [r_max-1, r_max, 0]
-            } else {
-                [r-1, r, r+1]
-            },
+    /// Bring every given cell to life or kill it, see `set_alive`, e.g. to
+    /// draw a shape on the board.
+    pub fn set_cells(&mut self, cells: &[Cell], alive: bool) {
+        for cell in cells {
+            self.set_alive(*cell, alive);
+        }
+    }
 
-            if c == 0 {
-                [c_max, 0, 1]
-            } else if c == c_max {
-                [c_max-1, c_max, 0]
-            } else {
-                [c-1, c, c+1]
+    /// The number of consecutive generations a cell has been alive, counting
+    /// the current one, or `None` if the cell is dead.
+    pub fn age(&self, cell: Cell) -> Option<u32> {
+        self.ages.get(&cell).copied()
+    }
+
+    /// Set the number of consecutive generations a live cell has been alive,
+    /// e.g. to see how old cells are colored. Ages start at 1, so 0 is taken
+    /// as 1. Returns whether the cell was alive; dead cells are left dead,
+    /// with no age.
+    pub fn set_age(&mut self, cell: Cell, age: u32) -> bool {
+        match self.ages.get_mut(&cell) {
+            Some(current) => {
+                *current = age.max(1);
+                true
             },
-        )
+            None => false,
+        }
+    }
+
+    /// The number of cells that are currently alive in the game.
+    pub fn population(&self) -> usize {
+        self.live.len()
     }
 
-    fn is_live (&self, cell: &Cell) -> bool {
-        self.live.contains(cell)
+    /// The smallest region holding every live cell, or `None` if every cell
+    /// is dead. A pattern that wraps around the edges of a toroidal board is
+    /// not recognized as one piece, so its box spans the board.
+    pub fn bounding_box(&self) -> Option<Region> {
+        let mut cells = self.live.iter();
+        let first = cells.next()?;
+        let (mut top, mut left, mut bottom, mut right) = (first.r, first.c, first.r, first.c);
+        for cell in cells {
+            top = top.min(cell.r);
+            left = left.min(cell.c);
+            bottom = bottom.max(cell.r);
+            right = right.max(cell.c);
+        }
+        Some(Region { r: top, c: left, rows: bottom - top + 1, cols: right - left + 1 })
+    }
+
+    /// Freeze a cell dead: it is killed if alive and is never born again,
+    /// though it still counts as a dead neighbor. Freezing a cell outside
+    /// the board has no effect.
+    pub fn freeze(&mut self, cell: Cell) {
+        if cell.r < self.rows && cell.c < self.cols {
+            self.set_alive(cell, false);
+            self.frozen.insert(cell);
+        }
+    }
+
+    /// Let a frozen cell be born again.
+    pub fn thaw(&mut self, cell: Cell) {
+        self.frozen.remove(&cell);
+    }
+
+    /// Whether a cell is frozen dead, see `freeze`.
+    pub fn is_frozen(&self, cell: Cell) -> bool {
+        self.frozen.contains(&cell)
+    }
+
+    /// The number of frozen cells.
+    pub fn frozen_count(&self) -> usize {
+        self.frozen.len()
+    }
+
+    /// Get every frozen cell, sorted by row then column.
+    pub fn frozen_cells_sorted(&self) -> Vec<Cell> {
+        let mut cells: Vec<Cell> = self.frozen.iter().copied().collect();
+        cells.sort_unstable();
+        cells
+    }
+
+    /// Freeze every cell within `thickness` rows or columns of an edge of the
+    /// board, so patterns inside are boxed in even on a toroidal board: they
+    /// neither wrap around nor grow past the walls. Walls at least half as
+    /// thick as the board is wide or tall freeze every cell.
+    pub fn add_border_walls(&mut self, thickness: usize) {
+        for r in 0..self.rows {
+            for c in 0..self.cols {
+                let from_edge = r.min(self.rows - 1 - r).min(c).min(self.cols - 1 - c);
+                if from_edge < thickness {
+                    self.freeze(Cell { r, c });
+                }
+            }
+        }
+    }
+
+    /// The fewest dead rows or columns between the live cells and an edge of
+    /// the board, or `None` if every cell is dead. A pattern with a margin of
+    /// 0 touches an edge, so on a toroidal board it is next to the cells
+    /// across that edge from the first generation.
+    pub fn edge_margin(&self) -> Option<usize> {
+        let region = self.bounding_box()?;
+        let (bottom, right) = (self.rows - (region.r + region.rows), self.cols - (region.c + region.cols));
+        Some(region.r.min(region.c).min(bottom).min(right))
+    }
+
+    /// The average position of the live cells, as fractional (row, column),
+    /// or `None` if every cell is dead. Each toroidal axis is averaged
+    /// around the circle it wraps into, so a pattern straddling an
+    /// edge has its center by the edge rather than in the middle of the
+    /// board; when cells are spread evenly around an axis, that axis falls
+    /// back to the plain average.
+    pub fn centroid(&self) -> Option<(f64, f64)> {
+        if self.live.is_empty() {
+            return None;
+        }
+        let count = self.live.len() as f64;
+        let mean = |position: fn(&Cell) -> usize, len: usize, boundary: BoundaryMode| -> f64 {
+            let plain = self.live.iter().map(|cell| position(cell) as f64).sum::<f64>() / count;
+            if boundary != BoundaryMode::Toroidal {
+                return plain;
+            }
+            let turn = std::f64::consts::TAU / len as f64;
+            let (sin, cos) = self.live.iter()
+                .map(|cell| (position(cell) as f64 * turn).sin_cos())
+                .fold((0.0, 0.0), |(sin, cos), (s, c)| (sin + s, cos + c));
+            match (sin / count).hypot(cos / count) {
+                spread if spread < 1e-9 => plain,
+                _ => sin.atan2(cos).rem_euclid(std::f64::consts::TAU) / turn,
+            }
+        };
+        Some((mean(|cell| cell.r, self.rows, self.boundary_rows), mean(|cell| cell.c, self.cols, self.boundary_cols)))
+    }
+
+    /// A copy of the game with its live cells wrapped around so their
+    /// centroid is in the middle of the board, e.g. to draw a pattern
+    /// straddling the edges of a toroidal board in one piece. Only toroidal
+    /// axes are shifted, since shifting bounded ones would lose cells, so
+    /// bounded boards and empty boards are copied unchanged.
+    pub fn recentered(&self) -> GameOfLife {
+        let mut game = self.clone();
+        if let Some((r, c)) = self.centroid() {
+            let offset = |center: f64, len: usize, boundary: BoundaryMode| match boundary {
+                BoundaryMode::Toroidal => (len / 2) as isize - center.round() as isize,
+                BoundaryMode::Dead | BoundaryMode::Reflect => 0,
+            };
+            game.shift(offset(r, self.rows, self.boundary_rows), offset(c, self.cols, self.boundary_cols));
+        }
+        game
     }
 
     /// Get all cells that are currently alive in the game.
     pub fn live_cells(&self) -> Vec<Cell> {
         self.live.iter().copied().collect()
     }
+
+    /// Get the cells that are currently alive inside a region, in no
+    /// particular order unless the live cells are `Ordered`, see `LiveSet`.
+    pub fn live_cells_in(&self, region: Region) -> Vec<Cell> {
+        self.live.in_region(region)
+    }
+
+    /// Get all cells that are currently alive in the game, sorted by row then
+    /// column. Unlike `live_cells`, the order is the same across runs, for
+    /// output that must be reproducible byte for byte.
+    pub fn live_cells_sorted(&self) -> Vec<Cell> {
+        let mut cells = self.live_cells();
+        cells.sort_unstable_by_key(|cell| (cell.r, cell.c));
+        cells
+    }
+
+    /// Iterate over every cell in the game in row-major order, along with
+    /// whether it is alive. Cells are produced lazily, so this does not
+    /// allocate a grid.
+    pub fn iter_grid(&self) -> impl Iterator<Item = (Cell, bool)> + '_ {
+        (0..self.rows)
+            .flat_map(move |r| (0..self.cols).map(move |c| Cell { r, c }))
+            .map(move |cell| (cell, self.is_alive(cell)))
+    }
+
+    /// Pack the grid into 64-bit words, one bit per cell, set for live cells.
+    ///
+    /// Cells are numbered in row-major order, so cell `(r, c)` is number
+    /// `r * cols + c`. Cell number `i` is bit `i % 64` of word `i / 64`, where
+    /// bit 0 is the least significant bit. The last word is padded with zero
+    /// bits, so there are `ceil(rows * cols / 64)` words. Only the cells are
+    /// packed; the dimensions must be passed to `from_bitvec` separately.
+    pub fn to_bitvec(&self) -> Vec<u64> {
+        let mut words = vec![0; (self.rows * self.cols).div_ceil(64)];
+        for cell in self.live.iter() {
+            let index = cell.r * self.cols + cell.c;
+            words[index / 64] |= 1 << (index % 64);
+        }
+        words
+    }
+
+    /// Copy the live cells in a region of `rows` by `cols` cells whose top
+    /// left corner is `origin`, with coordinates relative to `origin`.
+    ///
+    /// On a toroidal axis, a region that extends past the bottom or right
+    /// edge wraps around to the top or left, and the pattern straddling the
+    /// seam is reassembled contiguously. A region longer than a toroidal axis
+    /// is cut to the axis's length, so no cell is copied twice. On a bounded
+    /// axis, the part of the region beyond the edges is empty.
+    pub fn crop(&self, origin: Cell, rows: usize, cols: usize) -> Vec<Cell> {
+        let wrap = |i: usize, len: usize, boundary: BoundaryMode| match boundary {
+            BoundaryMode::Toroidal => Some(i % len),
+            BoundaryMode::Dead | BoundaryMode::Reflect => Some(i).filter(|i| *i < len),
+        };
+        let (rows, cols) = self.crop_size(rows, cols);
+        let mut cropped = Vec::new();
+        for dr in 0..rows {
+            for dc in 0..cols {
+                let r = wrap(origin.r + dr, self.rows, self.boundary_rows);
+                let c = wrap(origin.c + dc, self.cols, self.boundary_cols);
+                let (Some(r), Some(c)) = (r, c) else {
+                    continue;
+                };
+                if self.is_alive(Cell { r, c }) {
+                    cropped.push(Cell { r: dr, c: dc });
+                }
+            }
+        }
+        cropped
+    }
+
+    /// Copy the live cells in the region spanning from the corner `first` down
+    /// and to the right to the corner `last`, inclusive, with coordinates
+    /// relative to the top left of the region.
+    ///
+    /// On a toroidal axis, two corners describe two different regions: the
+    /// one between them, and the one across the seam. If `last` lies above or
+    /// to the left of `first`, the region crosses the seam on that axis; swap
+    /// the corners to select the region between them instead. On a bounded
+    /// axis, the region always lies between the corners.
+    pub fn crop_between(&self, first: Cell, last: Cell) -> Vec<Cell> {
+        let span = |from: usize, to: usize, len: usize, boundary: BoundaryMode| {
+            if to >= from {
+                (from, to - from + 1)
+            } else if boundary == BoundaryMode::Toroidal {
+                (from, len - from + to + 1)
+            } else {
+                (to, from - to + 1)
+            }
+        };
+        let (r, rows) = span(first.r, last.r, self.rows, self.boundary_rows);
+        let (c, cols) = span(first.c, last.c, self.cols, self.boundary_cols);
+        self.crop(Cell { r, c }, rows, cols)
+    }
+
+    /// The size `crop` copies for a region of `rows` by `cols` cells, with
+    /// each toroidal axis cut to its length.
+    fn crop_size(&self, rows: usize, cols: usize) -> (usize, usize) {
+        let size = |wanted: usize, len: usize, boundary: BoundaryMode| match boundary {
+            BoundaryMode::Toroidal => wanted.min(len),
+            BoundaryMode::Dead | BoundaryMode::Reflect => wanted,
+        };
+        (size(rows, self.rows, self.boundary_rows), size(cols, self.cols, self.boundary_cols))
+    }
+
+    /// A game of just the live cells `crop` copies from a region, with this
+    /// game's rule, neighborhood, and metadata, e.g. to export a pattern that
+    /// straddles the edges of a toroidal board in one piece.
+    pub fn cropped(&self, region: Region) -> GameOfLife {
+        let (rows, cols) = self.crop_size(region.rows, region.cols);
+        let live = self.crop(Cell { r: region.r, c: region.c }, rows, cols).into_iter().collect();
+        let mut game = GameOfLife::with_live(rows, cols, live);
+        game.rule = self.rule;
+        game.neighborhood = self.neighborhood;
+        game.meta = self.meta.clone();
+        game
+    }
+
+    /// The smallest regions holding every live cell, where a region may wrap
+    /// around the edges of a toroidal axis so that a pattern straddling them
+    /// is found in one piece. Pass one to `crop` or `cropped` to copy it.
+    ///
+    /// On a toroidal axis, the region is cut across the widest run of rows or
+    /// columns without live cells. When several runs tie for widest, it is
+    /// not clear which to cut across, so there is a region for each choice,
+    /// ordered by row then column. Empty if every cell is dead.
+    pub fn pattern_regions(&self) -> Vec<Region> {
+        let (mut rows, mut cols) = (vec![false; self.rows], vec![false; self.cols]);
+        for cell in self.live.iter() {
+            rows[cell.r] = true;
+            cols[cell.c] = true;
+        }
+        let col_spans = occupied_spans(&cols, self.boundary_cols);
+        occupied_spans(&rows, self.boundary_rows).into_iter()
+            .flat_map(|(r, rows)| col_spans.iter().map(move |&(c, cols)| Region { r, c, rows, cols }))
+            .collect()
+    }
+}
+
+/// The shortest spans of an axis, as `(start, length)`, holding every index
+/// marked occupied. On a toroidal axis a span may wrap past the end, and
+/// there is one span for each widest gap between occupied indices.
+fn occupied_spans(occupied: &[bool], boundary: BoundaryMode) -> Vec<(usize, usize)> {
+    let len = occupied.len();
+    let Some(first) = occupied.iter().position(|occupied| *occupied) else {
+        return Vec::new();
+    };
+    let last = occupied.iter().rposition(|occupied| *occupied).unwrap();
+    if boundary != BoundaryMode::Toroidal {
+        return vec![(first, last - first + 1)];
+    }
+
+    // Walk once around the axis from the first occupied index, noting each
+    // gap and the occupied index after it.
+    let mut gaps = Vec::new();
+    let mut gap = 0;
+    for step in 1..=len {
+        let i = (first + step) % len;
+        if occupied[i] {
+            gaps.push((gap, i));
+            gap = 0;
+        } else {
+            gap += 1;
+        }
+    }
+    let widest = gaps.iter().map(|(gap, _)| *gap).max().unwrap();
+    if widest == 0 {
+        return vec![(0, len)];
+    }
+    let mut spans: Vec<_> = gaps.into_iter()
+        .filter(|(gap, _)| *gap == widest)
+        .map(|(_, start)| (start, len - widest))
+        .collect();
+    spans.sort_unstable();
+    spans
+}
+
+/// Clamp `p` to a probability `gen_bool` accepts, treating NaN as 0.
+fn probability(p: f64) -> f64 {
+    if p.is_nan() { 0.0 } else { p.clamp(0.0, 1.0) }
 }
 
-impl ToString for GameOfLife {
-    fn to_string(&self) -> String {
+impl fmt::Display for GameOfLife {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut res = String::new();
         for r in 0..self.rows {
             for c in 0..self.cols {
-                if self.is_live(&Cell { r: r.try_into().unwrap(), c: c.try_into().unwrap() }) {
+                if self.is_alive(Cell { r, c }) {
                     res.push('█');
                 } else {
                     res.push(' ');
@@ -211,6 +1895,55 @@ impl ToString for GameOfLife {
                 res.push('\n');
             }
         }
-        res
+        write!(f, "{}", res)
+    }
+}
+
+/// A one-line summary of the game, since the grid printed by `Display` looks
+/// the same for every empty board.
+impl fmt::Debug for GameOfLife {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GameOfLife")
+            .field("rows", &self.rows)
+            .field("cols", &self.cols)
+            .field("population", &self.live.len())
+            .field("rule", &format_args!("{}", self.rule))
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+/// Whether a cell is alive, like `is_alive`, so that `game[cell]` reads as a
+/// `bool`.
+///
+/// # Panics
+///
+/// Panics if the cell is outside the game, as indexing a slice out of bounds
+/// does, where `is_alive` would return `false`.
+impl Index<Cell> for GameOfLife {
+    type Output = bool;
+
+    fn index(&self, cell: Cell) -> &bool {
+        if cell.r >= self.rows || cell.c >= self.cols {
+            panic!("cell ({}, {}) is outside the {}x{} game", cell.r, cell.c, self.rows, self.cols);
+        }
+        match self.is_alive(cell) {
+            true => &true,
+            false => &false,
+        }
+    }
+}
+
+/// Whether the cell at `(row, col)` is alive, so that `game[(r, c)]` reads as
+/// a `bool`.
+///
+/// # Panics
+///
+/// Panics if the cell is outside the game, see `Index<Cell>`.
+impl Index<(usize, usize)> for GameOfLife {
+    type Output = bool;
+
+    fn index(&self, (r, c): (usize, usize)) -> &bool {
+        &self[Cell { r, c }]
     }
 }
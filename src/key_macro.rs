@@ -0,0 +1,220 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use sdl2::keyboard::{Keycode, Mod};
+
+/// First line of every macro file.
+const HEADER: &str = "# conway key macro";
+
+/// Names of the keys that can be recorded in a macro or bound in a keymap,
+/// which are the keys the simulator can respond to.
+const KEY_NAMES: &[(Keycode, &str)] = &[
+    (Keycode::Space, "Space"), (Keycode::Escape, "Escape"), (Keycode::Return, "Return"),
+    (Keycode::Backspace, "Backspace"), (Keycode::Tab, "Tab"), (Keycode::Delete, "Delete"),
+    (Keycode::Home, "Home"), (Keycode::End, "End"), (Keycode::PageUp, "PageUp"), (Keycode::PageDown, "PageDown"),
+    (Keycode::Up, "Up"), (Keycode::Down, "Down"), (Keycode::Left, "Left"), (Keycode::Right, "Right"),
+    (Keycode::A, "A"), (Keycode::B, "B"), (Keycode::C, "C"), (Keycode::D, "D"), (Keycode::E, "E"),
+    (Keycode::F, "F"), (Keycode::G, "G"), (Keycode::H, "H"), (Keycode::I, "I"), (Keycode::J, "J"),
+    (Keycode::K, "K"), (Keycode::L, "L"), (Keycode::M, "M"), (Keycode::N, "N"), (Keycode::O, "O"),
+    (Keycode::P, "P"), (Keycode::Q, "Q"), (Keycode::R, "R"), (Keycode::S, "S"), (Keycode::T, "T"),
+    (Keycode::U, "U"), (Keycode::V, "V"), (Keycode::W, "W"), (Keycode::X, "X"), (Keycode::Y, "Y"),
+    (Keycode::Z, "Z"),
+    (Keycode::Num0, "0"), (Keycode::Num1, "1"), (Keycode::Num2, "2"), (Keycode::Num3, "3"),
+    (Keycode::Num4, "4"), (Keycode::Num5, "5"), (Keycode::Num6, "6"), (Keycode::Num7, "7"),
+    (Keycode::Num8, "8"), (Keycode::Num9, "9"),
+    (Keycode::Kp0, "Keypad0"), (Keycode::Kp1, "Keypad1"), (Keycode::Kp2, "Keypad2"),
+    (Keycode::Kp3, "Keypad3"), (Keycode::Kp4, "Keypad4"), (Keycode::Kp5, "Keypad5"),
+    (Keycode::Kp6, "Keypad6"), (Keycode::Kp7, "Keypad7"), (Keycode::Kp8, "Keypad8"),
+    (Keycode::Kp9, "Keypad9"),
+];
+
+/// Errors produced when loading a macro.
+#[derive(Debug)]
+pub enum MacroError {
+    /// The macro could not be read.
+    Io(io::Error),
+    /// The data does not start like a macro.
+    NotAMacro,
+    /// A line of the macro could not be understood.
+    Invalid { line: usize, message: String },
+}
+
+impl fmt::Display for MacroError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MacroError::Io(error) => write!(f, "error reading macro: {}", error),
+            MacroError::NotAMacro => write!(f, "file is not a key macro (expected `{}` on the first line)", HEADER),
+            MacroError::Invalid { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for MacroError {}
+
+impl From<io::Error> for MacroError {
+    fn from(error: io::Error) -> MacroError {
+        MacroError::Io(error)
+    }
+}
+
+/// A key pressed at a generation of the game.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub struct KeyPress {
+    pub generation: u128,  // generation of the game when the key was pressed
+    pub keycode: Keycode,  // key pressed
+    pub shift: bool,  // whether SHIFT was held
+    pub ctrl: bool,  // whether CTRL was held
+}
+
+impl KeyPress {
+    /// A key press from an SDL key event, or `None` if the key cannot be
+    /// recorded in a macro.
+    pub fn new(generation: u128, keycode: Keycode, keymod: Mod) -> Option<KeyPress> {
+        key_name(keycode)?;
+        Some(KeyPress {
+            generation,
+            keycode,
+            shift: keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD),
+            ctrl: keymod.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD),
+        })
+    }
+
+    /// The modifier keys held, as SDL key modifiers.
+    pub fn keymod(&self) -> Mod {
+        let mut keymod = Mod::NOMOD;
+        if self.shift {
+            keymod |= Mod::LSHIFTMOD;
+        }
+        if self.ctrl {
+            keymod |= Mod::LCTRLMOD;
+        }
+        keymod
+    }
+}
+
+impl fmt::Display for KeyPress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ", self.generation)?;
+        if self.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        write!(f, "{}", key_name(self.keycode).unwrap())
+    }
+}
+
+/// Writes the keys pressed while running the simulator to a macro file, so
+/// that a session can be replayed by `MacroPlayer`.
+///
+/// A macro is a text file starting with the line `# conway key macro`,
+/// followed by one key press per line, in the order pressed: the generation
+/// of the game when the key was pressed, then the key name, prefixed by
+/// `Ctrl+` and/or `Shift+` if held, like `120 Shift+Left`. Blank lines and
+/// lines starting with `#` are ignored.
+pub struct MacroRecorder<W: Write> {
+    writer: W,  // destination of the macro
+}
+
+impl<W: Write> MacroRecorder<W> {
+    /// Start a macro with no key presses.
+    pub fn new(mut writer: W) -> io::Result<MacroRecorder<W>> {
+        writeln!(writer, "{}", HEADER)?;
+        Ok(MacroRecorder { writer })
+    }
+
+    /// Add a key press to the end of the macro.
+    pub fn record(&mut self, press: &KeyPress) -> io::Result<()> {
+        writeln!(self.writer, "{}", press)
+    }
+
+    /// Finish recording, flushing and returning the writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Plays back the key presses of a macro written by `MacroRecorder`, each
+/// once the game reaches the generation it was pressed at.
+pub struct MacroPlayer {
+    presses: VecDeque<KeyPress>,  // key presses not yet played, in order
+}
+
+impl MacroPlayer {
+    /// Read a whole macro.
+    pub fn new<R: BufRead>(reader: R) -> Result<MacroPlayer, MacroError> {
+        let mut lines = reader.lines();
+        match lines.next().transpose()? {
+            Some(header) if header.trim_end() == HEADER => (),
+            _ => return Err(MacroError::NotAMacro),
+        }
+
+        let mut presses = VecDeque::new();
+        for (index, line) in lines.enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let press = parse_press(line).map_err(|message| MacroError::Invalid { line: index + 2, message })?;
+            if presses.back().is_some_and(|last: &KeyPress| last.generation > press.generation) {
+                return Err(MacroError::Invalid {
+                    line: index + 2,
+                    message: String::from("generations must not decrease from one key press to the next"),
+                });
+            }
+            presses.push_back(press);
+        }
+        Ok(MacroPlayer { presses })
+    }
+
+    /// Take the next key press if the game has reached its generation.
+    pub fn next_due(&mut self, generation: u128) -> Option<KeyPress> {
+        match self.presses.front() {
+            Some(press) if press.generation <= generation => self.presses.pop_front(),
+            _ => None,
+        }
+    }
+
+    /// Whether every key press has been played.
+    pub fn finished(&self) -> bool {
+        self.presses.is_empty()
+    }
+}
+
+/// Parse a key press written like `120 Ctrl+Shift+Left`.
+fn parse_press(line: &str) -> Result<KeyPress, String> {
+    let (generation, key) = line.split_once(char::is_whitespace)
+        .ok_or_else(|| String::from("expected a generation and a key"))?;
+    let generation = generation.parse::<u128>()
+        .map_err(|_| format!("generation `{}` is not a whole number", generation))?;
+
+    let (mut shift, mut ctrl) = (false, false);
+    let mut key = key.trim();
+    loop {
+        if let Some(rest) = key.strip_prefix("Ctrl+") {
+            ctrl = true;
+            key = rest;
+        } else if let Some(rest) = key.strip_prefix("Shift+") {
+            shift = true;
+            key = rest;
+        } else {
+            break;
+        }
+    }
+    let keycode = key_code(key).ok_or_else(|| format!("unknown key `{}`", key))?;
+    Ok(KeyPress { generation, keycode, shift, ctrl })
+}
+
+/// The key with a name from `KEY_NAMES`, if any.
+pub(crate) fn key_code(name: &str) -> Option<Keycode> {
+    KEY_NAMES.iter().find(|(_, key)| *key == name).map(|(keycode, _)| *keycode)
+}
+
+/// The name of a key in `KEY_NAMES`, if it has one.
+pub(crate) fn key_name(keycode: Keycode) -> Option<&'static str> {
+    KEY_NAMES.iter().find(|(key, _)| *key == keycode).map(|(_, name)| *name)
+}
@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, BufRead};
+
+use sdl2::keyboard::Keycode;
+
+use crate::key_macro::{key_code, key_name};
+
+/// Something the simulator does when a key is pressed in the window.
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub enum KeyAction {
+    /// End the simulation.
+    Quit,
+    /// Play or pause the simulation.
+    PlayPause,
+    /// Step a typed number of generations, or one, while paused.
+    Step,
+    /// Raise the framerate; with SHIFT, scroll the world up.
+    Faster,
+    /// Lower the framerate; with SHIFT, scroll the world down.
+    Slower,
+    /// Run more generations per frame; with SHIFT, scroll the world right.
+    MoreSteps,
+    /// Run fewer generations per frame; with SHIFT, scroll the world left.
+    FewerSteps,
+    /// Randomly flip a few cells.
+    Mutate,
+    /// Switch to the next way of coloring cells.
+    CycleColor,
+    /// Pause at a typed generation, or clear the generation to pause at.
+    PauseAt,
+    /// Switch the units step timing is shown in.
+    TimingUnits,
+    /// Undo the last brush stroke, with CTRL, while paused.
+    Undo,
+    /// Show the next pattern of the catalog.
+    NextPattern,
+    /// Show the previous pattern of the catalog.
+    PreviousPattern,
+    /// Switch whether the top and bottom edges wrap, from the next generation.
+    WrapRows,
+    /// Switch whether the left and right edges wrap, from the next generation.
+    WrapCols,
+    /// Switch to the next shape drawn by dragging with SHIFT.
+    CycleShape,
+}
+
+/// Every action, with its name in keymap files.
+const ACTION_NAMES: [(KeyAction, &str); 17] = [
+    (KeyAction::Quit, "quit"),
+    (KeyAction::PlayPause, "play_pause"),
+    (KeyAction::Step, "step"),
+    (KeyAction::Faster, "faster"),
+    (KeyAction::Slower, "slower"),
+    (KeyAction::MoreSteps, "more_steps"),
+    (KeyAction::FewerSteps, "fewer_steps"),
+    (KeyAction::Mutate, "mutate"),
+    (KeyAction::CycleColor, "cycle_color"),
+    (KeyAction::PauseAt, "pause_at"),
+    (KeyAction::TimingUnits, "timing_units"),
+    (KeyAction::Undo, "undo"),
+    (KeyAction::NextPattern, "next_pattern"),
+    (KeyAction::PreviousPattern, "previous_pattern"),
+    (KeyAction::WrapRows, "wrap_rows"),
+    (KeyAction::WrapCols, "wrap_cols"),
+    (KeyAction::CycleShape, "cycle_shape"),
+];
+
+/// The keys each action is bound to by default.
+const DEFAULT_BINDINGS: [(KeyAction, &[Keycode]); 17] = [
+    (KeyAction::Quit, &[Keycode::Q, Keycode::Escape]),
+    (KeyAction::PlayPause, &[Keycode::Space]),
+    (KeyAction::Step, &[Keycode::N]),
+    (KeyAction::Faster, &[Keycode::Up]),
+    (KeyAction::Slower, &[Keycode::Down]),
+    (KeyAction::MoreSteps, &[Keycode::Right]),
+    (KeyAction::FewerSteps, &[Keycode::Left]),
+    (KeyAction::Mutate, &[Keycode::M]),
+    (KeyAction::CycleColor, &[Keycode::V]),
+    (KeyAction::PauseAt, &[Keycode::P]),
+    (KeyAction::TimingUnits, &[Keycode::U]),
+    (KeyAction::Undo, &[Keycode::Z]),
+    (KeyAction::NextPattern, &[Keycode::PageDown]),
+    (KeyAction::PreviousPattern, &[Keycode::PageUp]),
+    (KeyAction::WrapRows, &[Keycode::R]),
+    (KeyAction::WrapCols, &[Keycode::C]),
+    (KeyAction::CycleShape, &[Keycode::S]),
+];
+
+impl fmt::Display for KeyAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (_, name) = ACTION_NAMES.iter().find(|(action, _)| action == self).unwrap();
+        write!(f, "{}", name)
+    }
+}
+
+/// Errors produced when loading a keymap.
+#[derive(Debug)]
+pub enum KeymapError {
+    /// The keymap could not be read.
+    Io(io::Error),
+    /// A line of the keymap could not be understood.
+    Invalid { line: usize, message: String },
+}
+
+impl fmt::Display for KeymapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KeymapError::Io(error) => write!(f, "error reading keymap: {}", error),
+            KeymapError::Invalid { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for KeymapError {}
+
+impl From<io::Error> for KeymapError {
+    fn from(error: io::Error) -> KeymapError {
+        KeymapError::Io(error)
+    }
+}
+
+/// The keys that trigger each action in the window. Each key triggers at
+/// most one action, and an action may have any number of keys, including
+/// none to disable it. The number keys always type counts, so binding one
+/// has no effect.
+///
+/// A keymap file has one action per line, with its keys after an `=`,
+/// separated by spaces or commas, like `quit = Q Escape`. Key names are the
+/// same as in key macros. Actions not listed keep their default keys, and an
+/// action with nothing after the `=` is disabled. Blank lines and lines
+/// starting with `#` are ignored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Keymap {
+    actions: HashMap<Keycode, KeyAction>,  // action of each bound key
+}
+
+impl Keymap {
+    /// Read a keymap file, applied on top of the default keys.
+    pub fn load<R: BufRead>(reader: R) -> Result<Keymap, KeymapError> {
+        let mut keymap = Keymap::default();
+        let mut bound_by = HashMap::new();  // line each key was bound on
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |message: String| KeymapError::Invalid { line: index + 1, message };
+
+            let (action, keys) = line.split_once('=')
+                .ok_or_else(|| invalid(String::from("expected an action, `=`, and its keys")))?;
+            let action = action.trim();
+            let action = ACTION_NAMES.iter()
+                .find(|(_, name)| *name == action)
+                .map(|(action, _)| *action)
+                .ok_or_else(|| invalid(format!("unknown action `{}`", action)))?;
+            let mut keycodes = Vec::new();
+            for key in keys.split([' ', ',']).filter(|key| !key.is_empty()) {
+                let keycode = key_code(key).ok_or_else(|| invalid(format!("unknown key `{}`", key)))?;
+                if let Some(line) = bound_by.insert(keycode, index + 1) {
+                    return Err(invalid(format!("key `{}` is already bound on line {}", key, line)));
+                }
+                keycodes.push(keycode);
+            }
+            keymap.bind(action, &keycodes);
+        }
+        Ok(keymap)
+    }
+
+    /// The action a key triggers, if any.
+    pub fn action(&self, keycode: Keycode) -> Option<KeyAction> {
+        self.actions.get(&keycode).copied()
+    }
+
+    /// The keys bound to an action, sorted by name.
+    pub fn keys(&self, action: KeyAction) -> Vec<Keycode> {
+        let mut keys: Vec<_> = self.actions.iter()
+            .filter(|(_, bound)| **bound == action)
+            .map(|(keycode, _)| *keycode)
+            .collect();
+        keys.sort_by_key(|keycode| key_name(*keycode));
+        keys
+    }
+
+    /// Bind an action to exactly the given keys, replacing its keys. Keys
+    /// bound to other actions are moved to this one.
+    pub fn bind(&mut self, action: KeyAction, keys: &[Keycode]) {
+        self.actions.retain(|_, bound| *bound != action);
+        for keycode in keys {
+            self.actions.insert(*keycode, action);
+        }
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Keymap {
+        let mut keymap = Keymap { actions: HashMap::new() };
+        for (action, keys) in DEFAULT_BINDINGS {
+            keymap.bind(action, keys);
+        }
+        keymap
+    }
+}
+
+/// The largest count that can be typed before a key, e.g. the generations
+/// to step with **n** or the generation to pause at with **p**, so a held
+/// digit cannot queue an unbounded amount of work.
+pub const MAX_KEY_COUNT: usize = 1_000_000;
+
+/// Append a typed digit to the count typed so far, if any, capped at
+/// `MAX_KEY_COUNT`.
+pub fn push_count_digit(count: Option<usize>, digit: usize) -> usize {
+    count.unwrap_or(0).saturating_mul(10).saturating_add(digit).min(MAX_KEY_COUNT)
+}
@@ -1,5 +1,40 @@
+mod age_bands;
+mod analysis;
+mod catalog;
+mod dense;
+mod export;
+mod fingerprint;
+mod font;
 mod game_of_life;
+mod key_macro;
+mod live_set;
+mod keymap;
+mod pacing;
+mod recording;
 mod render;
+mod renderer;
+mod rule;
+mod shapes;
+mod snapshot;
+mod stats;
+mod world;
 
-pub use game_of_life::{GameOfLife};
-pub use render::{SdlRender};
\ No newline at end of file
+pub use age_bands::{AgeBands, ParseAgeBandsError};
+pub use analysis::{Behavior, Fate, Object};
+pub use catalog::Catalog;
+pub use export::{render_to_rgba, write_png};
+pub use fingerprint::chain_hash;
+pub use font::draw_label;
+pub use game_of_life::{GameOfLife, GameError, Backend, Edge, StepAlgorithm, Cell, StepDelta, StepOutcome, SetOp, StressKind, BoundaryMode, Neighborhood, CoordOrder, PatternMeta, PatternError, PatternFormat, PatternInfo, PatternOptions, Region, pattern_format_for_path, pattern_formats, pattern_info};
+pub use key_macro::{KeyPress, MacroError, MacroPlayer, MacroRecorder};
+pub use keymap::{KeyAction, Keymap, KeymapError, MAX_KEY_COUNT, push_count_digit};
+pub use live_set::LiveSet;
+pub use pacing::{Clock, FramePacer, SpeedMeter, SystemClock};
+pub use recording::{DeltaPlayer, DeltaRecorder, RecordingError};
+pub use render::{SdlRender, ColorMode, CountFormat};
+pub use renderer::{Corner, PngRender, Renderer, TextRender};
+pub use rule::{RuleString, ParseRuleError};
+pub use shapes::ShapeTool;
+pub use snapshot::{Checkpoints, SnapshotError};
+pub use stats::RunStats;
+pub use world::{GameResult, World};
\ No newline at end of file
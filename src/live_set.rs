@@ -0,0 +1,115 @@
+use std::collections::{btree_set, hash_set, BTreeSet, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Cell, Region};
+
+/// How a game stores its live cells.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Default, Serialize, Deserialize)]
+pub enum LiveSet {
+    /// A hash set. Fastest, but live cells come out in no particular order,
+    /// which may differ between runs.
+    #[default]
+    Hashed,
+    /// A sorted set. Somewhat slower to step, but live cells always come out
+    /// sorted by row then column, and the live cells of a region are found
+    /// without visiting every live cell.
+    Ordered,
+}
+
+/// The live cells of a game, stored as chosen by a `LiveSet`.
+#[derive(Clone, Debug)]
+pub(crate) enum LiveCells {
+    Hashed(HashSet<Cell>),
+    Ordered(BTreeSet<Cell>),
+}
+
+impl LiveCells {
+    /// Store the given cells in a set of the given kind.
+    pub(crate) fn new(kind: LiveSet, cells: HashSet<Cell>) -> LiveCells {
+        match kind {
+            LiveSet::Hashed => LiveCells::Hashed(cells),
+            LiveSet::Ordered => LiveCells::Ordered(cells.into_iter().collect()),
+        }
+    }
+
+    /// The kind of set the cells are stored in.
+    pub(crate) fn kind(&self) -> LiveSet {
+        match self {
+            LiveCells::Hashed(_) => LiveSet::Hashed,
+            LiveCells::Ordered(_) => LiveSet::Ordered,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            LiveCells::Hashed(cells) => cells.len(),
+            LiveCells::Ordered(cells) => cells.len(),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(crate) fn contains(&self, cell: &Cell) -> bool {
+        match self {
+            LiveCells::Hashed(cells) => cells.contains(cell),
+            LiveCells::Ordered(cells) => cells.contains(cell),
+        }
+    }
+
+    /// Add a cell, returning whether it was not already there.
+    pub(crate) fn insert(&mut self, cell: Cell) -> bool {
+        match self {
+            LiveCells::Hashed(cells) => cells.insert(cell),
+            LiveCells::Ordered(cells) => cells.insert(cell),
+        }
+    }
+
+    /// Remove a cell, returning whether it was there.
+    pub(crate) fn remove(&mut self, cell: &Cell) -> bool {
+        match self {
+            LiveCells::Hashed(cells) => cells.remove(cell),
+            LiveCells::Ordered(cells) => cells.remove(cell),
+        }
+    }
+
+    /// The cells, sorted by row then column if ordered.
+    pub(crate) fn iter(&self) -> Iter<'_> {
+        match self {
+            LiveCells::Hashed(cells) => Iter::Hashed(cells.iter()),
+            LiveCells::Ordered(cells) => Iter::Ordered(cells.iter()),
+        }
+    }
+
+    /// The cells inside a region, sorted by row then column if ordered. An
+    /// ordered set only visits the cells of the region's rows that lie
+    /// between its left and right edges.
+    pub(crate) fn in_region(&self, region: Region) -> Vec<Cell> {
+        match self {
+            LiveCells::Hashed(cells) => cells.iter().copied().filter(|cell| region.contains(*cell)).collect(),
+            LiveCells::Ordered(cells) => (region.r..region.r + region.rows)
+                .flat_map(|r| cells.range(Cell { r, c: region.c }..Cell { r, c: region.c + region.cols }))
+                .copied()
+                .collect(),
+        }
+    }
+}
+
+/// An iterator over the cells of a `LiveCells`.
+pub(crate) enum Iter<'a> {
+    Hashed(hash_set::Iter<'a, Cell>),
+    Ordered(btree_set::Iter<'a, Cell>),
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a Cell;
+
+    fn next(&mut self) -> Option<&'a Cell> {
+        match self {
+            Iter::Hashed(cells) => cells.next(),
+            Iter::Ordered(cells) => cells.next(),
+        }
+    }
+}
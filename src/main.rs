@@ -1,9 +1,34 @@
-use clap::{Arg, App};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::process;
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant};
+use clap::{Arg, App, AppSettings, ArgMatches, SubCommand};
 use regex::Regex;
 
-use conway::{GameOfLife, SdlRender};
+use conway::{
+    AgeBands, Backend, Behavior, Catalog, Cell, Checkpoints, ColorMode, Corner, CoordOrder, CountFormat, DeltaPlayer, DeltaRecorder,
+    GameOfLife, KeyAction, KeyPress, Keymap, KeymapError, MacroError, MacroPlayer, MacroRecorder, Object, PatternOptions, PngRender,
+    RecordingError, Region, Renderer, RuleString, RunStats, SdlRender, SnapshotError, StepAlgorithm, TextRender, World, chain_hash,
+    pattern_format_for_path, pattern_formats, pattern_info, push_count_digit, render_to_rgba, write_png,
+};
 
 const DEFAULT_CELL_SIZE: usize = 5;
+const HIGH_CONTRAST_CELL_SIZE: usize = 10;  // default cell size with --high-contrast, large enough for grid lines
+const MUTATION_RATE: f64 = 0.001;
+const SOUP_DENSITY: f64 = 0.1;  // chance each cell of a seeded random soup starts alive, as in an unseeded one
+const DEFAULT_MARKER_SCALE: usize = 2;  // pixels per dot of the generation label on recorded frames
+const IDLE_WAIT_MS: u32 = 250;  // longest the window sleeps waiting for an event while paused with nothing to draw
+const DEFAULT_AUTOFIT_MAX: (usize, usize) = (1000, 1000);
+const MAX_WINDOW_SIZE: usize = 8192;  // widest and tallest window made when the display size is unknown
+const SUMMARY_LOOKAHEAD: usize = 30;  // generations run ahead to judge whether the game has settled
+const DEFAULT_FATE_GENERATIONS: usize = 1000;
+const DEFAULT_OFFLINE_DIMENSIONS: (usize, usize) = (500, 500);  // grid subcommands run patterns in without a window
+const EXIT_SETTLED: i32 = 0;  // --exit-code when the game ended still or oscillating
+const EXIT_ACTIVE: i32 = 3;  // --exit-code when the game was still changing at the end
+const EXIT_EXTINCT: i32 = 4;  // --exit-code when every cell died
 
 fn main() {
     let cli = App::new("Game of Life")
@@ -13,117 +38,1717 @@ fn main() {
         .after_help(
             "This program simulates Conway's Game of Life on a toroidal surface \
              (edges are connected). Use SPACE to play/pause the simulation, N \
-             to single step the simulation while paused, and the arrow keys to \
-             adjust the framerate/evolutions per frame of the simulation."
+             to single step the simulation while paused, M to randomly mutate \
+             a few cells, V to cycle between plain, age, and heat coloring, U to \
+             switch step timing units when profiling, P to clear the generation \
+             to pause at, and the arrow keys to adjust the framerate/evolutions \
+             per frame of the simulation. Type a number before N to step that \
+             many generations, or before P to pause at that generation. \
+             While paused, drag with the left mouse button to draw cells, and \
+             use CTRL+Z to undo a drawing stroke; hold SHIFT while dragging to \
+             draw a box, line, or ellipse instead, switching shapes with S. \
+             R and C toggle wrapping of rows and columns. SHIFT and the arrow \
+             keys scroll the world."
         )
+        .setting(AppSettings::SubcommandsNegateReqs)
+        .subcommand(SubCommand::with_name("formats")
+            .about("Lists the pattern file formats that can be loaded, with an example of each.")
+            .arg(Arg::with_name("json")
+                .help("print the formats as a JSON array of objects with `name`, `description`, `example`, and `extensions` fields")
+                .long("json")))
+        .subcommand(SubCommand::with_name("convert")
+            .about("Converts a pattern file or snapshot to a pattern format, or to a PNG image, chosen by the output file's extension.")
+            .arg(Arg::with_name("input")
+                .help("the pattern file to convert, in any supported format, or a snapshot saved with --save, whose live cells \
+                       are written in one piece even if they straddle the edges of a toroidal board")
+                .required(true))
+            .arg(Arg::with_name("output")
+                .help("the file to write, ending in the extension of a pattern format or `.png`")
+                .required(true))
+            .arg(Arg::with_name("coord_order")
+                .help("the order of the numbers in a coords input file: `rc` for `row,col`, `xy` for `col,row`")
+                .long("coord-order")
+                .takes_value(true)
+                .possible_values(&["rc", "xy"])
+                .default_value("rc"))
+            .arg(Arg::with_name("cell_size")
+                .help("the size of each cell in pixels when writing a PNG image")
+                .short("c")
+                .long("cell")
+                .takes_value(true))
+            .arg(Arg::with_name("region")
+                .help("only convert the cells in this region, given as `{row},{col},{rows}x{cols}` and wrapping past the \
+                       edges of a toroidal board; needed to pick where to cut a snapshot whose live cells could be \
+                       unwrapped across more than one seam")
+                .long("region")
+                .takes_value(true)))
+        .subcommand(SubCommand::with_name("info")
+            .about("Summarizes a pattern file without running it: its format, rule, size, population, and metadata.")
+            .arg(Arg::with_name("file")
+                .help("the pattern file to summarize, in any supported format")
+                .required(true))
+            .arg(Arg::with_name("coord_order")
+                .help("the order of the numbers in a coords file: `rc` for `row,col`, `xy` for `col,row`")
+                .long("coord-order")
+                .takes_value(true)
+                .possible_values(&["rc", "xy"])
+                .default_value("rc"))
+            .arg(Arg::with_name("json")
+                .help("print the summary as a JSON object with `format`, `rule`, `rows`, `cols`, `population`, `name`, \
+                       `author`, and `comments` fields")
+                .long("json")))
+        .subcommand(SubCommand::with_name("fate")
+            .about("Runs a pattern file without a window until it dies, settles, or starts repeating, and reports how it ended up.")
+            .arg(Arg::with_name("file")
+                .help("the pattern file to run, centered in the grid")
+                .short("f")
+                .long("file")
+                .takes_value(true)
+                .required(true))
+            .arg(Arg::with_name("generations")
+                .help("the most generations to run before giving up on a verdict [default: 1000]")
+                .short("g")
+                .long("generations")
+                .takes_value(true))
+            .arg(Arg::with_name("dimensions")
+                .help("the dimensions of the game grid in cells, as `{rows}x{cols}`; the edges are connected [default: 500x500]")
+                .short("d")
+                .long("dimensions")
+                .takes_value(true))
+            .arg(Arg::with_name("json")
+                .help("print the fate as a JSON object with `behavior`, `settled`, `settled_at`, `period`, and `population` fields")
+                .long("json")))
+        .subcommand(SubCommand::with_name("hashrun")
+            .about("Runs a pattern file without a window and prints a hash of the board at each generation, to compare runs across machines.")
+            .arg(Arg::with_name("file")
+                .help("the pattern file to run, centered in the grid")
+                .short("f")
+                .long("file")
+                .takes_value(true)
+                .required(true))
+            .arg(Arg::with_name("generations")
+                .help("the number of generations to run")
+                .short("g")
+                .long("generations")
+                .takes_value(true)
+                .required(true))
+            .arg(Arg::with_name("dimensions")
+                .help("the dimensions of the game grid in cells, as `{rows}x{cols}`; the edges are connected [default: 500x500]")
+                .short("d")
+                .long("dimensions")
+                .takes_value(true))
+            .arg(Arg::with_name("combined")
+                .help("print one hash chaining together the hashes of every generation, instead of one line per generation")
+                .long("combined")))
+        .subcommand(SubCommand::with_name("diff")
+            .about("Loads two pattern files into grids of the same size and prints the cells that differ, then how many. \
+                    Exits with 0 if the boards match and 1 if they differ.")
+            .arg(Arg::with_name("a")
+                .help("the first pattern file, centered in the grid")
+                .required(true))
+            .arg(Arg::with_name("b")
+                .help("the second pattern file, centered in the grid")
+                .required(true))
+            .arg(Arg::with_name("gens")
+                .help("the number of generations to advance the first pattern before comparing, e.g. to check the second is what it becomes")
+                .long("gens")
+                .takes_value(true))
+            .arg(Arg::with_name("dimensions")
+                .help("the dimensions of the game grid in cells, as `{rows}x{cols}`; the edges are connected [default: 500x500]")
+                .short("d")
+                .long("dimensions")
+                .takes_value(true)))
         .arg(Arg::with_name("file")
             .help("the pattern file to start the game with; omit to use random pattern")
             .short("f")
             .long("file")
             .takes_value(true))
+        .arg(Arg::with_name("cells")
+            .help("the live cells to start the game with instead of a pattern file, as `{row},{col}` pairs separated by \
+                   spaces, centered in the grid like a coords file, e.g. \"0,1 1,1 2,1\" for a blinker")
+            .long("cells")
+            .takes_value(true)
+            .conflicts_with_all(&["file", "compare", "load", "replay", "catalog"]))
+        .arg(Arg::with_name("dump")
+            .help("print the board loaded from the pattern file or --cells as text and exit, to check how the pattern is read")
+            .long("dump")
+            .conflicts_with_all(&["headless", "frames_dir", "save", "analyze"]))
+        .arg(Arg::with_name("coord_order")
+            .help("the order of the numbers in coords pattern files: `rc` for `row,col`, `xy` for `col,row`")
+            .long("coord-order")
+            .takes_value(true)
+            .possible_values(&["rc", "xy"])
+            .default_value("rc"))
+        .arg(Arg::with_name("compare")
+            .help("the two pattern files to run side by side, with the same dimensions and controls")
+            .long("compare")
+            .takes_value(true)
+            .number_of_values(2)
+            .value_names(&["fileA", "fileB"])
+            .conflicts_with_all(&["file", "load", "headless", "frames_dir"]))
+        .arg(Arg::with_name("compare_seed")
+            .help("run two random soups side by side, generated from these seeds, with the same dimensions and controls; \
+                   the same seed twice with --compare-rule shows one soup under two rules")
+            .long("compare-seed")
+            .takes_value(true)
+            .number_of_values(2)
+            .value_names(&["seedA", "seedB"])
+            .conflicts_with_all(&["file", "cells", "compare", "load", "replay", "catalog", "fit_view", "headless", "frames_dir"]))
+        .arg(Arg::with_name("compare_rule")
+            .help("run the second game of --compare or --compare-seed under this rule, e.g. `B36/S23`; without either, run \
+                   a copy of the game under this rule side by side with it")
+            .long("compare-rule")
+            .takes_value(true)
+            .value_name("RULE")
+            .conflicts_with_all(&["load", "replay", "catalog", "fit_view", "headless", "frames_dir"]))
+        .arg(Arg::with_name("autofit")
+            .help("grow the game grid to fit the pattern file or --cells plus a margin if it is too small")
+            .long("autofit"))
+        .arg(Arg::with_name("pattern_scale")
+            .help("scale the pattern file or --cells up so each of its cells becomes an NxN square of cells")
+            .long("pattern-scale")
+            .takes_value(true)
+            .value_name("N"))
+        .arg(Arg::with_name("load_margin")
+            .help("warn if a loaded pattern comes closer than N cells to an edge of the grid, where it may interact with itself across the edges [default: 0]")
+            .long("load-margin")
+            .takes_value(true)
+            .value_name("N"))
+        .arg(Arg::with_name("autofit_max")
+            .help("the largest dimensions the game grid may grow to with --autofit, as `{rows}x{cols}` [default: 1000x1000]")
+            .long("autofit-max")
+            .takes_value(true)
+            .requires("autofit"))
         .arg(Arg::with_name("dimensions")
             .help("the dimensions of the game grid in cells, as `{rows}x{cols}`")
             .short("d")
             .long("dimensions")
             .takes_value(true)
-            .required(true))
+            .required_unless_one(&["load", "replay"]))
+        .arg(Arg::with_name("load")
+            .help("the snapshot file to resume a saved game from, instead of a pattern")
+            .long("load")
+            .takes_value(true)
+            .conflicts_with_all(&["file", "dimensions"]))
+        .arg(Arg::with_name("record")
+            .help("the file to record the headless generations to, keeping only the cells that change each generation")
+            .long("record")
+            .takes_value(true)
+            .requires("generations"))
+        .arg(Arg::with_name("replay")
+            .help("the recording to print the generations of in headless mode, instead of simulating a game")
+            .long("replay")
+            .takes_value(true)
+            .requires("headless")
+            .conflicts_with_all(&["file", "load", "dimensions", "compare", "record"]))
+        .arg(Arg::with_name("record_macro")
+            .help("the file to record the keys pressed in the window to, with the generation each was pressed at")
+            .long("record-macro")
+            .takes_value(true)
+            .conflicts_with_all(&["headless", "frames_dir", "dump"]))
+        .arg(Arg::with_name("play_macro")
+            .help("the key macro to play back in the window, pressing each key when the game reaches its generation")
+            .long("play-macro")
+            .takes_value(true)
+            .conflicts_with_all(&["headless", "frames_dir", "dump"]))
+        .arg(Arg::with_name("catalog")
+            .help("the directory of pattern files to browse with PAGEUP/PAGEDOWN, each centered in the grid; files that cannot be loaded are skipped")
+            .long("catalog")
+            .takes_value(true)
+            .conflicts_with_all(&["file", "compare", "load", "replay", "headless", "frames_dir"]))
+        .arg(Arg::with_name("keymap")
+            .help("the keymap file to change the keys of the window with, e.g. to disable quitting with ESC")
+            .long("keymap")
+            .takes_value(true)
+            .conflicts_with_all(&["headless", "frames_dir", "dump"]))
+        .arg(Arg::with_name("save")
+            .help("the snapshot file to save the game to when the simulation ends")
+            .long("save")
+            .takes_value(true))
+        .arg(Arg::with_name("checkpoint_every")
+            .help("save a snapshot to the checkpoint file every N generations, keeping the one before it as `<file>.prev`, to resume a long run with --load after a crash")
+            .long("checkpoint-every")
+            .takes_value(true)
+            .value_name("N")
+            .requires("checkpoint_file")
+            .conflicts_with_all(&["replay", "dump"]))
+        .arg(Arg::with_name("checkpoint_file")
+            .help("the snapshot file to save checkpoints to with --checkpoint-every")
+            .long("checkpoint-file")
+            .takes_value(true)
+            .requires("checkpoint_every"))
         .arg(Arg::with_name("cell_size")
             .help("the display size of each cell in pixels")
             .short("c")
             .long("cell")
-            .takes_value(true));
+            .takes_value(true))
+        .arg(Arg::with_name("fit_view")
+            .help("zoom and pan to follow the live cells instead of showing the whole board, e.g. for a lone spaceship on a huge grid")
+            .long("fit-view")
+            .conflicts_with_all(&["compare", "headless", "frames_dir"]))
+        .arg(Arg::with_name("fullscreen")
+            .help("show the board full screen, stretched to fill the display")
+            .long("fullscreen")
+            .conflicts_with_all(&["fit_view", "headless", "frames_dir", "dump"]))
+        .arg(Arg::with_name("letterbox")
+            .help("with --fullscreen, keep cells square by scaling the board evenly and centering it, with bars around it")
+            .long("letterbox")
+            .requires("fullscreen"))
+        .arg(Arg::with_name("dynamic_speed")
+            .help("speed up the framerate while the population changes quickly and slow it down as it stabilizes")
+            .long("dynamic-speed"))
+        .arg(Arg::with_name("autoplay")
+            .help("start playing the simulation immediately instead of starting paused")
+            .long("autoplay"))
+        .arg(Arg::with_name("pause_at")
+            .help("the generation to pause the simulation at")
+            .long("pause-at")
+            .takes_value(true))
+        .arg(Arg::with_name("profile")
+            .help("show how long each generation takes to compute in the window title")
+            .long("profile"))
+        .arg(Arg::with_name("count_format")
+            .help("how to write generation counts in the window title: `plain` digits, `grouped` in thousands \
+                   like 1,234,567, or `scientific` like 1.23e6")
+            .long("count-format")
+            .takes_value(true)
+            .possible_values(&["plain", "grouped", "scientific"])
+            .default_value("plain"))
+        .arg(Arg::with_name("trail")
+            .help("the number of generations dead cells stay visible as a fading trail")
+            .long("trail")
+            .takes_value(true))
+        .arg(Arg::with_name("activity_heatmap")
+            .help("start coloring by activity, where dead cells glow warmer the more cells were recently born or died there")
+            .long("activity-heatmap"))
+        .arg(Arg::with_name("heat_decay")
+            .help("the fraction of its heat each cell loses every generation when coloring by activity, from 0 to 1 [default: 0.1]")
+            .long("heat-decay")
+            .takes_value(true))
+        .arg(Arg::with_name("age_bands")
+            .help("start coloring live cells by bands of age, each the first age of the band and its color, like `1:#ffffff,3:#78aaff,10:#2846dc`")
+            .long("age-bands")
+            .takes_value(true)
+            .conflicts_with("activity_heatmap"))
+        .arg(Arg::with_name("high_contrast")
+            .help("draw live cells white and all other cells black, with black grid lines between cells, for low vision; \
+                   cells are 10 pixels unless --cell is given")
+            .long("high-contrast")
+            .conflicts_with_all(&["bg_image", "headless", "frames_dir", "dump"]))
+        .arg(Arg::with_name("redraw_paused")
+            .help("keep redrawing the window every frame while paused, instead of only when something shown changes")
+            .long("redraw-paused")
+            .conflicts_with_all(&["headless", "frames_dir", "dump"]))
+        .arg(Arg::with_name("smooth")
+            .help("cross-fade cells that are born or die over the rest of each frame, for a smoother look at low framerates; \
+                   stepping is not slowed")
+            .long("smooth")
+            .conflicts_with_all(&["headless", "frames_dir", "dump"]))
+        .arg(Arg::with_name("outline")
+            .help("draw cells as outlines instead of filled squares, an eighth of a cell thick and at least a pixel")
+            .long("outline")
+            .conflicts_with_all(&["headless", "frames_dir", "dump"]))
+        .arg(Arg::with_name("frames_dir")
+            .help("the directory to write PNG images of each generation to, instead of opening a window")
+            .long("frames-dir")
+            .takes_value(true)
+            .requires("frames")
+            .conflicts_with("headless"))
+        .arg(Arg::with_name("frames")
+            .help("the number of generations to write PNG images of")
+            .long("frames")
+            .takes_value(true)
+            .requires("frames_dir"))
+        .arg(Arg::with_name("frames_recenter")
+            .help("wrap each PNG image around the edges of the board so the live cells are whole and centered; the game itself is not moved")
+            .long("frames-recenter")
+            .requires("frames_dir"))
+        .arg(Arg::with_name("frames_marker")
+            .help("label each PNG image with its generation number in a corner, e.g. for self-labeling videos")
+            .long("frames-marker")
+            .takes_value(true)
+            .value_name("CORNER")
+            .possible_values(&["top-left", "top-right", "bottom-left", "bottom-right"])
+            .requires("frames_dir"))
+        .arg(Arg::with_name("frames_marker_scale")
+            .help("the size in pixels of each dot of the generation label's font, which is 7 dots tall [default: 2]")
+            .long("frames-marker-scale")
+            .takes_value(true)
+            .requires("frames_marker"))
+        .arg(Arg::with_name("birth")
+            .help("the live neighbor counts at which dead cells are born, separated by commas, e.g. `3` or `3,6` [default: 3]")
+            .long("birth")
+            .takes_value(true)
+            .conflicts_with("replay"))
+        .arg(Arg::with_name("survive")
+            .help("the live neighbor counts at which live cells survive, separated by commas, e.g. `2,3`; empty for none [default: 2,3]")
+            .long("survive")
+            .takes_value(true)
+            .empty_values(true)
+            .conflicts_with("replay"))
+        .arg(Arg::with_name("backend")
+            .help("how to compute each generation: `sparse` is fastest for mostly empty boards, `dense` for busy ones")
+            .long("backend")
+            .takes_value(true)
+            .possible_values(&["sparse", "dense"])
+            .default_value("sparse"))
+        .arg(Arg::with_name("step_algorithm")
+            .help("how the sparse backend finds each generation: `count` pushes neighbor counts out from live cells, \
+                   `scan` scans around live cells, remembering the dead cells it has checked, and is slower")
+            .long("step-algorithm")
+            .takes_value(true)
+            .possible_values(&["count", "scan"])
+            .default_value("count"))
+        .arg(Arg::with_name("halt_when_extinct")
+            .help("stop counting generations once every cell has died")
+            .long("halt-when-extinct"))
+        .arg(Arg::with_name("analyze")
+            .help("when the simulation ends, list the objects on the board, naming common ones")
+            .long("analyze"))
+        .arg(Arg::with_name("quiet")
+            .help("don't print a summary of the run when the simulation ends")
+            .short("q")
+            .long("quiet"))
+        .arg(Arg::with_name("exit_code")
+            .help("in headless mode, exit with a code for how the game ended: 0 if it settled into a still life or \
+                   oscillator, 3 if it was still changing at the last generation, 4 if every cell died; \
+                   1 and 2 still mean a failure or invalid arguments")
+            .long("exit-code")
+            .requires("headless"))
+        .arg(Arg::with_name("headless")
+            .help("print each generation to stdout as text instead of opening a window")
+            .long("headless"))
+        .arg(Arg::with_name("generations")
+            .help("the number of generations to run in headless mode; omit to run forever")
+            .short("g")
+            .long("generations")
+            .takes_value(true)
+            .requires("headless"))
+        .arg(Arg::with_name("timelapse")
+            .help("with --headless or --frames-dir, only print or write every Kth generation, though every generation is run \
+                   and numbered; e.g. with --frames, for compact videos of long runs")
+            .long("timelapse")
+            .value_name("K")
+            .takes_value(true)
+            .conflicts_with_all(&["replay", "watch_region"]))
+        .arg(Arg::with_name("max_sps")
+            .help("the most generations to compute per second in the window, whatever the framerate and evolutions per \
+                   frame, e.g. to keep the machine cool during long demos; 0 for no limit")
+            .long("max-sps")
+            .takes_value(true)
+            .conflicts_with_all(&["headless", "frames_dir", "dump"]))
+        .arg(Arg::with_name("tick_ms")
+            .help("the minimum time between generations in headless mode, in milliseconds; omit to run as fast as possible")
+            .long("tick-ms")
+            .takes_value(true)
+            .requires("headless"))
+        .arg(Arg::with_name("watch_region")
+            .help("in headless mode, instead of printing each generation, run until a spaceship lies wholly outside this region \
+                   and report the generation it was emitted at; given as `{row},{col},{rows}x{cols}`, e.g. around a gun; \
+                   requires --generations to bound the search, since a region may never emit one")
+            .long("watch-region")
+            .takes_value(true)
+            .requires_all(&["headless", "generations"])
+            .conflicts_with("replay"))
+        .arg(Arg::with_name("ship")
+            .help("the pattern file of one phase of the spaceship to watch for, in any position and orientation; omit to watch for any spaceship")
+            .long("ship")
+            .takes_value(true)
+            .requires("watch_region"));
+
+    #[cfg(feature = "bg-image")]
+    let cli = cli.arg(Arg::with_name("bg_image")
+        .help("the image file to show behind the board, stretched to fill it, with live cells drawn over it and dead cells showing it through")
+        .long("bg-image")
+        .takes_value(true)
+        .conflicts_with_all(&["headless", "frames_dir", "dump"]));
 
     let matches = cli.get_matches();
+    if let Some(formats) = matches.subcommand_matches("formats") {
+        print_formats(formats.is_present("json"));
+        return;
+    }
+    if let Some(convert) = matches.subcommand_matches("convert") {
+        convert_pattern(convert);
+        return;
+    }
+    if let Some(info) = matches.subcommand_matches("info") {
+        print_info(info);
+        return;
+    }
+    if let Some(fate) = matches.subcommand_matches("fate") {
+        print_fate(fate);
+        return;
+    }
+    if let Some(hashrun) = matches.subcommand_matches("hashrun") {
+        print_hashes(hashrun);
+        return;
+    }
+    if let Some(diff) = matches.subcommand_matches("diff") {
+        print_diff(diff);
+        return;
+    }
+    let options = match Options::from_matches(&matches) {
+        Ok(options) => options,
+        Err(message) => exit_with_error(&message),
+    };
+
+    let player = options.replay.map(|recording| {
+        File::open(recording).map_err(RecordingError::from)
+            .and_then(|file| DeltaPlayer::new(BufReader::new(file)))
+            .unwrap_or_else(|error| exit_with_error(&format!("failed to load `{}`: {}", recording, error)))
+    });
+
+    let mut compare = None;
+    let mut catalog = None;
+    let mut game = if let Some(player) = &player {
+        player.game().clone()
+    } else if let Some(snapshot) = options.load {
+        load_snapshot(snapshot)
+            .unwrap_or_else(|error| exit_with_error(&format!("failed to load `{}`: {}", snapshot, error)))
+    } else {
+        let (rows, cols) = options.dimensions.unwrap();
+        let pattern_options = PatternOptions {
+            coord_order: options.coord_order,
+            autofit: options.autofit,
+            scale: options.pattern_scale,
+        };
+        match (options.cells, options.file) {
+            (Some(cells), _) => {
+                let game = GameOfLife::from_coords(cells, rows, cols, &pattern_options)
+                    .unwrap_or_else(|error| exit_with_error(&format!("invalid --cells `{}`: {}", cells, error)));
+                if (game.rows, game.cols) != (rows, cols) {
+                    eprintln!("grew game to {}x{} to fit the pattern", game.rows, game.cols);
+                }
+                game
+            },
+            (None, Some(file)) => {
+                let game = GameOfLife::from_file_with(file, rows, cols, &pattern_options)
+                    .unwrap_or_else(|error| exit_with_error(&format!("failed to load `{}`: {}", file, error)));
+                if (game.rows, game.cols) != (rows, cols) {
+                    eprintln!("grew game to {}x{} to fit the pattern", game.rows, game.cols);
+                }
+                warn_near_edges(file, &game, options.load_margin);
+                game
+            },
+            (None, None) => match (options.compare, options.catalog) {
+                (Some((file_a, file_b)), _) => {
+                    let load = |file: &str| {
+                        let game = GameOfLife::from_file_with(file, rows, cols, &pattern_options)
+                            .unwrap_or_else(|error| exit_with_error(&format!("failed to load `{}`: {}", file, error)));
+                        warn_near_edges(file, &game, options.load_margin);
+                        game
+                    };
+                    compare = Some(load(file_b));
+                    load(file_a)
+                },
+                (None, Some(dir)) => {
+                    let loaded = Catalog::load(Path::new(dir), rows, cols, &pattern_options)
+                        .unwrap_or_else(|error| exit_with_error(&format!("failed to read `{}`: {}", dir, error)));
+                    for (path, error) in loaded.skipped() {
+                        eprintln!("warning: skipping `{}`: {}", path.display(), error);
+                    }
+                    let game = match loaded.current() {
+                        Some((_, game)) => game.clone(),
+                        None => exit_with_error(&format!("no pattern files in `{}` could be loaded", dir)),
+                    };
+                    catalog = Some(loaded);
+                    game
+                },
+                (None, None) => match options.compare_seeds {
+                    Some((seed_a, seed_b)) => {
+                        let soup = |seed| World::soup(rows, cols, SOUP_DENSITY, seed)
+                            .unwrap_or_else(|error| exit_with_error(&error.to_string()));
+                        compare = Some(soup(seed_b));
+                        soup(seed_a)
+                    },
+                    None => GameOfLife::random(rows, cols),
+                },
+            },
+        }
+    };
+
+    for game in std::iter::once(&mut game).chain(compare.as_mut()) {
+        configure(game, &options);
+    }
+    if let Some(rule) = options.compare_rule {
+        compare.get_or_insert_with(|| game.clone()).set_rule(rule);
+    }
+
+    if options.dump {
+        println!("{}", game);
+        return;
+    }
+
+    let start = Instant::now();
+    let (mut game, stats) = if let Some(player) = player {
+        run_replay(player, &options)
+    } else if let Some(dir) = options.frames_dir {
+        let mut renderer = PngRender::new(Path::new(dir), options.cell_size, options.frames).unwrap_or_else(|error| {
+            eprintln!("error: failed to create `{}`: {}", dir, error);
+            process::exit(1);
+        });
+        renderer.set_recenter(options.frames_recenter);
+        renderer.set_generation_marker(options.frames_marker);
+        run_rendered(game, &mut renderer, None, &options)
+    } else if let Some(region) = options.watch_region {
+        let signature = options.ship.map(|path| {
+            let ship = GameOfLife::from_file_fitted(path, &PatternOptions { coord_order: options.coord_order, ..PatternOptions::default() })
+                .unwrap_or_else(|error| exit_with_error(&format!("failed to load `{}`: {}", path, error)));
+            if ship.is_extinct() {
+                exit_with_error(&format!("`{}` has no live cells to watch for", path));
+            }
+            ship.live_cells()
+        });
+        let mut watch = ShipWatch { region, signature, emitted: None };
+        let (game, stats) = run_rendered(game, &mut watch, options.generations, &options);
+        match watch.emitted {
+            Some((generation, ship)) => {
+                let r = ship.cells.iter().map(|cell| cell.r).min().unwrap();
+                let c = ship.cells.iter().map(|cell| cell.c).min().unwrap();
+                println!("spaceship emitted at generation {}: ({}, {}): {}", generation, r, c, ship);
+            },
+            None => println!("no spaceship left the region in {} generations", stats.generations()),
+        }
+        (game, stats)
+    } else if options.headless {
+        run_rendered(game, &mut TextRender::new(io::stdout()), options.generations, &options)
+    } else {
+        run(game, compare, catalog, &options).unwrap_or_else(|message| {
+            eprintln!("error: failed to open a window: {}", message);
+            eprintln!(
+                "hint: on a machine without a display, use --headless to print generations as text, \
+                 or --frames-dir to write them as images"
+            );
+            process::exit(1);
+        })
+    };
+
+    let elapsed = start.elapsed();
+    let behavior = (!options.quiet || options.exit_code).then(|| match stats.extinct_at() {
+        Some(_) => Behavior::Died,
+        None => game.classify(SUMMARY_LOOKAHEAD),
+    });
+    if !options.quiet {
+        print_summary(&game, &stats, behavior.unwrap(), elapsed);
+    }
+
+    if options.analyze {
+        print_objects(&game);
+    }
+
+    if let Some(snapshot) = options.save {
+        if let Err(error) = save_snapshot(&game, snapshot) {
+            eprintln!("error: failed to save `{}`: {}", snapshot, error);
+            process::exit(1);
+        }
+    }
+
+    if options.exit_code {
+        process::exit(match behavior.unwrap() {
+            Behavior::Stable | Behavior::Oscillating { .. } => EXIT_SETTLED,
+            Behavior::Growing | Behavior::Unsettled => EXIT_ACTIVE,
+            Behavior::Died => EXIT_EXTINCT,
+        });
+    }
+}
+
+/// Apply the settings given on the command line to a game.
+fn configure(game: &mut GameOfLife, options: &Options) {
+    if let Some(rule) = options.rule {
+        game.set_rule(rule);
+    }
+    game.set_halt_when_extinct(options.halt_when_extinct);
+    game.set_backend(options.backend);
+    game.set_step_algorithm(options.step_algorithm);
+}
+
+fn load_snapshot(path: &str) -> Result<GameOfLife, Box<dyn std::error::Error>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    Ok(GameOfLife::load_snapshot(&mut reader)?)
+}
+
+fn save_snapshot(game: &GameOfLife, path: &str) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    game.save_snapshot(&mut writer)
+}
+
+/// Start checkpointing a game as given on the command line, if asked to.
+fn checkpoints(game: &GameOfLife, options: &Options) -> Option<Checkpoints> {
+    options.checkpoint.map(|(path, every)| Checkpoints::new(Path::new(path), every, game.generation()))
+}
+
+/// Save a checkpoint of a game if one is due. A checkpoint that cannot be
+/// written is reported, and the run goes on so the next one can be tried.
+fn save_checkpoint(checkpoints: Option<&mut Checkpoints>, game: &GameOfLife) {
+    if let Some(checkpoints) = checkpoints {
+        if let Err(error) = checkpoints.save_if_due(game) {
+            eprintln!("error: failed to write checkpoint `{}`: {}", checkpoints.path().display(), error);
+        }
+    }
+}
+
+/// Print an error message for invalid input and exit.
+fn exit_with_error(message: &str) -> ! {
+    eprintln!("error: {}", message);
+    process::exit(2);
+}
+
+/// Print the pattern file formats that can be loaded to stdout, as text or
+/// as JSON.
+fn print_formats(json: bool) {
+    let formats = pattern_formats();
+    if json {
+        let objects: Vec<String> = formats.iter()
+            .map(|format| format!(
+                "{{\"name\":{},\"description\":{},\"example\":{},\"extensions\":[{}]}}",
+                json_string(format.name), json_string(format.description), json_string(format.example),
+                format.extensions.iter().map(|extension| json_string(extension)).collect::<Vec<_>>().join(","),
+            ))
+            .collect();
+        println!("[{}]", objects.join(","));
+        return;
+    }
+
+    for (i, format) in formats.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let extensions: Vec<String> = format.extensions.iter().map(|extension| format!(".{}", extension)).collect();
+        println!("{} ({}): {}", format.name, extensions.join(", "), format.description);
+        for line in format.example.lines() {
+            println!("    {}", line);
+        }
+    }
+}
+
+/// Read a pattern file and print a summary of it without running it, see
+/// `pattern_info`.
+fn print_info(matches: &ArgMatches) {
+    let file = matches.value_of("file").unwrap();
+    let coord_order = match matches.value_of("coord_order") {
+        Some("xy") => CoordOrder::XY,
+        _ => CoordOrder::RowCol,
+    };
+    let options = PatternOptions { coord_order, ..PatternOptions::default() };
+    let info = pattern_info(file, &options)
+        .unwrap_or_else(|error| exit_with_error(&format!("failed to load `{}`: {}", file, error)));
+
+    if matches.is_present("json") {
+        let string = |value: Option<String>| value.map_or(String::from("null"), |value| json_string(&value));
+        let comments: Vec<String> = info.meta.comments.iter().map(|comment| json_string(comment)).collect();
+        println!(
+            "{{\"format\":{},\"rule\":{},\"rows\":{},\"cols\":{},\"population\":{},\"name\":{},\"author\":{},\"comments\":[{}]}}",
+            json_string(info.format.name), string(info.rule.map(|rule| rule.to_string())), info.rows, info.cols,
+            info.population, string(info.meta.name), string(info.meta.author), comments.join(","),
+        );
+        return;
+    }
+
+    println!("format: {}", info.format.name);
+    match info.rule {
+        Some(rule) => println!("rule: {}", rule),
+        None => println!("rule: none declared"),
+    }
+    println!("size: {}x{}", info.rows, info.cols);
+    println!("population: {}", info.population);
+    if let Some(name) = &info.meta.name {
+        println!("name: {}", name);
+    }
+    if let Some(author) = &info.meta.author {
+        println!("author: {}", author);
+    }
+    for comment in &info.meta.comments {
+        println!("comment: {}", comment);
+    }
+}
+
+/// Load a pattern file and report how it ends up, see `GameOfLife::fate`.
+fn print_fate(matches: &ArgMatches) {
+    let file = matches.value_of("file").unwrap();
+    let generations = parse_value(matches, "generations", "generations must be a whole number")
+        .unwrap_or_else(|message| exit_with_error(&message))
+        .unwrap_or(DEFAULT_FATE_GENERATIONS);
+    let (rows, cols) = matches.value_of("dimensions")
+        .map(parse_dimensions)
+        .transpose()
+        .unwrap_or_else(|message| exit_with_error(&message))
+        .unwrap_or(DEFAULT_OFFLINE_DIMENSIONS);
+
+    let mut game = GameOfLife::from_file(file, rows, cols)
+        .unwrap_or_else(|error| exit_with_error(&format!("failed to load `{}`: {}", file, error)));
+    let fate = game.fate(generations);
+    let period = match fate.behavior {
+        Behavior::Stable => Some(1),
+        Behavior::Oscillating { period } => Some(period),
+        _ => None,
+    };
+
+    if matches.is_present("json") {
+        let behavior = match fate.behavior {
+            Behavior::Oscillating { .. } => String::from("oscillating"),
+            behavior => behavior.to_string(),
+        };
+        let number = |value: Option<usize>| value.map_or(String::from("null"), |value| value.to_string());
+        println!(
+            "{{\"behavior\":{},\"settled\":{},\"settled_at\":{},\"period\":{},\"population\":{}}}",
+            json_string(&behavior), fate.settled_at.is_some(), number(fate.settled_at), number(period), fate.population,
+        );
+        return;
+    }
+
+    println!("{}", fate.behavior);
+    match fate.settled_at {
+        Some(generation) => println!("settled at generation {}", generation),
+        None => println!("did not settle within {} generations", generations),
+    }
+    println!("final population: {}", fate.population);
+}
+
+/// Run a pattern file and print the hash of the board at each generation,
+/// from the first, as `{generation} {hash}` with the hash in hex, or only
+/// the hash chaining them all together. See `GameOfLife::state_hash`.
+fn print_hashes(matches: &ArgMatches) {
+    let file = matches.value_of("file").unwrap();
+    let generations: u128 = parse_value(matches, "generations", "generations must be a whole number")
+        .unwrap_or_else(|message| exit_with_error(&message))
+        .unwrap();
+    let (rows, cols) = matches.value_of("dimensions")
+        .map(parse_dimensions)
+        .transpose()
+        .unwrap_or_else(|message| exit_with_error(&message))
+        .unwrap_or(DEFAULT_OFFLINE_DIMENSIONS);
+
+    let mut game = GameOfLife::from_file(file, rows, cols)
+        .unwrap_or_else(|error| exit_with_error(&format!("failed to load `{}`: {}", file, error)));
+    let combined = matches.is_present("combined");
+    let mut chain = 0;
+    for generation in 0..=generations {
+        if generation > 0 {
+            game.step();
+        }
+        let hash = game.state_hash();
+        match combined {
+            true => chain = chain_hash(chain, hash),
+            false => println!("{} {:016x}", generation, hash),
+        }
+    }
+    if combined {
+        println!("{:016x}", chain);
+    }
+}
+
+/// Load two pattern files, optionally advance the first, and print the cells
+/// that differ: `-` for cells alive only in the first, `+` for cells alive
+/// only in the second. Exits with 1 if any cells differ.
+fn print_diff(matches: &ArgMatches) {
+    let (file_a, file_b) = (matches.value_of("a").unwrap(), matches.value_of("b").unwrap());
+    let gens: usize = parse_value(matches, "gens", "gens must be a whole number of generations")
+        .unwrap_or_else(|message| exit_with_error(&message))
+        .unwrap_or(0);
+    let (rows, cols) = matches.value_of("dimensions")
+        .map(parse_dimensions)
+        .transpose()
+        .unwrap_or_else(|message| exit_with_error(&message))
+        .unwrap_or(DEFAULT_OFFLINE_DIMENSIONS);
+
+    let load = |file: &str| GameOfLife::from_file(file, rows, cols)
+        .unwrap_or_else(|error| exit_with_error(&format!("failed to load `{}`: {}", file, error)));
+    let (mut a, b) = (load(file_a), load(file_b));
+    a.step_n(gens);
+    let delta = a.diff(&b).unwrap_or_else(|error| exit_with_error(&format!("cannot compare the patterns: {}", error)));
+    for cell in &delta.died {
+        println!("- {},{}", cell.r, cell.c);
+    }
+    for cell in &delta.born {
+        println!("+ {},{}", cell.r, cell.c);
+    }
+    let differ = delta.died.len() + delta.born.len();
+    println!(
+        "{} cells differ: {} only in `{}`, {} only in `{}`",
+        differ, delta.died.len(), file_a, delta.born.len(), file_b
+    );
+    if differ > 0 {
+        process::exit(1);
+    }
+}
+
+/// Load a pattern file and write it in the format given by the extension of
+/// the output file, without simulating it.
+fn convert_pattern(matches: &ArgMatches) {
+    let (input, output) = (matches.value_of("input").unwrap(), matches.value_of("output").unwrap());
+    let coord_order = match matches.value_of("coord_order") {
+        Some("xy") => CoordOrder::XY,
+        _ => CoordOrder::RowCol,
+    };
+    let cell_size = parse_value(matches, "cell_size", "cell size must be a positive whole number")
+        .unwrap_or_else(|message| exit_with_error(&message))
+        .unwrap_or(DEFAULT_CELL_SIZE);
+    if cell_size == 0 {
+        exit_with_error("cell size must be a positive whole number");
+    }
+
+    let output_path = Path::new(output);
+    let png = output_path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("png"));
+    let format = pattern_format_for_path(output_path);
+    if !png && format.is_none() {
+        let extensions: Vec<String> = pattern_formats().iter()
+            .flat_map(|format| format.extensions.iter().map(|extension| format!(".{}", extension)))
+            .chain([String::from(".png")])
+            .collect();
+        exit_with_error(&format!(
+            "cannot tell what format to write `{}` in; end it in one of {}", output, extensions.join(", "),
+        ));
+    }
+
+    let region = matches.value_of("region").map(|region| parse_region(region, "region"))
+        .transpose()
+        .unwrap_or_else(|message| exit_with_error(&message));
+
+    let failed = |error: &dyn std::fmt::Display| -> ! { exit_with_error(&format!("failed to load `{}`: {}", input, error)) };
+    let snapshot = File::open(input).map_err(SnapshotError::from)
+        .and_then(|file| GameOfLife::load_snapshot(&mut BufReader::new(file)));
+    let (game, wraps) = match snapshot {
+        Ok(game) => (game, true),
+        Err(SnapshotError::NotASnapshot) => {
+            let options = PatternOptions { coord_order, ..PatternOptions::default() };
+            (GameOfLife::from_file_fitted(input, &options).unwrap_or_else(|error| failed(&error)), false)
+        },
+        Err(error) => failed(&error),
+    };
+    // A snapshot's live cells may straddle the edges of the board, so they
+    // are unwrapped into one piece, which needs a choice of seam when more
+    // than one is as good.
+    let region = region.or_else(|| match game.pattern_regions().as_slice() {
+        _ if !wraps => None,
+        [] => None,
+        [region] => Some(*region).filter(|region| region.r + region.rows > game.rows || region.c + region.cols > game.cols),
+        regions => {
+            let regions: Vec<_> = regions.iter()
+                .map(|region| format!("{},{},{}x{}", region.r, region.c, region.rows, region.cols))
+                .collect();
+            exit_with_error(&format!(
+                "the live cells of `{}` can be unwrapped across more than one seam; pick one with --region {}",
+                input, regions.join(" or "),
+            ));
+        },
+    });
+    let game = match region {
+        Some(region) => game.cropped(region),
+        None => game,
+    };
+    let result = File::create(output_path).and_then(|file| {
+        let mut writer = BufWriter::new(file);
+        match format {
+            Some(format) => format.write(&game, &mut writer)?,
+            None => {
+                let pixels = render_to_rgba(&game, cell_size);
+                write_png(&mut writer, game.cols * cell_size, game.rows * cell_size, &pixels)?;
+            },
+        }
+        writer.flush()
+    });
+    if let Err(error) = result {
+        eprintln!("error: failed to write `{}`: {}", output, error);
+        process::exit(1);
+    }
+}
+
+/// A string as a quoted JSON string.
+fn json_string(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Settings for a run of the simulator, parsed from the command line.
+struct Options<'a> {
+    file: Option<&'a str>,  // pattern file to start with, if any
+    cells: Option<&'a str>,  // live cells to start with, written out as coordinates, if any
+    compare: Option<(&'a str, &'a str)>,  // pattern files to run side by side, if comparing
+    compare_seeds: Option<(u64, u64)>,  // seeds of random soups to run side by side, if comparing
+    compare_rule: Option<RuleString>,  // rule the second game runs under, if comparing rules
+    load: Option<&'a str>,  // snapshot file to resume from, if any
+    save: Option<&'a str>,  // snapshot file to save to at the end, if any
+    checkpoint: Option<(&'a str, u128)>,  // snapshot file to checkpoint to and generations between checkpoints, if checkpointing
+    record: Option<&'a str>,  // file to record headless generations to, if any
+    replay: Option<&'a str>,  // recording to print instead of simulating, if any
+    record_macro: Option<&'a str>,  // file to record window key presses to, if any
+    play_macro: Option<&'a str>,  // key macro to play back in the window, if any
+    keymap: Option<&'a str>,  // keymap file to rebind the window's keys with, if any
+    #[cfg(feature = "bg-image")]
+    bg_image: Option<&'a str>,  // image file to show behind the board, if any
+    catalog: Option<&'a str>,  // directory of pattern files to browse, if any
+    dimensions: Option<(usize, usize)>,  // rows and columns of a new game
+    autofit: Option<(usize, usize)>,  // largest rows and columns to grow a game to, if growing
+    pattern_scale: Option<usize>,  // side length of the square each pattern cell becomes, if scaling
+    load_margin: usize,  // fewest cells between a loaded pattern and the edges without a warning
+    dump: bool,  // whether to print the loaded board and exit
+    coord_order: CoordOrder,  // order of the numbers in coords files
+    cell_size: usize,  // display size of each cell in pixels
+    fit_view: bool,  // whether the view follows the live cells
+    fullscreen: bool,  // whether the window fills the display
+    letterbox: bool,  // whether the board keeps its shape when scaled to fill the display
+    dynamic_speed: bool,  // whether framerate follows population activity
+    profile: bool,  // whether to show step timing
+    count_format: CountFormat,  // how generation counts are written in the title
+    high_contrast: bool,  // whether to draw cells in high contrast with grid lines
+    outline: bool,  // whether to draw cells as outlines
+    smooth: bool,  // whether to cross-fade changed cells between generations
+    redraw_paused: bool,  // whether to redraw every frame while paused
+    autoplay: bool,  // whether to start playing instead of paused
+    pause_at: Option<u128>,  // generation to pause at, if any
+    trail: usize,  // generations dead cells stay visible
+    activity_heatmap: bool,  // whether to start coloring by activity
+    heat_decay: Option<f32>,  // fraction of heat lost each generation, if given
+    age_bands: Option<AgeBands>,  // colors of live cells by age to start coloring with, if given
+    frames_dir: Option<&'a str>,  // directory to write generation images to
+    frames: usize,  // number of generation images to write
+    frames_recenter: bool,  // whether generation images are wrapped to center the live cells
+    frames_marker: Option<(Corner, usize)>,  // corner and scale of the generation label on images, if labeled
+    rule: Option<RuleString>,  // rule given on the command line, replacing the game's own, if any
+    backend: Backend,  // how generations are computed
+    step_algorithm: StepAlgorithm,  // how the sparse backend finds generations
+    halt_when_extinct: bool,  // whether generations stop counting on an empty board
+    analyze: bool,  // whether to list the objects on the board at the end
+    quiet: bool,  // whether to leave out the run summary at the end
+    exit_code: bool,  // whether the exit code tells how the game ended
+    headless: bool,  // whether to print text instead of opening a window
+    generations: Option<u128>,  // generations to run in headless mode
+    tick: Option<Duration>,  // minimum time between headless generations
+    timelapse: u128,  // generations between those printed or written, 1 for all of them
+    max_sps: Option<u128>,  // most generations computed per second in the window, if limited
+    watch_region: Option<Region>,  // region to watch for spaceships leaving in headless mode, if any
+    ship: Option<&'a str>,  // pattern file of the spaceship to watch for, if not any
+}
+
+impl<'a> Options<'a> {
+    /// Validate and collect the command line arguments, returning a message
+    /// describing the problem if any argument is invalid.
+    fn from_matches(matches: &'a ArgMatches) -> Result<Options<'a>, String> {
+        let dimensions = matches.value_of("dimensions").map(parse_dimensions).transpose()?;
+        let autofit = match matches.value_of("autofit_max") {
+            Some(max) => Some(parse_dimensions(max).map_err(|message| format!("autofit max {}", message))?),
+            None if matches.is_present("autofit") => Some(DEFAULT_AUTOFIT_MAX),
+            None => None,
+        };
+        let has_pattern = matches.is_present("file") || matches.is_present("cells");
+        for (name, flag) in [("dump", "--dump"), ("autofit", "--autofit"), ("pattern_scale", "--pattern-scale")] {
+            if matches.is_present(name) && !has_pattern {
+                return Err(format!("{} needs --file or --cells", flag));
+            }
+        }
+
+        let rule = match (matches.value_of("birth"), matches.value_of("survive")) {
+            (None, None) => None,
+            (birth, survive) => {
+                let birth = birth.map(|birth| parse_counts(birth, "birth")).transpose()?.unwrap_or(vec![3]);
+                if birth.is_empty() {
+                    return Err(String::from("birth must list at least one neighbor count"));
+                }
+                let survive = survive.map(|survive| parse_counts(survive, "survive")).transpose()?.unwrap_or(vec![2, 3]);
+                Some(RuleString::new(&birth, &survive).map_err(|error| error.to_string())?)
+            },
+        };
+
+        let high_contrast = matches.is_present("high_contrast");
+        let timelapse = parse_value(matches, "timelapse", "timelapse must be a positive whole number of generations")?
+            .unwrap_or(1);
+        if timelapse == 0 {
+            return Err(String::from("timelapse must be a positive whole number of generations"));
+        }
+        if timelapse > 1 && !matches.is_present("headless") && !matches.is_present("frames_dir") {
+            return Err(String::from("timelapse needs --headless or --frames-dir"));
+        }
+        let cell_size = parse_value(matches, "cell_size", "cell size must be a positive whole number")?
+            .unwrap_or(if high_contrast { HIGH_CONTRAST_CELL_SIZE } else { DEFAULT_CELL_SIZE });
+        if cell_size == 0 {
+            return Err(String::from("cell size must be a positive whole number"));
+        }
+
+        let marker_scale = parse_value(matches, "frames_marker_scale", "marker scale must be a positive whole number")?
+            .unwrap_or(DEFAULT_MARKER_SCALE);
+        if marker_scale == 0 {
+            return Err(String::from("marker scale must be a positive whole number"));
+        }
+        let frames_marker = matches.value_of("frames_marker").map(|corner| {
+            let corner = match corner {
+                "top-right" => Corner::TopRight,
+                "bottom-left" => Corner::BottomLeft,
+                "bottom-right" => Corner::BottomRight,
+                _ => Corner::TopLeft,
+            };
+            (corner, marker_scale)
+        });
+
+        let pattern_scale = parse_value(matches, "pattern_scale", "pattern scale must be a positive whole number")?;
+        if pattern_scale == Some(0) {
+            return Err(String::from("pattern scale must be a positive whole number"));
+        }
+
+        let checkpoint_every = parse_value(matches, "checkpoint_every", "checkpoint interval must be a positive whole number")?;
+        if checkpoint_every == Some(0) {
+            return Err(String::from("checkpoint interval must be a positive whole number"));
+        }
+        let checkpoint = matches.value_of("checkpoint_file").zip(checkpoint_every);
+
+        let load_margin = parse_value(matches, "load_margin", "load margin must be a whole number")?.unwrap_or(0);
+
+        let heat_decay = parse_value(matches, "heat_decay", "heat decay must be a number greater than 0 and at most 1")?;
+        if heat_decay.is_some_and(|decay: f32| !(decay > 0.0 && decay <= 1.0)) {
+            return Err(String::from("heat decay must be a number greater than 0 and at most 1"));
+        }
+
+        let compare_seeds = match matches.values_of("compare_seed") {
+            Some(seeds) => {
+                let seeds = seeds
+                    .map(|seed| seed.parse().map_err(|_| String::from("compare seeds must be whole numbers")))
+                    .collect::<Result<Vec<u64>, String>>()?;
+                Some((seeds[0], seeds[1]))
+            },
+            None => None,
+        };
+        let compare_rule = matches.value_of("compare_rule")
+            .map(|rule| rule.parse::<RuleString>().map_err(|error| error.to_string()))
+            .transpose()?;
+
+        let age_bands = matches.value_of("age_bands")
+            .map(|bands| bands.parse::<AgeBands>().map_err(|error| error.to_string()))
+            .transpose()?;
 
-    // get filename of game to start with
-    let file = matches.value_of("file");
+        Ok(Options {
+            file: matches.value_of("file"),
+            cells: matches.value_of("cells"),
+            compare: matches.values_of("compare").map(|mut files| (files.next().unwrap(), files.next().unwrap())),
+            compare_seeds,
+            compare_rule,
+            load: matches.value_of("load"),
+            save: matches.value_of("save"),
+            checkpoint,
+            record: matches.value_of("record"),
+            replay: matches.value_of("replay"),
+            record_macro: matches.value_of("record_macro"),
+            play_macro: matches.value_of("play_macro"),
+            keymap: matches.value_of("keymap"),
+            #[cfg(feature = "bg-image")]
+            bg_image: matches.value_of("bg_image"),
+            catalog: matches.value_of("catalog"),
+            dimensions, autofit, pattern_scale, load_margin, cell_size, rule, heat_decay, age_bands,
+            dump: matches.is_present("dump"),
+            coord_order: match matches.value_of("coord_order") {
+                Some("xy") => CoordOrder::XY,
+                _ => CoordOrder::RowCol,
+            },
+            fit_view: matches.is_present("fit_view"),
+            fullscreen: matches.is_present("fullscreen"),
+            letterbox: matches.is_present("letterbox"),
+            dynamic_speed: matches.is_present("dynamic_speed"),
+            profile: matches.is_present("profile"),
+            high_contrast,
+            outline: matches.is_present("outline"),
+            smooth: matches.is_present("smooth"),
+            redraw_paused: matches.is_present("redraw_paused"),
+            count_format: match matches.value_of("count_format") {
+                Some("grouped") => CountFormat::Grouped,
+                Some("scientific") => CountFormat::Scientific,
+                _ => CountFormat::Plain,
+            },
+            autoplay: matches.is_present("autoplay"),
+            pause_at: parse_value(matches, "pause_at", "pause at must be a whole number of generations")?,
+            trail: parse_value(matches, "trail", "trail must be a whole number of generations")?
+                .unwrap_or(0),
+            activity_heatmap: matches.is_present("activity_heatmap"),
+            frames_dir: matches.value_of("frames_dir"),
+            frames: parse_value(matches, "frames", "frames must be a whole number")?
+                .unwrap_or(0),
+            frames_recenter: matches.is_present("frames_recenter"),
+            frames_marker,
+            backend: match matches.value_of("backend") {
+                Some("dense") => Backend::Dense,
+                _ => Backend::Sparse,
+            },
+            step_algorithm: match matches.value_of("step_algorithm") {
+                Some("scan") => StepAlgorithm::ScanMemo,
+                _ => StepAlgorithm::NeighborCount,
+            },
+            halt_when_extinct: matches.is_present("halt_when_extinct"),
+            analyze: matches.is_present("analyze"),
+            quiet: matches.is_present("quiet"),
+            exit_code: matches.is_present("exit_code"),
+            headless: matches.is_present("headless"),
+            generations: parse_value(matches, "generations", "generations must be a whole number")?,
+            tick: parse_value(matches, "tick_ms", "tick must be a whole number of milliseconds")?
+                .map(Duration::from_millis),
+            timelapse,
+            max_sps: parse_value(matches, "max_sps", "max sps must be a whole number of generations per second")?
+                .filter(|max_sps| *max_sps > 0),
+            watch_region: matches.value_of("watch_region").map(|region| parse_region(region, "watch region")).transpose()?,
+            ship: matches.value_of("ship"),
+        })
+    }
+}
 
-    // get dimensions of board
-    let dimensions = matches.value_of("dimensions").unwrap();
-    let re = Regex::new(r"(?P<rows>[\d]+)x(?P<cols>[\d]+)").unwrap();
-    let dimensions = re.captures(dimensions).unwrap();
-    let (rows, cols) = (dimensions.name("rows").unwrap().as_str(), dimensions.name("cols").unwrap().as_str());
-    let (rows, cols) = (rows.parse::<usize>().unwrap(), cols.parse::<usize>().unwrap());
+/// Parse dimensions given as `{rows}x{cols}`.
+fn parse_dimensions(value: &str) -> Result<(usize, usize), String> {
+    let invalid = || String::from("dimensions must be like 50x30");
+    let re = Regex::new(r"^(?P<rows>\d+)x(?P<cols>\d+)$").unwrap();
+    let dimensions = re.captures(value).ok_or_else(invalid)?;
+    let rows = dimensions["rows"].parse::<usize>().map_err(|_| invalid())?;
+    let cols = dimensions["cols"].parse::<usize>().map_err(|_| invalid())?;
+    if rows == 0 || cols == 0 {
+        return Err(String::from("dimensions must be at least 1x1"));
+    }
+    Ok((rows, cols))
+}
 
-    // get size of cell
-    let cell_size = match matches.value_of("cell_size") {
-        Some(value) => value.parse::<usize>().unwrap(),
-        None => DEFAULT_CELL_SIZE,
+/// Parse a region given as `{row},{col},{rows}x{cols}`, calling it `name` in
+/// error messages.
+fn parse_region(value: &str, name: &str) -> Result<Region, String> {
+    let invalid = || format!("{} must be like 0,0,20x40", name);
+    let (r, rest) = value.split_once(',').ok_or_else(invalid)?;
+    let (c, dimensions) = rest.split_once(',').ok_or_else(invalid)?;
+    let r = r.trim().parse::<usize>().map_err(|_| invalid())?;
+    let c = c.trim().parse::<usize>().map_err(|_| invalid())?;
+    let (rows, cols) = parse_dimensions(dimensions.trim()).map_err(|message| format!("{} {}", name, message))?;
+    Ok(Region { r, c, rows, cols })
+}
+
+/// Parse neighbor counts separated by commas, each in `0..=8`.
+fn parse_counts(value: &str, name: &str) -> Result<Vec<u8>, String> {
+    value.split(',')
+        .map(str::trim)
+        .filter(|count| !count.is_empty())
+        .map(|count| match count.parse::<u8>() {
+            Ok(count) if count <= 8 => Ok(count),
+            _ => Err(format!("{} counts must be whole numbers from 0 to 8, separated by commas", name)),
+        })
+        .collect()
+}
+
+/// Parse the value of an optional argument, returning `message` as the error
+/// if the value is invalid.
+fn parse_value<T: FromStr>(matches: &ArgMatches, name: &str, message: &str) -> Result<Option<T>, String> {
+    matches.value_of(name)
+        .map(|value| value.parse::<T>().map_err(|_| String::from(message)))
+        .transpose()
+}
+
+/// Warn if a pattern loaded from a file comes closer to the edges of its game
+/// than `margin` cells.
+fn warn_near_edges(file: &str, game: &GameOfLife, margin: usize) {
+    if let Some(found) = game.edge_margin().filter(|found| *found < margin) {
+        eprintln!(
+            "warning: the pattern in `{}` is {} cells from the edge of the {}x{} grid, closer than the margin of {}; \
+             it may interact with itself across the edges",
+            file, found, game.rows, game.cols, margin,
+        );
+    }
+}
+
+/// Print a summary of a finished run to stderr, given how the game was
+/// classified at the end.
+fn print_summary(game: &GameOfLife, stats: &RunStats, behavior: Behavior, elapsed: Duration) {
+    let (peak, peak_generation) = stats.peak();
+    let outcome = match stats.extinct_at() {
+        Some(generation) => format!("extinct at generation {}", generation),
+        None => behavior.to_string(),
     };
+    eprintln!("run summary:");
+    eprintln!("  generations: {} ({} to {})", stats.generations(), stats.start_generation(), game.generation());
+    eprintln!("  final population: {}", stats.final_population());
+    eprintln!("  peak population: {} at generation {}", peak, peak_generation);
+    eprintln!("  outcome: {}", outcome);
+    eprintln!("  elapsed: {:.3} s", elapsed.as_secs_f64());
+}
+
+/// Print each object on the board and the position of its top left corner.
+fn print_objects(game: &GameOfLife) {
+    let objects = game.objects();
+    println!("{} objects at generation {}", objects.len(), game.generation());
+    for object in objects {
+        let r = object.cells.iter().map(|cell| cell.r).min().unwrap();
+        let c = object.cells.iter().map(|cell| cell.c).min().unwrap();
+        println!("  ({}, {}): {}", r, c, object);
+    }
+}
 
-    run(file, rows, cols, cell_size);
+/// Watches each generation of a headless run for the first spaceship to lie
+/// wholly outside a region, finishing the run once one does.
+struct ShipWatch {
+    region: Region,  // region the spaceship must leave, e.g. around a gun
+    signature: Option<Vec<Cell>>,  // live cells of a phase of the spaceship to watch for, if not any
+    emitted: Option<(u128, Object)>,  // generation the first spaceship was seen outside, and the spaceship
 }
 
-use sdl2::event::Event;
-use sdl2::keyboard::Keycode;
+impl Renderer for ShipWatch {
+    fn render(&mut self, game: &GameOfLife) -> io::Result<()> {
+        if self.emitted.is_none() {
+            self.emitted = game.escaped_spaceships(self.region, self.signature.as_deref())
+                .into_iter()
+                .next()
+                .map(|ship| (game.generation(), ship));
+        }
+        Ok(())
+    }
+
+    fn finished(&self) -> bool {
+        self.emitted.is_some()
+    }
+}
+
+/// Show each generation of a game with a renderer, stepping it between
+/// renders while the renderer is playing, until `generations` generations
+/// have been run or the renderer is finished.
+fn run_rendered(
+    mut game: GameOfLife,
+    renderer: &mut dyn Renderer,
+    generations: Option<u128>,
+    options: &Options,
+) -> (GameOfLife, RunStats) {
+    let mut stats = RunStats::new(&game);
+    let mut generation: u128 = 0;
+    let write_failed = |path: &str, error: std::io::Error| -> ! {
+        eprintln!("error: failed to write `{}`: {}", path, error);
+        process::exit(1);
+    };
+    let mut recorder = options.record.map(|path| {
+        File::create(path)
+            .and_then(|file| DeltaRecorder::new(BufWriter::new(file), &game))
+            .unwrap_or_else(|error| write_failed(path, error))
+    });
+    let mut checkpoints = checkpoints(&game, options);
+
+    while !renderer.finished() {
+        // With a time-lapse, only every Kth generation and the last are
+        // rendered, though every generation is run.
+        let last = generations.is_some_and(|generations| generation >= generations);
+        if generation.is_multiple_of(options.timelapse) || last {
+            if let Err(error) = renderer.render(&game) {
+                eprintln!("error: failed to render generation {}: {}", game.generation(), error);
+                process::exit(1);
+            }
+        }
+        if renderer.finished() || last {
+            break;
+        }
+
+        // Throttle stepping so the output can be followed live.
+        if let Some(tick) = options.tick {
+            thread::sleep(tick);
+        }
+        if !renderer.playing() {
+            continue;
+        }
+        match recorder.as_mut() {
+            Some(recorder) => {
+                let delta = game.step_delta();
+                if let Err(error) = recorder.record(&delta) {
+                    write_failed(options.record.unwrap(), error);
+                }
+            },
+            None => {
+                game.step();
+            },
+        }
+        stats.observe(&game);
+        generation += 1;
+        save_checkpoint(checkpoints.as_mut(), &game);
+    }
+
+    if let Some(Err(error)) = recorder.map(DeltaRecorder::into_inner) {
+        write_failed(options.record.unwrap(), error);
+    }
+    (game, stats)
+}
+
+/// Print each generation of a recording to stdout as text, like headless
+/// mode.
+fn run_replay<R: std::io::Read>(mut player: DeltaPlayer<R>, options: &Options) -> (GameOfLife, RunStats) {
+    let mut stats = RunStats::new(player.game());
+    let mut generation: u128 = 0;
+    let mut renderer = TextRender::new(io::stdout());
+
+    loop {
+        if let Err(error) = renderer.render(player.game()) {
+            eprintln!("error: failed to render generation {}: {}", player.game().generation(), error);
+            process::exit(1);
+        }
+        if options.generations.is_some_and(|generations| generation >= generations) {
+            break;
+        }
+
+        // Throttle playback so the output can be followed live.
+        if let Some(tick) = options.tick {
+            thread::sleep(tick);
+        }
+        match player.next_frame() {
+            Ok(Some(_)) => {
+                stats.observe(player.game());
+                generation += 1;
+            },
+            Ok(None) => break,
+            Err(error) => {
+                eprintln!("error: failed to read `{}`: {}", options.replay.unwrap(), error);
+                process::exit(1);
+            },
+        }
+    }
+    (player.into_game(), stats)
+}
+
+use sdl2::event::{Event, WindowEvent};
+use sdl2::keyboard::{Keycode, Mod};
+use sdl2::mouse::MouseButton;
 use sdl2::render::Canvas;
 use sdl2::video::Window;
 
-fn run(file: Option<&str>, rows: usize, cols: usize, cell_size: usize) {
+/// Run the simulation in a window, returning a message describing the problem
+/// if SDL or the window cannot be set up.
+fn run(
+    game: GameOfLife,
+    compare: Option<GameOfLife>,
+    mut catalog: Option<Catalog>,
+    options: &Options,
+) -> Result<(GameOfLife, RunStats), String> {
+    let keymap = match options.keymap {
+        Some(path) => File::open(path).map_err(KeymapError::from)
+            .and_then(|file| Keymap::load(BufReader::new(file)))
+            .unwrap_or_else(|error| exit_with_error(&format!("failed to load `{}`: {}", path, error))),
+        None => Keymap::default(),
+    };
+    let mut macro_player = options.play_macro.map(|path| {
+        File::open(path).map_err(MacroError::from)
+            .and_then(|file| MacroPlayer::new(BufReader::new(file)))
+            .unwrap_or_else(|error| exit_with_error(&format!("failed to load `{}`: {}", path, error)))
+    });
+    let mut macro_recorder = options.record_macro.map(|path| {
+        File::create(path)
+            .and_then(|file| MacroRecorder::new(BufWriter::new(file)))
+            .unwrap_or_else(|error| {
+                eprintln!("error: failed to write `{}`: {}", path, error);
+                process::exit(1);
+            })
+    });
+
+    // Compared games are drawn side by side, one cell apart.
+    let (rows, cols, cell_size) = match &compare {
+        Some(compare) => (game.rows.max(compare.rows), game.cols + 1 + compare.cols, options.cell_size),
+        None => (game.rows, game.cols, options.cell_size),
+    };
+
     // Initialize SDL window, canvas, and event pump.
-    let sdl_context = sdl2::init().unwrap();
-    let video_subsystem = sdl_context.video().unwrap();
-    let (window_width, window_height) = (
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    // Shrink cells if the window would not fit on the display, or in the
+    // largest window that can be relied on when the display is unknown. When
+    // the view follows the live cells, the window only needs to fit on the
+    // display.
+    let (width, height, screen) = match video_subsystem.display_usable_bounds(0) {
+        Ok(bounds) => {
+            let (width, height) = (bounds.width().try_into().unwrap(), bounds.height().try_into().unwrap());
+            (width, height, format!("the {}x{} display", width, height))
+        },
+        Err(message) => {
+            eprintln!("failed to get display bounds, fitting window to {}px: {}", MAX_WINDOW_SIZE, message);
+            (MAX_WINDOW_SIZE, MAX_WINDOW_SIZE, format!("a {}x{} window", MAX_WINDOW_SIZE, MAX_WINDOW_SIZE))
+        },
+    };
+    let cell_size = match options.fit_view {
+        true => cell_size,
+        false => {
+            let fit = SdlRender::fit_cell_size(rows, cols, cell_size, width, height);
+            // Full screen, the board is scaled to the display, so only the
+            // window the board is first drawn in is too large.
+            if (cols * fit > width || rows * fit > height) && !options.fullscreen {
+                exit_with_error(&format!(
+                    "a {}x{} grid does not fit on {} even with 1px cells; use smaller --dimensions, \
+                     or --fit-view to follow the live cells",
+                    rows, cols, screen,
+                ));
+            }
+            if fit < cell_size {
+                eprintln!(
+                    "warning: a {}x{} grid of {}px cells does not fit on {}; using {}px cells",
+                    rows, cols, cell_size, screen, fit
+                );
+            }
+            fit
+        },
+    };
+
+    let (mut window_width, mut window_height) = (
         rows * cell_size,
         cols * cell_size,
     );
-    let window = video_subsystem.window(
+    if options.fit_view {
+        window_width = window_width.min(height);
+        window_height = window_height.min(width);
+    }
+    let mut window = video_subsystem.window(
         "GoL", 
         window_height.try_into().unwrap(), 
         window_width.try_into().unwrap()
-    ).position_centered().build().unwrap();
+    );
+    window.position_centered();
+    if options.fullscreen {
+        window.fullscreen_desktop();
+    }
+    let window = window.build().map_err(|error| error.to_string())?;
     let canvas : Canvas<Window> = window.into_canvas()
         .present_vsync()
-        .build().unwrap();
-    let mut event_pump = sdl_context.event_pump().unwrap();
+        .build().map_err(|error| error.to_string())?;
+    let mut event_pump = sdl_context.event_pump()?;
 
-    // Initialize game and renderer.
-    let game = match file {
-        Some(file) => GameOfLife::from_file(file, rows, cols),
-        None => GameOfLife::random(rows, cols),
-    };
+    // Initialize renderer.
+    let mut checkpoints = checkpoints(&game, options);
     let mut renderer = SdlRender::new(game, canvas, cell_size);
+    renderer.set_compare(compare);
+    renderer.set_fit_view(options.fit_view);
+    if options.fullscreen {
+        renderer.scale_to_window(options.letterbox)?;
+    }
+    renderer.set_dynamic_speed(options.dynamic_speed);
+    renderer.set_max_steps_per_second(options.max_sps);
+    renderer.set_trail(options.trail);
+    if let Some(decay) = options.heat_decay {
+        renderer.set_heat_decay(decay);
+    }
+    if options.activity_heatmap {
+        renderer.set_color_mode(ColorMode::Heat);
+    }
+    if let Some(age_bands) = options.age_bands.clone() {
+        renderer.set_age_bands(age_bands);
+        renderer.set_color_mode(ColorMode::Bands);
+    }
+    #[cfg(feature = "bg-image")]
+    if let Some(path) = options.bg_image {
+        renderer.set_background(Some(Path::new(path)))
+            .unwrap_or_else(|message| exit_with_error(&format!("failed to load `{}`: {}", path, message)));
+    }
+    renderer.set_profile(options.profile);
+    renderer.set_count_format(options.count_format);
+    renderer.set_high_contrast(options.high_contrast);
+    renderer.set_outline(options.outline);
+    renderer.set_smooth(options.smooth);
+    renderer.set_idle_when_paused(!options.redraw_paused);
+    renderer.set_pause_at(options.pause_at);
+    if options.autoplay {
+        renderer.play();
+    }
+
+    // Digits typed before a command key, vi-style, to apply to it.
+    let mut pending_count: Option<usize> = None;
 
     'render: loop {
-        for event in event_pump.poll_iter() {
-            match event {
+        // Press the keys of the macro that are due before handling the keys
+        // actually pressed.
+        let mut events = Vec::new();
+        if let Some(player) = macro_player.as_mut() {
+            while let Some(press) = player.next_due(renderer.game().generation()) {
+                events.push(Event::KeyDown {
+                    timestamp: 0,
+                    window_id: 0,
+                    keycode: Some(press.keycode),
+                    scancode: None,
+                    keymod: press.keymod(),
+                    repeat: false,
+                });
+            }
+        }
+        // Sleep until something happens when there is nothing new to draw.
+        if renderer.idle() && events.is_empty() {
+            events.extend(event_pump.wait_event_timeout(IDLE_WAIT_MS));
+        }
+        events.extend(event_pump.poll_iter());
+
+        for event in events {
+            if let (Some(recorder), Event::KeyDown { keycode: Some(keycode), keymod, .. }) = (macro_recorder.as_mut(), &event) {
+                if let Some(press) = KeyPress::new(renderer.game().generation(), *keycode, *keymod) {
+                    if let Err(error) = recorder.record(&press) {
+                        eprintln!(
+                            "error: failed to write `{}`, no longer recording keys: {}",
+                            options.record_macro.unwrap(), error
+                        );
+                        macro_recorder = None;
+                    }
+                }
+            }
+
+            // Buffer digits, and hand the count to the next key, which either
+            // uses it or drops it.
+            let count = match event {
+                Event::KeyDown { keycode: Some(keycode), .. } => match digit(keycode) {
+                    Some(digit) => {
+                        pending_count = Some(push_count_digit(pending_count, digit));
+                        continue;
+                    },
+                    None => pending_count.take(),
+                },
+                _ => None,
+            };
+
+            let action = match event {
+                Event::KeyDown { keycode: Some(keycode), .. } => keymap.action(keycode),
+                _ => None,
+            };
+            let shift = |keymod: Mod| keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD);
+
+            match (event, action) {
                 // Quit on ESC, Q, or close window.
-                Event::Quit {..} |
-                Event::KeyDown { keycode: Some(Keycode::Q), .. } | 
-                Event::KeyDown { keycode: Some(Keycode::Escape), .. } => {
+                (Event::Quit {..}, _) | (_, Some(KeyAction::Quit)) => {
                     break 'render;
                 },
                 // Toggle play/pause with SPACE.
-                Event::KeyDown { keycode: Some(Keycode::Space), .. } => { 
+                (_, Some(KeyAction::PlayPause)) => {
                     match renderer.playing() {
                         true => renderer.pause(),
                         false => renderer.play(),
                     }
                 },
-                // Render frame by frame with N when paused.
-                Event::KeyDown { keycode: Some(Keycode::N), .. } => { 
-                    if !renderer.playing() {
-                        renderer.step(1);
+                // Render frame by frame with N when paused, or a typed number
+                // of frames at once.
+                (_, Some(KeyAction::Step)) if !renderer.playing() => {
+                    renderer.step(count.unwrap_or(1));
+                },
+                // Scroll the world by a typed number of cells, or one, with
+                // SHIFT and the arrow keys.
+                (
+                    Event::KeyDown { keymod, .. },
+                    Some(action @ (KeyAction::Faster | KeyAction::Slower | KeyAction::FewerSteps | KeyAction::MoreSteps)),
+                ) if shift(keymod) => {
+                    let distance = isize::try_from(count.unwrap_or(1)).unwrap_or(isize::MAX);
+                    match action {
+                        KeyAction::Faster => renderer.shift(-distance, 0),
+                        KeyAction::Slower => renderer.shift(distance, 0),
+                        KeyAction::FewerSteps => renderer.shift(0, -distance),
+                        _ => renderer.shift(0, distance),
                     }
                 },
                 // Increase/decrease framerate with UP/DOWN arrows.
-                Event::KeyDown { keycode: Some(Keycode::Up), .. } => { 
+                (_, Some(KeyAction::Faster)) => {
                     renderer.inc_framerate();
                 },
-                Event::KeyDown { keycode: Some(Keycode::Down), .. } => { 
+                (_, Some(KeyAction::Slower)) => {
                     renderer.dec_framerate();
                 },
                 // Increase/decrease generations per frame with RIGHT/LEFT arrows.
-                Event::KeyDown { keycode: Some(Keycode::Right), .. } => { 
+                (_, Some(KeyAction::MoreSteps)) => {
                     renderer.inc_steps_per_frame();
                 },
-                Event::KeyDown { keycode: Some(Keycode::Left), .. } => { 
+                (_, Some(KeyAction::FewerSteps)) => {
                     renderer.dec_steps_per_frame();
                 },
+                // Randomly flip a small fraction of cells with M.
+                (_, Some(KeyAction::Mutate)) => {
+                    renderer.mutate(MUTATION_RATE, rand::random());
+                },
+                // Cycle how live cells are colored with V.
+                (_, Some(KeyAction::CycleColor)) => {
+                    renderer.cycle_color_mode();
+                },
+                // Set the generation to pause at to a typed number with P, or
+                // clear it without one.
+                (_, Some(KeyAction::PauseAt)) => {
+                    renderer.set_pause_at(count.map(|generation| generation as u128));
+                },
+                // Switch step timing between ms and gen/s with U.
+                (_, Some(KeyAction::TimingUnits)) => {
+                    renderer.toggle_timing_units();
+                },
+                // Switch whether rows or columns wrap around the edges with
+                // R/C, from the next generation.
+                (_, Some(KeyAction::WrapRows)) => {
+                    renderer.toggle_wrap_rows();
+                },
+                (_, Some(KeyAction::WrapCols)) => {
+                    renderer.toggle_wrap_cols();
+                },
+                // Show the next or previous pattern of the catalog with
+                // PAGEDOWN/PAGEUP.
+                (_, Some(action @ (KeyAction::NextPattern | KeyAction::PreviousPattern))) => {
+                    let shown = catalog.as_mut().and_then(|catalog| match action {
+                        KeyAction::NextPattern => catalog.next_pattern(),
+                        _ => catalog.previous_pattern(),
+                    });
+                    if let Some((_, game)) = shown {
+                        let mut game = game.clone();
+                        configure(&mut game, options);
+                        renderer.set_game(game);
+                    }
+                },
+                // Undo the last brush stroke with CTRL+Z while paused.
+                (Event::KeyDown { keymod, .. }, Some(KeyAction::Undo))
+                    if keymod.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD) && !renderer.playing() => {
+                    renderer.undo_edit();
+                },
+                // Switch the shape drawn by dragging with SHIFT with S.
+                (_, Some(KeyAction::CycleShape)) => {
+                    renderer.cycle_shape_tool();
+                },
+                // Draw on the board by dragging with the left mouse button while
+                // paused; each drag is one stroke, or one shape with SHIFT held.
+                (Event::MouseButtonDown { mouse_btn: MouseButton::Left, x, y, .. }, _) if !renderer.playing() => {
+                    match shift(sdl_context.keyboard().mod_state()) {
+                        true => renderer.begin_shape(x, y),
+                        false => renderer.begin_stroke(x, y),
+                    }
+                },
+                (Event::MouseButtonUp { mouse_btn: MouseButton::Left, .. }, _) => {
+                    renderer.end_stroke();
+                    renderer.end_shape();
+                },
+                // Show the cell under the mouse while paused.
+                (Event::MouseMotion { x, y, .. }, _) => {
+                    renderer.hover(Some((x, y)));
+                    renderer.stroke_to(x, y);
+                    renderer.shape_to(x, y);
+                },
+                (Event::Window { win_event: WindowEvent::Leave, .. }, _) => {
+                    renderer.hover(None);
+                },
+                // Redraw the whole board when the window contents are lost.
+                (Event::Window { win_event: WindowEvent::Exposed, .. }, _) |
+                (Event::Window { win_event: WindowEvent::SizeChanged(..), .. }, _) |
+                (Event::RenderTargetsReset { .. }, _) => {
+                    renderer.redraw_all();
+                },
                 _ => (),
             }
         }
 
         renderer.render();
+        save_checkpoint(checkpoints.as_mut(), renderer.game());
     }
+
+    if let Some(Err(error)) = macro_recorder.map(MacroRecorder::into_inner) {
+        eprintln!("error: failed to write `{}`: {}", options.record_macro.unwrap(), error);
+    }
+    let stats = renderer.stats().clone();
+    Ok((renderer.into_game(), stats))
+}
+
+/// Get the digit typed by a number key, on the main keyboard or the keypad.
+fn digit(keycode: Keycode) -> Option<usize> {
+    const NUMBER_KEYS: [Keycode; 10] = [
+        Keycode::Num0, Keycode::Num1, Keycode::Num2, Keycode::Num3, Keycode::Num4,
+        Keycode::Num5, Keycode::Num6, Keycode::Num7, Keycode::Num8, Keycode::Num9,
+    ];
+    const KEYPAD_KEYS: [Keycode; 10] = [
+        Keycode::Kp0, Keycode::Kp1, Keycode::Kp2, Keycode::Kp3, Keycode::Kp4,
+        Keycode::Kp5, Keycode::Kp6, Keycode::Kp7, Keycode::Kp8, Keycode::Kp9,
+    ];
+    NUMBER_KEYS.iter().position(|key| *key == keycode)
+        .or_else(|| KEYPAD_KEYS.iter().position(|key| *key == keycode))
 }
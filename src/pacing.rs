@@ -0,0 +1,223 @@
+use std::collections::VecDeque;
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub(crate) const DEFAULT_FRAMERATE: u128 = 24;
+pub(crate) const MAX_FRAMERATE: u128 = 120;
+
+/// A source of the current time that can also wait, so that code that paces
+/// itself can be run against a fake clock.
+pub trait Clock {
+    /// The current time.
+    fn now(&self) -> Instant;
+
+    /// Wait for `duration` to pass.
+    fn sleep(&self, duration: Duration);
+}
+
+/// The real clock, which reads the system time and sleeps the thread.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// Keeps frames from being rendered faster than a maximum framerate, by
+/// sleeping off whatever is left of each frame's time once it is drawn.
+pub struct FramePacer {
+    clock: Box<dyn Clock>,  // source of time and sleeping
+    framerate: Option<u128>,  // maximum framerate, from 1 to MAX_FRAMERATE, or `None` for no limit
+    frame_start: Option<Instant>,  // time the current frame started, if started
+    max_steps_per_second: Option<u128>,  // most generations computed per second, or `None` for no limit
+    step_credit: u128,  // billionths of a generation that may be computed before waiting for more
+    credited_at: Option<Instant>,  // time the credit was last topped up, if ever
+}
+
+impl FramePacer {
+    /// Create a pacer at the default framerate that uses the given clock.
+    pub fn new(clock: Box<dyn Clock>) -> FramePacer {
+        FramePacer {
+            clock,
+            framerate: Some(DEFAULT_FRAMERATE),
+            frame_start: None,
+            max_steps_per_second: None,
+            step_credit: 0,
+            credited_at: None,
+        }
+    }
+
+    /// Replace the clock used to measure and wait out frames.
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.clock = clock;
+        self.frame_start = None;
+        self.credited_at = None;
+    }
+
+    /// The maximum framerate, or `None` if frames are not limited.
+    pub fn framerate(&self) -> Option<u128> {
+        self.framerate
+    }
+
+    /// The least time each frame takes, or zero if frames are not limited.
+    pub fn min_frame_time(&self) -> Duration {
+        let nanos = self.framerate.map_or(0, |framerate| 1_000_000_000 / framerate);
+        Duration::from_nanos(nanos.try_into().unwrap())
+    }
+
+    /// Increase the framerate by 1 FPS, up to a max value, and past the max
+    /// value to no limit at all.
+    pub fn inc_framerate(&mut self) {
+        self.framerate = match self.framerate {
+            Some(framerate) if framerate < MAX_FRAMERATE => Some(framerate + 1),
+            _ => None,
+        };
+    }
+
+    /// Decrease the framerate by 1 FPS, down to a minimum of 1 FPS. Without
+    /// a limit, the framerate drops to the max value.
+    pub fn dec_framerate(&mut self) {
+        self.framerate = match self.framerate {
+            Some(framerate) => Some(framerate.saturating_sub(1).max(1)),
+            None => Some(MAX_FRAMERATE),
+        };
+    }
+
+    /// The most generations computed per second, or `None` if steps are not
+    /// limited.
+    pub fn max_steps_per_second(&self) -> Option<u128> {
+        self.max_steps_per_second
+    }
+
+    /// Limit how many generations are computed per second, whatever the
+    /// framerate and generations per frame, or `None` for no limit. See
+    /// `allowed_steps`.
+    pub fn set_max_steps_per_second(&mut self, limit: Option<u128>) {
+        self.max_steps_per_second = limit;
+        self.step_credit = 0;
+        self.credited_at = None;
+    }
+
+    /// How many of the `wanted` generations may be computed now without
+    /// going over the maximum generations per second. Frames keep their
+    /// framerate; when the limit is lower than the framerate times the
+    /// generations per frame, some frames step fewer generations or none.
+    /// Time spent not stepping earns at most `wanted` generations, and at
+    /// most one second's worth, so a long pause is not made up for in a
+    /// burst faster than the limit.
+    pub fn allowed_steps(&mut self, wanted: usize) -> usize {
+        let Some(limit) = self.max_steps_per_second else {
+            return wanted;
+        };
+        const STEP: u128 = 1_000_000_000;
+        let now = self.clock.now();
+        self.step_credit = match self.credited_at {
+            Some(credited_at) => {
+                let earned = now.saturating_duration_since(credited_at).as_nanos() * limit;
+                (self.step_credit + earned).min((wanted as u128).min(limit) * STEP)
+            },
+            // The first frame steps once, or not at all if nothing is wanted.
+            None => wanted.min(1) as u128 * STEP,
+        };
+        self.credited_at = Some(now);
+        let allowed = (self.step_credit / STEP) as usize;
+        self.step_credit -= allowed as u128 * STEP;
+        allowed
+    }
+
+    /// How much of the minimum time of the current frame is left, or zero if
+    /// no frame is started or frames are not limited.
+    pub fn time_left(&self) -> Duration {
+        match self.frame_start {
+            Some(start) => self.min_frame_time().saturating_sub(self.clock.now().saturating_duration_since(start)),
+            None => Duration::ZERO,
+        }
+    }
+
+    /// Wait for `duration` to pass on the pacer's clock, e.g. to spread
+    /// extra drawing over the rest of a frame.
+    pub(crate) fn wait(&self, duration: Duration) {
+        self.clock.sleep(duration);
+    }
+
+    /// The current time, by the pacer's clock.
+    pub(crate) fn now(&self) -> Instant {
+        self.clock.now()
+    }
+
+    /// Mark the start of a frame.
+    pub fn start_frame(&mut self) {
+        self.frame_start = Some(self.clock.now());
+    }
+
+    /// Sleep until the minimum time per frame has passed since the frame
+    /// started, returning how long it slept.
+    pub fn finish_frame(&mut self) -> Duration {
+        let Some(start) = self.frame_start.take() else {
+            return Duration::ZERO;
+        };
+        let elapsed = self.clock.now().saturating_duration_since(start);
+        let delay = self.min_frame_time().saturating_sub(elapsed);
+        if delay.is_zero() {
+            return Duration::ZERO;
+        }
+        self.clock.sleep(delay);
+        delay
+    }
+}
+
+impl Default for FramePacer {
+    fn default() -> FramePacer {
+        FramePacer::new(Box::new(SystemClock))
+    }
+}
+
+/// Measures how many generations a game actually advances per second, which
+/// falls short of the framerate times the generations per frame when steps
+/// or drawing take too long. The rate is taken over a rolling window of the
+/// most recent samples, so it follows changes in speed.
+#[derive(Clone, Debug)]
+pub struct SpeedMeter {
+    window: Duration,  // how far back samples are kept
+    samples: VecDeque<(Instant, u128)>,  // times and generations, oldest first
+}
+
+impl SpeedMeter {
+    /// Create a meter that measures over the last `window` of time.
+    pub fn new(window: Duration) -> SpeedMeter {
+        SpeedMeter { window, samples: VecDeque::new() }
+    }
+
+    /// Record the generation a game is at. The meter starts over if the
+    /// generation went backwards, e.g. because the game was replaced.
+    pub fn record(&mut self, time: Instant, generation: u128) {
+        if self.samples.back().is_some_and(|(_, last)| *last > generation) {
+            self.samples.clear();
+        }
+        self.samples.push_back((time, generation));
+        // Keep the newest sample from before the window, so the rate covers
+        // the whole window.
+        while self.samples.get(1).is_some_and(|(sampled, _)| time.saturating_duration_since(*sampled) >= self.window) {
+            self.samples.pop_front();
+        }
+    }
+
+    /// The generations advanced per second over the window, or `None` until
+    /// two samples at different times have been recorded.
+    pub fn generations_per_second(&self) -> Option<f64> {
+        let ((first_time, first), (last_time, last)) = (self.samples.front()?, self.samples.back()?);
+        let elapsed = last_time.saturating_duration_since(*first_time).as_secs_f64();
+        (elapsed > 0.0).then(|| (last - first) as f64 / elapsed)
+    }
+
+    /// Forget every sample, e.g. when the game is paused.
+    pub fn reset(&mut self) {
+        self.samples.clear();
+    }
+}
@@ -0,0 +1,209 @@
+use std::fmt;
+use std::io::{self, Read, Write};
+
+use crate::{Cell, GameOfLife, SnapshotError, StepDelta};
+
+/// Bytes every recording starts with.
+const MAGIC: &[u8; 4] = b"GOLD";
+
+/// Recording format version written by this build.
+const VERSION: u16 = 1;
+
+/// Errors produced when playing a recording.
+#[derive(Debug)]
+pub enum RecordingError {
+    /// The recording could not be read.
+    Io(io::Error),
+    /// The data does not start like a recording.
+    NotARecording,
+    /// The recording was written in a format version this build cannot read.
+    UnsupportedVersion(u16),
+    /// The first generation of the recording could not be read.
+    Snapshot(SnapshotError),
+    /// The recording is damaged or inconsistent.
+    Corrupt(String),
+}
+
+impl fmt::Display for RecordingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RecordingError::Io(error) => write!(f, "error reading recording: {}", error),
+            RecordingError::NotARecording => write!(f, "file is not a recording"),
+            RecordingError::UnsupportedVersion(version) => write!(
+                f, "recording version {} is not supported (expected version {})", version, VERSION
+            ),
+            RecordingError::Snapshot(error) => write!(f, "first generation of recording: {}", error),
+            RecordingError::Corrupt(message) => write!(f, "recording is corrupt: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for RecordingError {}
+
+impl From<io::Error> for RecordingError {
+    fn from(error: io::Error) -> RecordingError {
+        match error.kind() {
+            io::ErrorKind::UnexpectedEof => RecordingError::Corrupt(String::from("unexpected end of data")),
+            _ => RecordingError::Io(error),
+        }
+    }
+}
+
+impl From<SnapshotError> for RecordingError {
+    fn from(error: SnapshotError) -> RecordingError {
+        RecordingError::Snapshot(error)
+    }
+}
+
+/// Writes a game's generations as a recording of only the cells that change,
+/// which is far smaller than full frames when little of the board is active.
+///
+/// A recording is a binary format starting with the bytes `GOLD` and a format
+/// version, followed by a snapshot of the first generation (see
+/// `GameOfLife::save_snapshot`), then one frame for each later generation up
+/// to the end of the data. A frame lists the cells born and then the cells
+/// that died, each list as a count followed by `(row, col)` pairs sorted by
+/// row then column. Counts are 64-bit and coordinates 32-bit, all in little
+/// endian byte order. Since cells are sorted, the same game always produces
+/// the same recording.
+pub struct DeltaRecorder<W: Write> {
+    writer: W,  // destination of the recording
+}
+
+impl<W: Write> DeltaRecorder<W> {
+    /// Start a recording with `game` as its first generation.
+    pub fn new(mut writer: W, game: &GameOfLife) -> io::Result<DeltaRecorder<W>> {
+        if u32::try_from(game.rows.max(game.cols)).is_err() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "game is too large to record"));
+        }
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        game.save_snapshot(&mut writer)?;
+        Ok(DeltaRecorder { writer })
+    }
+
+    /// Add the next generation, given by the cells that changed during the
+    /// step to it, e.g. from `GameOfLife::step_delta`.
+    pub fn record(&mut self, delta: &StepDelta) -> io::Result<()> {
+        for cells in [&delta.born, &delta.died] {
+            let mut cells = cells.clone();
+            cells.sort_unstable_by_key(|cell| (cell.r, cell.c));
+            self.writer.write_all(&u64::try_from(cells.len()).unwrap().to_le_bytes())?;
+            for cell in cells.iter() {
+                self.writer.write_all(&u32::try_from(cell.r).unwrap().to_le_bytes())?;
+                self.writer.write_all(&u32::try_from(cell.c).unwrap().to_le_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Finish recording, flushing and returning the writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Plays back a recording written by `DeltaRecorder`, one generation at a
+/// time.
+pub struct DeltaPlayer<R: Read> {
+    reader: R,  // source of the recording
+    game: GameOfLife,  // generation reached so far
+}
+
+impl<R: Read> DeltaPlayer<R> {
+    /// Open a recording, starting at its first generation. Recordings written
+    /// in an unsupported format version are rejected.
+    pub fn new(mut reader: R) -> Result<DeltaPlayer<R>, RecordingError> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic).map_err(|_| RecordingError::NotARecording)?;
+        if &magic != MAGIC {
+            return Err(RecordingError::NotARecording);
+        }
+        let mut version = [0; 2];
+        reader.read_exact(&mut version)?;
+        let version = u16::from_le_bytes(version);
+        if version != VERSION {
+            return Err(RecordingError::UnsupportedVersion(version));
+        }
+
+        let game = GameOfLife::load_snapshot(&mut reader)?;
+        Ok(DeltaPlayer { reader, game })
+    }
+
+    /// The generation reached so far.
+    pub fn game(&self) -> &GameOfLife {
+        &self.game
+    }
+
+    /// Stop playing and take the generation reached so far.
+    pub fn into_game(self) -> GameOfLife {
+        self.game
+    }
+
+    /// Advance to the next generation of the recording, returning the cells
+    /// that changed, or `None` at the end of the recording.
+    pub fn next_frame(&mut self) -> Result<Option<StepDelta>, RecordingError> {
+        let born_count = match read_count(&mut self.reader)? {
+            Some(count) => count,
+            None => return Ok(None),
+        };
+        let born = self.read_cells(born_count)?;
+        let died_count = read_count(&mut self.reader)?
+            .ok_or_else(|| RecordingError::Corrupt(String::from("unexpected end of data")))?;
+        let died = self.read_cells(died_count)?;
+
+        if born.iter().any(|cell| self.game.is_alive(*cell)) || died.iter().any(|cell| !self.game.is_alive(*cell)) {
+            return Err(RecordingError::Corrupt(format!(
+                "frame for generation {} does not match the board", self.game.generation() + 1
+            )));
+        }
+        let delta = StepDelta { born, died };
+        self.game.apply_delta(&delta);
+        Ok(Some(delta))
+    }
+
+    /// Read `count` cells, checking that they are on the board.
+    fn read_cells(&mut self, count: usize) -> Result<Vec<Cell>, RecordingError> {
+        if count > self.game.rows * self.game.cols {
+            return Err(RecordingError::Corrupt(format!("frame of {} cells is larger than the board", count)));
+        }
+        let mut cells = Vec::with_capacity(count);
+        for _ in 0..count {
+            let cell = Cell { r: read_u32(&mut self.reader)?, c: read_u32(&mut self.reader)? };
+            if cell.r >= self.game.rows || cell.c >= self.game.cols {
+                return Err(RecordingError::Corrupt(format!("cell ({}, {}) is out of bounds", cell.r, cell.c)));
+            }
+            cells.push(cell);
+        }
+        Ok(cells)
+    }
+}
+
+/// Read a count at the start of a list of cells, or `None` if the data ends
+/// before it.
+fn read_count<R: Read>(reader: &mut R) -> Result<Option<usize>, RecordingError> {
+    let mut bytes = [0; 8];
+    let mut filled = 0;
+    while filled < bytes.len() {
+        match reader.read(&mut bytes[filled..]) {
+            Ok(0) => break,
+            Ok(read) => filled += read,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error.into()),
+        }
+    }
+    match filled {
+        0 => Ok(None),
+        8 => usize::try_from(u64::from_le_bytes(bytes))
+            .map(Some)
+            .map_err(|_| RecordingError::Corrupt(String::from("value too large for this platform"))),
+        _ => Err(RecordingError::Corrupt(String::from("unexpected end of data"))),
+    }
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<usize, RecordingError> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(usize::try_from(u32::from_le_bytes(bytes)).unwrap())
+}
@@ -1,98 +1,580 @@
-use std::thread;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::io;
+use std::iter;
+#[cfg(feature = "bg-image")]
+use std::path::Path;
 use std::time::{Duration, Instant};
+#[cfg(feature = "bg-image")]
+use sdl2::image::LoadTexture;
 use sdl2::video::Window;
-use sdl2::render::Canvas;
+use sdl2::render::{BlendMode, Canvas, Texture};
 use sdl2::rect::Rect;
 use sdl2::pixels::Color;
 
-use crate::GameOfLife;
-
-const DEFAULT_FRAMERATE: u128 = 24;
-const MAX_FRAMERATE: u128 = 120;
+use crate::{AgeBands, BoundaryMode, Cell, Clock, FramePacer, GameOfLife, Region, Renderer, RunStats, ShapeTool, SpeedMeter, StepDelta};
+use crate::pacing::MAX_FRAMERATE;
 
 const DEFAULT_STEPS_PER_FRAME: usize = 1;
 const MAX_STEPS_PER_FRAME: usize = 50;
 
+const DYNAMIC_SPEED_FRAMES: usize = 24;  // frames of population history for dynamic speed
+const DYNAMIC_SPEED_ACTIVE_PERCENT: usize = 1;  // population change over history that counts as active
+
+const AGE_COLOR_SPAN: u32 = 50;  // age at which live cells reach their oldest color
+const DEFAULT_HEAT_DECAY: f32 = 0.1;  // fraction of heat cells lose each generation
+const HEAT_COLOR_SPAN: f32 = 8.0;  // heat at which cells reach their hottest color
+const HEAT_CUTOFF: f32 = 0.05;  // heat below which cells are cold again
+const FIT_VIEW_PADDING: f64 = 4.0;  // cells shown around the live cells when fitting the view
+const FIT_VIEW_MIN_CELLS: f64 = 24.0;  // fewest rows or columns shown when fitting the view
+const FIT_VIEW_EASING: f64 = 0.15;  // fraction of the way the view moves toward its target each frame
+const SPEED_WINDOW: Duration = Duration::from_secs(1);  // time the measured speed is averaged over
+const DIVIDER_COLOR: Color = Color::RGB(80, 80, 80);  // color between compared games
+const CLEAR_COLOR: Color = Color::RGBA(0, 0, 0, 0);  // dead cells when a background image shows through them
+const SHAPE_PREVIEW_COLOR: Color = Color::RGB(255, 200, 0);  // cells of a shape being dragged out
+const HIGH_CONTRAST_LIVE_COLOR: Color = Color::WHITE;  // live cells in high contrast, 21:1 against black
+const HIGH_CONTRAST_DEAD_COLOR: Color = Color::BLACK;  // dead cells and grid lines in high contrast
+const GRID_LINE_MIN_CELL_SIZE: usize = 4;  // smallest cell size, in pixels, that leaves room for grid lines
+const OUTLINE_SCALE: u32 = 8;  // times wider a cell is than its outline, which is at least 1 pixel
+const FADE_STEPS: usize = 8;  // most frames a smooth cross-fade between generations is drawn in
+const MIN_FADE_FRAME: Duration = Duration::from_millis(10);  // least time each cross-fade frame is shown
+
+/// How live cells are colored.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    /// Every live cell is white.
+    #[default]
+    Single,
+    /// Live cells fade from white to blue as they age.
+    Age,
+    /// Live cells are colored by the band of ages they fall in, see
+    /// `AgeBands`.
+    Bands,
+    /// Live cells are white, and dead cells glow from dark red to yellow
+    /// where cells have recently been born or died, showing where the
+    /// pattern is active.
+    Heat,
+}
+
+impl ColorMode {
+    /// The mode after this one, cycling back to the first.
+    pub fn next(self) -> ColorMode {
+        match self {
+            ColorMode::Single => ColorMode::Age,
+            ColorMode::Age => ColorMode::Bands,
+            ColorMode::Bands => ColorMode::Heat,
+            ColorMode::Heat => ColorMode::Single,
+        }
+    }
+}
+
+impl fmt::Display for ColorMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ColorMode::Single => write!(f, "single"),
+            ColorMode::Age => write!(f, "age"),
+            ColorMode::Bands => write!(f, "bands"),
+            ColorMode::Heat => write!(f, "heat"),
+        }
+    }
+}
+
+/// How generation counts are written in the window title.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CountFormat {
+    /// Every digit, like `1234567`.
+    #[default]
+    Plain,
+    /// Digits in groups of three separated by commas, like `1,234,567`.
+    Grouped,
+    /// Scientific notation with three significant digits, like `1.23e6`.
+    /// Counts under 1000 are written in full.
+    Scientific,
+}
+
+impl CountFormat {
+    /// Write a count in this format.
+    pub fn format(self, count: u128) -> String {
+        let digits = count.to_string();
+        match self {
+            CountFormat::Plain => digits,
+            CountFormat::Grouped => {
+                let mut grouped = String::new();
+                for (i, digit) in digits.chars().enumerate() {
+                    if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                        grouped.push(',');
+                    }
+                    grouped.push(digit);
+                }
+                grouped
+            },
+            CountFormat::Scientific if count < 1000 => digits,
+            CountFormat::Scientific => format!("{:.2e}", count as f64),
+        }
+    }
+}
+
+/// Units to show step timing in when profiling.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum TimingUnits {
+    /// Milliseconds per generation.
+    #[default]
+    Millis,
+    /// Generations per second.
+    GenerationsPerSecond,
+}
+
+impl TimingUnits {
+    /// Format the time taken to compute one generation in these units.
+    fn format(self, step_time: Duration) -> String {
+        match self {
+            TimingUnits::Millis => format!("{:.3} ms", step_time.as_secs_f64() * 1000.0),
+            TimingUnits::GenerationsPerSecond => match step_time.as_secs_f64() {
+                secs if secs > 0.0 => format!("{:.0} gen/s", 1.0 / secs),
+                _ => String::from("max gen/s"),
+            },
+        }
+    }
+}
 
 /// Struct to render a GameOfLife using SDL.
+///
+/// A second game can be shown to the right of the first to compare them, see
+/// `set_compare`. Both games are stepped, mutated, and played together.
 pub struct SdlRender {
-    game: GameOfLife,  // game to render
+    pane: Pane,  // game to render, on the left of the window
+    compare: Option<Pane>,  // game to compare with, on the right of the window, if any
     canvas: Canvas<Window>,  // SDL canvas to draw on
+    color_mode: ColorMode,  // how live cells are colored
+    age_bands: AgeBands,  // colors of live cells by age when coloring by bands
+    background: Option<Texture>,  // image shown through dead cells, if any
+    redraw: bool,  // whether the whole board must be drawn on next render
     cell_size: usize,  // side length of square cell, in pixels
     play: bool,  // whether calling self.render() causes game steps
-    framerate: u128,  // maximum framerate of render
-    min_render_nanos: u128,  // minimum time per render step based on framerate
+    pacer: FramePacer,  // limits the framerate of render while playing
     steps_per_frame: usize,  // how many game steps to take on each frame
-    step_count: u128,  // number of steps taken so far
+    dynamic_speed: bool,  // whether framerate follows population activity
+    population_history: VecDeque<usize>,  // recent populations for dynamic speed
+    hover: Option<(i32, i32)>,  // pixel position of the mouse, if it is over the window
+    profile: bool,  // whether step timing is shown in the title
+    timing_units: TimingUnits,  // units step timing is shown in
+    count_format: CountFormat,  // how generation counts are written in the title
+    high_contrast: bool,  // whether cells are drawn in high contrast with grid lines
+    outline: bool,  // whether cells are drawn as outlines instead of filled
+    smooth: bool,  // whether changed cells cross-fade over the rest of each frame
+    idle_when_paused: bool,  // whether renders while paused draw only when something shown changed
+    idle: bool,  // whether the last render drew nothing, as nothing shown had changed
+    changed: bool,  // whether something drawn over the board changed since the last draw
+    title: String,  // title last given to the window
+    step_time: Option<Duration>,  // time taken per generation by the last step
+    pause_at: Option<u128>,  // generation to pause at while playing, if any
+    stroke: Option<Stroke>,  // brush stroke being drawn, if any
+    shape_tool: ShapeTool,  // shape drawn by dragging with the shape modifier
+    shape: Option<ShapeDrag>,  // shape being dragged out, if any
+    edit_history: Vec<Vec<Edit>>,  // edits of each finished stroke since the last step, oldest first
+    stats: RunStats,  // statistics of the game's run so far
+    fit_view: bool,  // whether the view follows the live cells instead of showing the whole board
+    window_scale: Option<bool>,  // whether the board keeps its shape when scaled to fill the window, if it is
+    camera: Option<Camera>,  // part of the board shown when fitting the view, once placed
+    speed: SpeedMeter,  // measures the generations actually run per second while playing
+    boundary: Option<(BoundaryMode, BoundaryMode)>,  // modes of rows and columns to switch to before the next step, if toggled
+}
+
+/// The part of the board shown in the window, in cells. Fractional so that
+/// the view can glide smoothly from one place to another.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Camera {
+    r: f64,  // top edge, in rows
+    c: f64,  // left edge, in columns
+    rows: f64,  // height, in rows
+    cols: f64,  // width, in columns
+}
+
+impl Camera {
+    /// The view that fits a region plus padding, widened or heightened to
+    /// match the window's shape so cells stay square.
+    fn fitting(region: Region, width: f64, height: f64) -> Camera {
+        let mut rows = (region.rows as f64 + 2.0 * FIT_VIEW_PADDING).max(FIT_VIEW_MIN_CELLS);
+        let mut cols = (region.cols as f64 + 2.0 * FIT_VIEW_PADDING).max(FIT_VIEW_MIN_CELLS);
+        if cols / rows < width / height {
+            cols = rows * width / height;
+        } else {
+            rows = cols * height / width;
+        }
+        let center_r = region.r as f64 + region.rows as f64 / 2.0;
+        let center_c = region.c as f64 + region.cols as f64 / 2.0;
+        Camera { r: center_r - rows / 2.0, c: center_c - cols / 2.0, rows, cols }
+    }
+
+    /// Move part of the way toward another view.
+    fn ease_toward(&self, target: Camera) -> Camera {
+        let ease = |from: f64, to: f64| from + (to - from) * FIT_VIEW_EASING;
+        Camera {
+            r: ease(self.r, target.r),
+            c: ease(self.c, target.c),
+            rows: ease(self.rows, target.rows),
+            cols: ease(self.cols, target.cols),
+        }
+    }
+}
+
+/// A brush stroke being drawn with the mouse.
+struct Stroke {
+    alive: bool,  // state the brush sets cells to
+    edits: Vec<Edit>,  // cells changed so far
+}
+
+/// A shape being dragged out on the board.
+#[derive(Copy, Clone)]
+struct ShapeDrag {
+    pane: usize,  // pane the shape is drawn in: 0 for the game, 1 for the compared game
+    from: Cell,  // cell the drag started at
+    to: Cell,  // cell the drag is over
+}
+
+/// A cell changed by drawing, with what is needed to undo the change.
+#[derive(Clone, Copy)]
+struct Edit {
+    pane: usize,  // pane of the cell: 0 for the game, 1 for the compared game
+    cell: Cell,  // cell that changed
+    was_alive: bool,  // state of the cell before the change
 }
 
 impl SdlRender {
     /// Create a new instance of a renderer with the given game to render,
     /// canvas to draw on, and size to draw cells at.
+    ///
+    /// When the canvas supports render targets, the board is kept in a
+    /// persistent texture and only cells that changed are redrawn each frame;
+    /// otherwise, the whole board is redrawn every frame.
     pub fn new(game: GameOfLife, canvas: Canvas<Window>, cell_size: usize) -> SdlRender {
         SdlRender {
-            game, canvas, cell_size,
+            stats: RunStats::new(&game),
+            fit_view: false,
+            window_scale: None,
+            camera: None,
+            speed: SpeedMeter::new(SPEED_WINDOW),
+            boundary: None,
+            pane: Pane::new(game, &canvas, cell_size, 0),
+            compare: None,
+            canvas, cell_size,
+            color_mode: ColorMode::default(),
+            age_bands: AgeBands::default(),
+            background: None,
+            redraw: true,
             play: false,
-            framerate: DEFAULT_FRAMERATE,
-            min_render_nanos: 1_000_000_000 / DEFAULT_FRAMERATE,
+            pacer: FramePacer::default(),
             steps_per_frame: DEFAULT_STEPS_PER_FRAME,
-            step_count: 0,
+            dynamic_speed: false,
+            population_history: VecDeque::new(),
+            hover: None,
+            profile: false,
+            timing_units: TimingUnits::default(),
+            count_format: CountFormat::default(),
+            high_contrast: false,
+            outline: false,
+            smooth: false,
+            idle_when_paused: true,
+            idle: false,
+            changed: false,
+            title: String::new(),
+            step_time: None,
+            pause_at: None,
+            stroke: None,
+            shape_tool: ShapeTool::default(),
+            shape: None,
+            edit_history: Vec::new(),
         }
     }
 
+    /// Get the largest cell size, up to `cell_size`, at which a game with the
+    /// given dimensions fits in a display area of `width` by `height` pixels.
+    /// Returns `cell_size` unchanged if it already fits, and never returns
+    /// less than 1.
+    pub fn fit_cell_size(rows: usize, cols: usize, cell_size: usize, width: usize, height: usize) -> usize {
+        let fit = (width / cols.max(1)).min(height / rows.max(1));
+        cell_size.min(fit).max(1)
+    }
+
     /// Render the game state on the canvas, and advance the game state if the
     /// renderer is currently playing.
     pub fn render(&mut self) {
-        let time = Instant::now();
+        self.pacer.start_frame();
+        self.idle = !self.play && self.idle_when_paused && !self.needs_draw();
+        if !self.idle {
+            self.draw();
+        }
 
-        // Render the game.
+        // Advance the game state.
+        if self.play {
+            let generation = self.pane.game.generation();
+            let steps = match self.pause_at {
+                Some(target) if target > generation => usize::try_from(target - generation)
+                    .map_or(self.steps_per_frame, |left| left.min(self.steps_per_frame)),
+                _ => self.steps_per_frame,
+            };
+            let steps = self.pacer.allowed_steps(steps);
+            self.step(steps);
+            if self.pause_at == Some(self.pane.game.generation()) {
+                self.pause();
+            }
+            if self.dynamic_speed {
+                self.adjust_speed();
+            }
+        }
+        // Measure across frames while playing, starting over each time play
+        // resumes so time spent paused is not counted.
+        match self.play {
+            true => self.speed.record(self.pacer.now(), self.pane.game.generation()),
+            false => self.speed.reset(),
+        }
+        self.update_title();
+
+        // Block to achieve desired framerate, cross-fading to the new
+        // generation in the meantime if smooth.
+        if self.play {
+            if self.smooth {
+                self.cross_fade();
+            }
+            self.pacer.finish_frame();
+        }
+    }
+
+    /// Draw the cells that changed in this frame's steps fading from their
+    /// old colors to their new ones, over the time left in the frame. Fade
+    /// frames are only drawn while there is time to spare, so stepping is
+    /// never slowed; with no limit on the framerate, nothing is faded.
+    fn cross_fade(&mut self) {
+        let changed = iter::once(&self.pane).chain(self.compare.as_ref()).any(|pane| !pane.fading.is_empty());
+        if !changed || (self.fit_view && self.camera.is_some()) {
+            return;
+        }
+        for step in 1..FADE_STEPS {
+            let wait = self.pacer.time_left() / u32::try_from(FADE_STEPS - step + 1).unwrap();
+            if wait < MIN_FADE_FRAME {
+                break;
+            }
+            self.pacer.wait(wait);
+            self.draw_faded(Some(1.0 - step as f64 / FADE_STEPS as f64));
+        }
+    }
+
+    /// Whether anything shown on the canvas changed since it was last drawn:
+    /// cells of either game, the whole board, or the shape being dragged
+    /// out. A view following the live cells may still be moving, so it
+    /// always needs drawing.
+    fn needs_draw(&self) -> bool {
+        self.redraw || self.changed || self.fit_view
+            || iter::once(&self.pane).chain(self.compare.as_ref()).any(|pane| !pane.dirty.is_empty())
+    }
+
+    /// Whether the last render drew nothing, because the renderer is paused
+    /// and nothing shown had changed; see `set_idle_when_paused`. Nothing
+    /// more will be drawn until an event changes something, so the caller
+    /// can wait for one instead of rendering again right away.
+    pub fn idle(&self) -> bool {
+        self.idle
+    }
+
+    /// Set whether renders while paused skip drawing when nothing shown has
+    /// changed, instead of redrawing every time. On by default; edits,
+    /// stepping, `redraw_all`, and other changes to what is shown are still
+    /// drawn on the next render.
+    pub fn set_idle_when_paused(&mut self, idle_when_paused: bool) {
+        self.idle_when_paused = idle_when_paused;
+    }
+
+    /// Draw the games on the canvas, with a divider between them when
+    /// comparing.
+    fn draw(&mut self) {
+        self.draw_faded(None);
+    }
+
+    /// Draw the games on the canvas like `draw`, with the cells that changed
+    /// in the last frame's steps still showing `fade` of the way back to
+    /// their old colors, if given, from 0 to 1.
+    fn draw_faded(&mut self, fade: Option<f64>) {
         self.canvas.set_draw_color(Color::BLACK);
         self.canvas.clear();
-        self.canvas.set_draw_color(Color::WHITE);
-        for cell in self.game.live_cells() {
-            let (x, y) = (cell.c * self.cell_size, cell.r * self.cell_size);
-            let cell = Rect::new(
-                x.try_into().unwrap(), 
-                y.try_into().unwrap(),
-                self.cell_size.try_into().unwrap(),
+        if self.fit_view {
+            self.move_camera();
+        }
+        let palette = Palette {
+            color_mode: self.color_mode,
+            age_bands: &self.age_bands,
+            dead: if self.background.is_some() { CLEAR_COLOR } else { Color::BLACK },
+            high_contrast: self.high_contrast,
+            outline: self.outline,
+        };
+        for pane in iter::once(&mut self.pane).chain(self.compare.as_mut()) {
+            let camera = self.camera.filter(|_| self.fit_view);
+            if let Some(background) = &self.background {
+                // Through a view, the image fills the window instead of the board.
+                let (width, height) = (pane.game.cols * self.cell_size, pane.game.rows * self.cell_size);
+                let dest = camera.is_none().then(|| {
+                    Rect::new(pane.x.try_into().unwrap(), 0, width.try_into().unwrap(), height.try_into().unwrap())
+                });
+                if let Err(message) = self.canvas.copy(background, None, dest) {
+                    eprintln!("failed to draw background image: {}", message);
+                }
+            }
+            match camera {
+                Some(camera) => pane.draw_view(&mut self.canvas, palette, camera),
+                None => pane.draw(&mut self.canvas, palette, self.cell_size, self.redraw),
+            }
+            if let (Some(fade), None) = (fade, camera) {
+                pane.draw_fade(&mut self.canvas, palette, self.cell_size, fade);
+            }
+        }
+        if let Some(shape) = self.shape {
+            self.draw_shape_preview(shape);
+        }
+        if let Some(compare) = &self.compare {
+            let rows = self.pane.game.rows.max(compare.game.rows);
+            let divider = Rect::new(
+                (compare.x - self.cell_size).try_into().unwrap(),
+                0,
                 self.cell_size.try_into().unwrap(),
+                (rows * self.cell_size).try_into().unwrap(),
             );
-            if let Err(message) = self.canvas.fill_rect(cell) {
-                eprintln!("failed to draw rect {:?}: {}", cell, message);
+            self.canvas.set_draw_color(DIVIDER_COLOR);
+            if let Err(message) = self.canvas.fill_rect(divider) {
+                eprintln!("failed to draw divider {:?}: {}", divider, message);
             }
         }
         self.canvas.present();
+        self.redraw = false;
+        self.changed = false;
+    }
 
-        // Advance the game state.
-        if self.play {
-            for _ in 0..self.steps_per_frame {
-                self.game.step();
-            }
-            self.step_count += u128::try_from(self.steps_per_frame).unwrap();
+    /// Draw the cells of a shape being dragged out over the board.
+    fn draw_shape_preview(&mut self, shape: ShapeDrag) {
+        let cells = self.shape_tool.cells(shape.from, shape.to);
+        match self.camera.filter(|_| self.fit_view) {
+            Some(camera) => {
+                let scale = self.view_scale(camera);
+                let size = (scale.ceil() as u32).max(1);
+                self.canvas.set_draw_color(SHAPE_PREVIEW_COLOR);
+                for cell in cells {
+                    let x = ((cell.c as f64 - camera.c) * scale).floor();
+                    let y = ((cell.r as f64 - camera.r) * scale).floor();
+                    let rect = Rect::new(x as i32, y as i32, size, size);
+                    if let Err(message) = self.canvas.fill_rect(rect) {
+                        eprintln!("failed to draw rect {:?}: {}", rect, message);
+                    }
+                }
+            },
+            None => {
+                let x = match shape.pane {
+                    0 => self.pane.x,
+                    _ => self.compare.as_ref().unwrap().x,
+                };
+                for cell in cells {
+                    draw_cell(&mut self.canvas, cell, self.cell_size, 0, x, SHAPE_PREVIEW_COLOR, 0);
+                }
+            },
         }
-        let steps = self.step_count;
+    }
 
-        // Update the canvas window title to reflect current render settings.
-        let framerate = if self.framerate == MAX_FRAMERATE + 1 {
-            String::from("max")
+    /// Update the canvas window title to reflect the game and the current
+    /// render settings.
+    fn update_title(&mut self) {
+        let generation = self.count_format.format(self.pane.game.generation());
+        let steps = if self.pane.game.is_extinct() {
+            format!("{} (extinct)", generation)
         } else {
-            format!("{}", self.framerate)
+            generation
+        };
+
+        let framerate = match self.pacer.framerate() {
+            None => String::from("max"),
+            Some(framerate) if self.dynamic_speed => format!("{} (dynamic)", framerate),
+            Some(framerate) => format!("{}", framerate),
         };
         let iters = self.steps_per_frame;
-        if let Err(message) = self.canvas.window_mut().set_title(&format!(
-            "Gol | {} | FPS: {} | Evolutions Per Frame: {}",
+        let speed = match self.speed.generations_per_second() {
+            Some(speed) => format!(" | Speed: {:.0} gen/s", speed),
+            None => String::new(),
+        };
+        let names: Vec<&str> = iter::once(&self.pane).chain(self.compare.as_ref())
+            .filter_map(|pane| pane.game.meta().name.as_deref())
+            .collect();
+        let name = match names.is_empty() {
+            true => String::new(),
+            false => format!("{} | ", names.join(" vs ")),
+        };
+        let population = match &self.compare {
+            Some(compare) => format!(" | Population: {} vs {}", self.pane.game.population(), compare.game.population()),
+            None => String::new(),
+        };
+        let changes: Vec<String> = iter::once(&self.pane).chain(self.compare.as_ref())
+            .filter_map(|pane| pane.population_change(self.steps_per_frame))
+            .map(|change| format!("{:+}", change.round() as i64))
+            .collect();
+        let change = match changes.is_empty() {
+            true => String::new(),
+            false => format!(" | Change: {}/gen", changes.join(" vs ")),
+        };
+        let hover = match self.hover {
+            Some((x, y)) if !self.play => {
+                let (pane, cell) = (self.pane_at(x), self.cell_at(x, y));
+                let side = match (&self.compare, pane.x) {
+                    (None, _) => "",
+                    (Some(_), 0) => "left ",
+                    (Some(_), _) => "right ",
+                };
+                format!(
+                    " | {}({}, {}): {}",
+                    side,
+                    cell.r,
+                    cell.c,
+                    if pane.game.is_alive(cell) { "alive" } else { "dead" },
+                )
+            },
+            _ => String::new(),
+        };
+        let shape_tool = match self.play {
+            true => String::new(),
+            false => format!(" | Shape: {}", self.shape_tool),
+        };
+        let pause_at = match self.pause_at {
+            Some(target) => format!(" | Pause At: {}", self.count_format.format(target)),
+            None => String::new(),
+        };
+        let color = match self.high_contrast {
+            true => String::from("high contrast"),
+            false => self.color_mode.to_string(),
+        };
+        let (rows, cols) = self.boundaries();
+        let edges = format!(" | Edges: rows {}, cols {}", boundary_name(rows), boundary_name(cols));
+        let timing = match self.step_time {
+            Some(step_time) if self.profile => format!(" | Step: {}", self.timing_units.format(step_time)),
+            None if self.profile => String::from(" | Step: -"),
+            _ => String::new(),
+        };
+        let title = format!(
+            "Gol | {}{}{}{}{} | FPS: {} | Evolutions Per Frame: {}{} | Color: {}{}{}{}{}",
+            name,
             steps,
+            population,
+            change,
+            pause_at,
             framerate,
-            iters)
-        ) {
-            eprintln!("failed to change window title: `{}`", message);
+            iters,
+            speed,
+            color,
+            edges,
+            shape_tool,
+            timing,
+            hover,
+        );
+        if title == self.title {
+            return;
         }
-
-        // Block to achieve desired framerate.
-        let elapsed = time.elapsed().as_nanos();
-        if self.play && elapsed < self.min_render_nanos {
-            thread::sleep(Duration::from_nanos((self.min_render_nanos - elapsed).try_into().unwrap()));
+        if let Err(message) = self.canvas.window_mut().set_title(&title) {
+            eprintln!("failed to change window title: `{}`", message);
         }
+        self.title = title;
     }
     
     /// Tell the renderer to advance the game state after a render.
@@ -106,27 +588,81 @@ impl SdlRender {
         self.play = false;
     }
 
+    /// The generation the renderer pauses at while playing, if any.
+    pub fn pause_at(&self) -> Option<u128> {
+        self.pause_at
+    }
+
+    /// Set a generation to pause at while playing, or `None` to play without
+    /// stopping. Playing never steps past the target in a single frame, so
+    /// the game pauses exactly at it; playing on from there continues past it.
+    pub fn set_pause_at(&mut self, target: Option<u128>) {
+        self.pause_at = target;
+    }
+
     /// Whether this renderer advances the game state after rendering.
     pub fn playing(&self) -> bool {
         self.play
     }
 
-    /// Increase the framerate by 1 FPS, up to a max value.
+    /// Limit how many generations are computed per second while playing,
+    /// whatever the framerate and evolutions per frame, or `None` for no
+    /// limit; see `FramePacer::allowed_steps`.
+    pub fn set_max_steps_per_second(&mut self, limit: Option<u128>) {
+        self.pacer.set_max_steps_per_second(limit);
+    }
+
+    /// Increase the framerate by 1 FPS, up to a max value, and past the max
+    /// value to no limit, see `FramePacer::inc_framerate`.
     pub fn inc_framerate(&mut self) {
-        if self.framerate < MAX_FRAMERATE {
-            self.framerate += 1;
-            self.min_render_nanos = 1_000_000_000 / self.framerate;
-        } else if self.framerate == MAX_FRAMERATE {
-            self.framerate += 1;
-            self.min_render_nanos = 0;
-        }
+        self.pacer.inc_framerate();
     }
 
-    /// Decrease the framerate by 1 FPS, down to a minimum of 1 FPS.
+    /// Decrease the framerate by 1 FPS, down to a minimum of 1 FPS. Without
+    /// a limit, the framerate drops back to the max value.
     pub fn dec_framerate(&mut self) {
-        if self.framerate > 1 {
-            self.framerate -= 1;
-            self.min_render_nanos = 1_000_000_000 / self.framerate;
+        self.pacer.dec_framerate();
+    }
+
+    /// Replace the clock used to pace frames and measure speed, see
+    /// `FramePacer`. The system clock is used by default.
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.pacer.set_clock(clock);
+        self.speed.reset();
+    }
+
+    /// Whether the framerate is adjusted automatically based on how quickly
+    /// the population is changing.
+    pub fn dynamic_speed(&self) -> bool {
+        self.dynamic_speed
+    }
+
+    /// Set whether to automatically speed up the framerate while the
+    /// population is changing quickly and slow it down as the population
+    /// stabilizes. The framerate stays between 1 FPS and the max value.
+    pub fn set_dynamic_speed(&mut self, dynamic_speed: bool) {
+        self.dynamic_speed = dynamic_speed;
+        self.population_history.clear();
+    }
+
+    /// Nudge the framerate by 1 FPS toward the speed suited to the recent
+    /// population activity, of both games when comparing.
+    fn adjust_speed(&mut self) {
+        let population = iter::once(&self.pane).chain(self.compare.as_ref())
+            .map(|pane| pane.game.population())
+            .sum();
+        self.population_history.push_back(population);
+        if self.population_history.len() > DYNAMIC_SPEED_FRAMES {
+            self.population_history.pop_front();
+        }
+
+        let oldest = *self.population_history.front().unwrap();
+        let newest = *self.population_history.back().unwrap();
+        let active = oldest.abs_diff(newest) * 100 > oldest.max(1) * DYNAMIC_SPEED_ACTIVE_PERCENT;
+        if active && self.pacer.framerate().is_some_and(|framerate| framerate < MAX_FRAMERATE) {
+            self.inc_framerate();
+        } else if !active {
+            self.dec_framerate();
         }
     }
 
@@ -147,10 +683,972 @@ impl SdlRender {
     }
 
     /// Step the game state by `step_count` independent of rendering or playing.
+    /// When comparing, both games are stepped, and the step timing covers
+    /// both.
     pub fn step(&mut self, step_count: usize) {
+        let time = Instant::now();
+        let track_heat = self.color_mode == ColorMode::Heat;
+        if step_count > 0 {
+            if let Some((rows, cols)) = self.boundary.take() {
+                for pane in iter::once(&mut self.pane).chain(self.compare.as_mut()) {
+                    pane.game.set_boundary_rows(rows);
+                    pane.game.set_boundary_cols(cols);
+                }
+            }
+        }
+        // Clear the last frame's fade even when nothing steps, so a frame
+        // the pacer holds back does not fade the same cells again.
+        for pane in iter::once(&mut self.pane).chain(self.compare.as_mut()) {
+            pane.fading.clear();
+        }
         for _ in 0..step_count {
-            self.game.step();
+            for pane in iter::once(&mut self.pane).chain(self.compare.as_mut()) {
+                pane.step(!self.redraw, track_heat, self.smooth);
+            }
+            self.stats.observe(&self.pane.game);
+        }
+        if step_count > 0 {
+            self.step_time = Some(time.elapsed() / u32::try_from(step_count).unwrap());
+            // Edits are undone on the board they were drawn on, not an evolved one.
+            self.edit_history.clear();
+        }
+        if matches!(self.color_mode, ColorMode::Age | ColorMode::Bands) && step_count > 0 {
+            // Every live cell ages, so any live cell may change color.
+            self.redraw_all();
+        }
+    }
+
+    /// Randomly flip a fraction `rate` of the cells in the game, see
+    /// `GameOfLife::mutate`. When comparing, both games are mutated with the
+    /// same seed.
+    pub fn mutate(&mut self, rate: f64, seed: u64) {
+        for pane in iter::once(&mut self.pane).chain(self.compare.as_mut()) {
+            pane.game.mutate(rate, seed);
+        }
+        self.stats.observe(&self.pane.game);
+        self.redraw_all();
+    }
+
+    /// The index of the pane drawn at a horizontal pixel position in the
+    /// window: 0 for the game, 1 for the compared game.
+    fn pane_index_at(&self, x: i32) -> usize {
+        match &self.compare {
+            Some(compare) if x >= i32::try_from(compare.x).unwrap() => 1,
+            _ => 0,
         }
-        self.step_count += u128::try_from(step_count).unwrap();
+    }
+
+    /// The pane drawn at a horizontal pixel position in the window.
+    fn pane_at(&self, x: i32) -> &Pane {
+        match self.pane_index_at(x) {
+            0 => &self.pane,
+            _ => self.compare.as_ref().unwrap(),
+        }
+    }
+
+    fn pane_mut(&mut self, index: usize) -> &mut Pane {
+        match index {
+            0 => &mut self.pane,
+            _ => self.compare.as_mut().unwrap(),
+        }
+    }
+
+    /// Set the state of a cell by drawing, keeping track of the change for
+    /// undo.
+    fn edit(&mut self, pane: usize, cell: Cell, alive: bool) -> Option<Edit> {
+        let redraw = self.redraw;
+        let pane_state = self.pane_mut(pane);
+        let was_alive = pane_state.game.is_alive(cell);
+        if was_alive == alive {
+            return None;
+        }
+        pane_state.game.set_alive(cell, alive);
+        if !redraw {
+            pane_state.dirty.insert(cell);
+        }
+        if pane == 0 {
+            self.stats.observe(&self.pane.game);
+        }
+        Some(Edit { pane, cell, was_alive })
+    }
+
+    /// Start a brush stroke at a pixel position in the window. The brush
+    /// flips the cell there, and sets every cell the stroke passes over to
+    /// the same state, until `end_stroke`.
+    pub fn begin_stroke(&mut self, x: i32, y: i32) {
+        let alive = !self.pane_at(x).game.is_alive(self.cell_at(x, y));
+        self.stroke = Some(Stroke { alive, edits: Vec::new() });
+        self.stroke_to(x, y);
+    }
+
+    /// Continue the brush stroke to a pixel position in the window, if a
+    /// stroke is being drawn.
+    pub fn stroke_to(&mut self, x: i32, y: i32) {
+        let Some(alive) = self.stroke.as_ref().map(|stroke| stroke.alive) else {
+            return;
+        };
+        let (pane, cell) = (self.pane_index_at(x), self.cell_at(x, y));
+        if let Some(edit) = self.edit(pane, cell, alive) {
+            self.stroke.as_mut().unwrap().edits.push(edit);
+        }
+    }
+
+    /// Finish the brush stroke, so that `undo_edit` undoes all of it at once.
+    pub fn end_stroke(&mut self) {
+        if let Some(stroke) = self.stroke.take() {
+            if !stroke.edits.is_empty() {
+                self.edit_history.push(stroke.edits);
+            }
+        }
+    }
+
+    /// The shape drawn by dragging with the shape modifier.
+    pub fn shape_tool(&self) -> ShapeTool {
+        self.shape_tool
+    }
+
+    /// Switch to the next shape tool, see `ShapeTool::next`.
+    pub fn cycle_shape_tool(&mut self) {
+        self.shape_tool = self.shape_tool.next();
+        self.changed |= self.shape.is_some();
+    }
+
+    /// Start dragging out a shape at a pixel position in the window. The
+    /// shape is previewed until `end_shape` draws it.
+    pub fn begin_shape(&mut self, x: i32, y: i32) {
+        let cell = self.cell_at(x, y);
+        self.shape = Some(ShapeDrag { pane: self.pane_index_at(x), from: cell, to: cell });
+        self.changed = true;
+    }
+
+    /// Move the far corner of the shape being dragged out to a pixel
+    /// position in the window, if a shape is being dragged out. The corner
+    /// stays in the game the drag started in.
+    pub fn shape_to(&mut self, x: i32, y: i32) {
+        if let Some(mut shape) = self.shape {
+            let pane = match shape.pane {
+                0 => &self.pane,
+                _ => self.compare.as_ref().unwrap(),
+            };
+            let column = i32::try_from(pane.x).unwrap();
+            let width = i32::try_from(pane.game.cols * self.cell_size).unwrap();
+            let cell = self.cell_at(x.clamp(column, column + width - 1), y);
+            self.changed |= shape.to != cell;
+            shape.to = cell;
+            self.shape = Some(shape);
+        }
+    }
+
+    /// Draw the shape being dragged out as live cells, so that `undo_edit`
+    /// removes all of it at once.
+    pub fn end_shape(&mut self) {
+        let Some(shape) = self.shape.take() else {
+            return;
+        };
+        self.changed = true;
+        let cells = self.shape_tool.cells(shape.from, shape.to);
+        let redraw = self.redraw;
+        let pane = self.pane_mut(shape.pane);
+        let edits: Vec<Edit> = cells.iter()
+            .filter(|cell| !pane.game.is_alive(**cell))
+            .map(|cell| Edit { pane: shape.pane, cell: *cell, was_alive: false })
+            .collect();
+        pane.game.set_cells(&cells, true);
+        if !redraw {
+            pane.dirty.extend(edits.iter().map(|edit| edit.cell));
+        }
+        if shape.pane == 0 {
+            self.stats.observe(&self.pane.game);
+        }
+        if !edits.is_empty() {
+            self.edit_history.push(edits);
+        }
+    }
+
+    /// Undo the most recent brush stroke, returning whether there was one to
+    /// undo. Edits are kept separately from the game's evolution: only
+    /// strokes drawn since the game last stepped can be undone.
+    pub fn undo_edit(&mut self) -> bool {
+        self.end_stroke();
+        let Some(edits) = self.edit_history.pop() else {
+            return false;
+        };
+        for edit in edits.into_iter().rev() {
+            self.edit(edit.pane, edit.cell, edit.was_alive);
+        }
+        true
+    }
+
+    /// Scroll the world `dr` rows down and `dc` columns right, see
+    /// `GameOfLife::shift`. When comparing, both games scroll together. The
+    /// trail and heat start over, and earlier edits can no longer be undone.
+    pub fn shift(&mut self, dr: isize, dc: isize) {
+        for pane in iter::once(&mut self.pane).chain(self.compare.as_mut()) {
+            pane.game.shift(dr, dc);
+            pane.trail.clear();
+            pane.heat.clear();
+        }
+        self.stroke = None;
+        self.shape = None;
+        self.edit_history.clear();
+        self.redraw_all();
+    }
+
+    /// Get the cell drawn at a pixel position in the window, in the game
+    /// drawn there when comparing. Positions outside the board are clamped to
+    /// the nearest cell on the edge.
+    pub fn cell_at(&self, x: i32, y: i32) -> Cell {
+        if let (Some(camera), true) = (self.camera, self.fit_view) {
+            let scale = self.view_scale(camera);
+            let clamp = |edge: f64, pixel: i32, len: usize| {
+                ((edge + f64::from(pixel) / scale).floor().max(0.0) as usize).min(len - 1)
+            };
+            return Cell { r: clamp(camera.r, y, self.pane.game.rows), c: clamp(camera.c, x, self.pane.game.cols) };
+        }
+        let pane = self.pane_at(x);
+        let clamp = |pixel: i32, len: usize| {
+            let index = usize::try_from(pixel.max(0)).unwrap() / self.cell_size;
+            index.min(len - 1)
+        };
+        Cell {
+            r: clamp(y, pane.game.rows),
+            c: clamp(x - i32::try_from(pane.x).unwrap(), pane.game.cols),
+        }
+    }
+
+    /// Set the pixel position of the mouse over the window, or `None` if the
+    /// mouse left the window. While paused, the coordinate and state of the
+    /// cell under the mouse are shown in the window title.
+    pub fn hover(&mut self, position: Option<(i32, i32)>) {
+        self.hover = position;
+    }
+
+    /// The game being rendered.
+    pub fn game(&self) -> &GameOfLife {
+        &self.pane.game
+    }
+
+    /// Statistics of the game's run since it was given to the renderer. When
+    /// comparing, these cover the game on the left.
+    pub fn stats(&self) -> &RunStats {
+        &self.stats
+    }
+
+    /// Stop rendering and take back the game.
+    pub fn into_game(self) -> GameOfLife {
+        self.pane.game
+    }
+
+    /// Forget what was drawn on the game being rendered after replacing it
+    /// with another run elsewhere, see `show`.
+    fn replaced_game(&mut self) {
+        self.stats.observe(&self.pane.game);
+        self.stroke = None;
+        self.shape = None;
+        self.edit_history.clear();
+    }
+
+    /// Replace the game being rendered, e.g. to reseed the board. The trail
+    /// and heat start over with the new game, as do its statistics.
+    pub fn set_game(&mut self, game: GameOfLife) {
+        self.stats = RunStats::new(&game);
+        self.pane.game = game;
+        self.pane.changes.clear();
+        self.pane.trail.clear();
+        self.pane.heat.clear();
+        self.stroke = None;
+        self.shape = None;
+        self.edit_history.clear();
+        self.redraw_all();
+    }
+
+    /// The game being compared with, if any.
+    pub fn compare(&self) -> Option<&GameOfLife> {
+        self.compare.as_ref().map(|pane| &pane.game)
+    }
+
+    /// Show a second game to the right of the first, or `None` to stop
+    /// comparing. The games are drawn one cell apart, so the window should be
+    /// `cols + 1 + compare cols` cells wide.
+    pub fn set_compare(&mut self, game: Option<GameOfLife>) {
+        let x = (self.pane.game.cols + 1) * self.cell_size;
+        self.compare = game.map(|game| {
+            let mut pane = Pane::new(game, &self.canvas, self.cell_size, x);
+            pane.trail = Trail::new(self.pane.trail.length);
+            pane.heat = Heatmap::new(self.pane.heat.decay);
+            pane
+        });
+        self.redraw_all();
+    }
+
+    /// How live cells are colored.
+    pub fn color_mode(&self) -> ColorMode {
+        self.color_mode
+    }
+
+    /// Set how live cells are colored. Heat only builds up while coloring
+    /// by heat, and starts over each time the mode is switched to.
+    pub fn set_color_mode(&mut self, color_mode: ColorMode) {
+        self.color_mode = color_mode;
+        for pane in iter::once(&mut self.pane).chain(self.compare.as_mut()) {
+            pane.heat.clear();
+        }
+        self.redraw_all();
+    }
+
+    /// Set the colors of live cells by age when coloring by bands.
+    pub fn set_age_bands(&mut self, age_bands: AgeBands) {
+        self.age_bands = age_bands;
+        self.redraw_all();
+    }
+
+    /// Show an image behind the board, stretched to fill it, with live cells
+    /// drawn over it and dead cells showing it through; `None` removes it.
+    /// Any format SDL_image reads can be loaded, such as PNG, JPEG, or BMP.
+    /// Only built with the `bg-image` feature, which links SDL2_image.
+    #[cfg(feature = "bg-image")]
+    pub fn set_background(&mut self, path: Option<&Path>) -> Result<(), String> {
+        self.background = path.map(|path| self.canvas.texture_creator().load_texture(path)).transpose()?;
+        self.redraw_all();
+        Ok(())
+    }
+
+    /// Switch to the next color mode, see `ColorMode::next`.
+    pub fn cycle_color_mode(&mut self) {
+        self.set_color_mode(self.color_mode.next());
+    }
+
+    /// The boundary modes of rows and columns the next step uses, including
+    /// any toggled since the last step.
+    fn boundaries(&self) -> (BoundaryMode, BoundaryMode) {
+        self.boundary.unwrap_or((self.pane.game.boundary_rows(), self.pane.game.boundary_cols()))
+    }
+
+    /// Switch whether the top and bottom edges wrap around to each other,
+    /// between toroidal and dead. The change applies from the next
+    /// generation on, to both games when comparing.
+    pub fn toggle_wrap_rows(&mut self) {
+        let (rows, cols) = self.boundaries();
+        self.boundary = Some((toggled_wrap(rows), cols));
+    }
+
+    /// Switch whether the left and right edges wrap around to each other,
+    /// between toroidal and dead. The change applies from the next
+    /// generation on, to both games when comparing.
+    pub fn toggle_wrap_cols(&mut self) {
+        let (rows, cols) = self.boundaries();
+        self.boundary = Some((rows, toggled_wrap(cols)));
+    }
+
+    /// Set whether the view follows the live cells, zooming and panning so
+    /// they fill the window with a little room around them, instead of
+    /// showing the whole board. The view glides toward the live cells rather
+    /// than jumping each frame. Meant for a single game; when comparing,
+    /// both games are shown through the view of the first.
+    pub fn set_fit_view(&mut self, fit_view: bool) {
+        self.fit_view = fit_view;
+        self.camera = None;
+        self.redraw_all();
+    }
+
+    /// Move the view toward the live cells of the game, or leave it where it
+    /// is if every cell is dead.
+    fn move_camera(&mut self) {
+        let (width, height) = match self.canvas.output_size() {
+            Ok((width, height)) if width > 0 && height > 0 => (f64::from(width), f64::from(height)),
+            _ => return,
+        };
+        let Some(region) = self.pane.game.bounding_box() else {
+            return;
+        };
+        let target = Camera::fitting(region, width, height);
+        self.camera = Some(match self.camera {
+            Some(camera) => camera.ease_toward(target),
+            None => target,
+        });
+    }
+
+    /// Set whether to show how long each generation takes to compute in the
+    /// window title.
+    pub fn set_profile(&mut self, profile: bool) {
+        self.profile = profile;
+    }
+
+    /// Set whether cells are drawn in high contrast for low vision: live
+    /// cells white and every other cell black, whatever the color mode, with
+    /// a black grid line between cells at least 4 pixels across.
+    pub fn set_high_contrast(&mut self, high_contrast: bool) {
+        self.high_contrast = high_contrast;
+        self.redraw = true;
+    }
+
+    /// Set whether cells that change cross-fade from their old colors to
+    /// their new ones while playing, drawn with alpha blending over the time
+    /// left in each frame after stepping. This only shows when frames have
+    /// time to spare, i.e. at low framerates, and never slows stepping.
+    pub fn set_smooth(&mut self, smooth: bool) {
+        self.smooth = smooth;
+        self.pane.fading.clear();
+        if let Some(compare) = self.compare.as_mut() {
+            compare.fading.clear();
+        }
+    }
+
+    /// Set whether cells are drawn as outlines instead of filled squares,
+    /// e.g. to see where structures overlap. The outline is an eighth of the
+    /// cell's width, and at least a pixel.
+    pub fn set_outline(&mut self, outline: bool) {
+        self.outline = outline;
+        self.redraw = true;
+    }
+
+    /// Set how generation counts are written in the window title.
+    pub fn set_count_format(&mut self, count_format: CountFormat) {
+        self.count_format = count_format;
+    }
+
+    /// Switch the units step timing is shown in between milliseconds per
+    /// generation and generations per second. The choice is kept for the
+    /// rest of the session, including when the game is replaced.
+    pub fn toggle_timing_units(&mut self) {
+        self.timing_units = match self.timing_units {
+            TimingUnits::Millis => TimingUnits::GenerationsPerSecond,
+            TimingUnits::GenerationsPerSecond => TimingUnits::Millis,
+        };
+    }
+
+    /// Set how many generations dead cells stay visible as a fading trail
+    /// after they die; 0 disables the trail.
+    pub fn set_trail(&mut self, length: usize) {
+        for pane in iter::once(&mut self.pane).chain(self.compare.as_mut()) {
+            pane.trail = Trail::new(length);
+        }
+        self.redraw_all();
+    }
+
+    /// Set the fraction of its heat each cell loses every generation when
+    /// coloring by heat, from 0 (exclusive) to 1. Smaller values keep
+    /// activity visible for longer. The heat starts over.
+    pub fn set_heat_decay(&mut self, decay: f32) {
+        for pane in iter::once(&mut self.pane).chain(self.compare.as_mut()) {
+            pane.heat = Heatmap::new(decay);
+        }
+        self.redraw_all();
+    }
+
+    /// Scale the board up or down to fill the window, e.g. when full screen,
+    /// following the window as it is resized. With `letterbox`, the board is
+    /// scaled evenly so cells stay square, and centered with bars of the
+    /// background color filling the rest of the window; otherwise it is
+    /// stretched to fill all of it. Not meant for fitting the view, which
+    /// already fills the window.
+    pub fn scale_to_window(&mut self, letterbox: bool) -> Result<(), String> {
+        self.window_scale = Some(letterbox);
+        self.apply_window_scale()
+    }
+
+    /// Set the canvas to draw the board across the window, see
+    /// `scale_to_window`.
+    fn apply_window_scale(&mut self) -> Result<(), String> {
+        let Some(letterbox) = self.window_scale else {
+            return Ok(());
+        };
+        let (width, height) = self.board_size();
+        if letterbox {
+            // SDL centers the board in the window at the largest even scale,
+            // and clearing the canvas fills the bars around it.
+            return self.canvas.set_logical_size(width, height).map_err(|error| error.to_string());
+        }
+        let (window_width, window_height) = self.canvas.output_size()?;
+        self.canvas.set_scale(window_width as f32 / width as f32, window_height as f32 / height as f32)
+    }
+
+    /// The size in pixels of the games as drawn, including the divider when
+    /// comparing.
+    fn board_size(&self) -> (u32, u32) {
+        let (width, rows) = match &self.compare {
+            Some(compare) => (compare.x + compare.game.cols * self.cell_size, self.pane.game.rows.max(compare.game.rows)),
+            None => (self.pane.game.cols * self.cell_size, self.pane.game.rows),
+        };
+        (width.try_into().unwrap(), (rows * self.cell_size).try_into().unwrap())
+    }
+
+    /// The size in pixels that cells are drawn at through a view.
+    fn view_scale(&self, camera: Camera) -> f64 {
+        let width = self.canvas.output_size().map_or(1, |(width, _)| width);
+        f64::from(width) / camera.cols
+    }
+
+    /// Draw the whole board on the next render instead of only the cells that
+    /// changed, e.g. after the window contents were lost.
+    pub fn redraw_all(&mut self) {
+        if let Err(message) = self.apply_window_scale() {
+            eprintln!("failed to scale the board to the window: {}", message);
+        }
+        self.redraw = true;
+        self.pane.dirty.clear();
+        if let Some(compare) = self.compare.as_mut() {
+            compare.dirty.clear();
+        }
+    }
+}
+
+/// Lets the window show a game that is run elsewhere, such as by a loop that
+/// drives any `Renderer`. The game is never stepped by the renderer, so
+/// trails and heat do not build up; use the renderer's own methods to run a
+/// game interactively.
+///
+/// The inherent `SdlRender::render` plays the renderer's own game, so call
+/// this one as `Renderer::render(&mut renderer, &game)`.
+impl Renderer for SdlRender {
+    /// Show `game` in place of the game being rendered, redrawing only the
+    /// cells that differ from it. A game equal to the one shown changes
+    /// nothing, keeping any edits that can be undone. When comparing, the
+    /// compared game is shown as it is.
+    fn render(&mut self, game: &GameOfLife) -> io::Result<()> {
+        let same = self.pane.game.generation() == game.generation() && self.pane.game.rule() == game.rule();
+        match self.pane.game.diff(game) {
+            Ok(delta) if same && delta.born.is_empty() && delta.died.is_empty() => {},
+            Ok(delta) => {
+                self.pane.game = game.clone();
+                if !self.redraw {
+                    self.pane.dirty.extend(delta.born.into_iter().chain(delta.died));
+                }
+                self.replaced_game();
+            },
+            // A game of another size needs the whole board drawn again.
+            Err(_) => {
+                self.pane.game = game.clone();
+                self.replaced_game();
+                self.redraw_all();
+            },
+        }
+        self.draw();
+        self.update_title();
+        Ok(())
+    }
+
+    fn playing(&self) -> bool {
+        self.play
+    }
+}
+
+/// A game drawn in one part of the window.
+struct Pane {
+    game: GameOfLife,  // game to render
+    board: Option<Texture>,  // persistent image of the board, if supported
+    dirty: HashSet<Cell>,  // cells that changed since the board was drawn
+    trail: Trail,  // fading trail of recently dead cells
+    heat: Heatmap,  // recent activity of each cell
+    changes: VecDeque<isize>,  // population change of each recent generation, oldest first
+    fading: HashMap<Cell, bool>,  // cells changed by the last frame's steps, and whether they came alive
+    x: usize,  // left edge of the pane in the window, in pixels
+}
+
+impl Pane {
+    fn new(game: GameOfLife, canvas: &Canvas<Window>, cell_size: usize, x: usize) -> Pane {
+        let board = if canvas.render_target_supported() {
+            let (width, height) = (game.cols * cell_size, game.rows * cell_size);
+            match canvas.create_texture_target(None, width.try_into().unwrap(), height.try_into().unwrap()) {
+                Ok(mut texture) => {
+                    // Cleared cells let whatever is drawn beneath the board show through.
+                    texture.set_blend_mode(BlendMode::Blend);
+                    Some(texture)
+                },
+                Err(message) => {
+                    eprintln!("failed to create board texture, redrawing every frame: {}", message);
+                    None
+                },
+            }
+        } else {
+            None
+        };
+        Pane {
+            game, board, x,
+            dirty: HashSet::new(),
+            changes: VecDeque::new(),
+            fading: HashMap::new(),
+            trail: Trail::new(0),
+            heat: Heatmap::new(DEFAULT_HEAT_DECAY),
+        }
+    }
+
+    /// Draw the game in its part of the canvas, either all of it or only the
+    /// cells that changed since it was last drawn.
+    fn draw(&mut self, canvas: &mut Canvas<Window>, palette: Palette, cell_size: usize, redraw: bool) {
+        let painter = Painter {
+            game: &self.game,
+            trail: &self.trail,
+            heat: &self.heat,
+            palette, cell_size,
+        };
+        match self.board.as_mut() {
+            Some(board) => {
+                let dirty = &self.dirty;
+                let result = canvas.with_texture_canvas(board, |canvas| {
+                    if redraw {
+                        canvas.set_draw_color(painter.color_of_dead());
+                        canvas.clear();
+                        painter.draw_board(canvas);
+                    } else {
+                        painter.draw_cells(canvas, dirty.iter().copied(), 0);
+                    }
+                });
+                if let Err(message) = result {
+                    eprintln!("failed to draw to board texture: {}", message);
+                }
+                let query = board.query();
+                let dest = Rect::new(self.x.try_into().unwrap(), 0, query.width, query.height);
+                if let Err(message) = canvas.copy(board, None, dest) {
+                    eprintln!("failed to copy board texture: {}", message);
+                }
+            },
+            None => {
+                let cells = self.trail.cells.keys().chain(self.heat.cells.keys()).copied().chain(self.game.live_cells());
+                painter.draw_cells(canvas, cells, self.x);
+            },
+        }
+        self.dirty.clear();
+    }
+
+    /// Draw the part of the game inside a view, scaled to fill the canvas.
+    /// Every cell in view is drawn each time, without the board texture.
+    fn draw_view(&mut self, canvas: &mut Canvas<Window>, palette: Palette, camera: Camera) {
+        let width = canvas.output_size().map_or(1, |(width, _)| width);
+        let scale = f64::from(width) / camera.cols;
+        let painter = Painter { game: &self.game, trail: &self.trail, heat: &self.heat, palette, cell_size: 1 };
+        let in_view = |cell: &Cell| {
+            let (r, c) = (cell.r as f64, cell.c as f64);
+            r + 1.0 > camera.r && r < camera.r + camera.rows && c + 1.0 > camera.c && c < camera.c + camera.cols
+        };
+        let cells = self.trail.cells.keys().chain(self.heat.cells.keys()).copied().chain(self.game.live_cells());
+        for cell in cells.filter(in_view) {
+            let x = ((cell.c as f64 - camera.c) * scale).floor();
+            let y = ((cell.r as f64 - camera.r) * scale).floor();
+            let size = (scale.ceil() as u32).max(1);
+            let rect = Rect::new(x as i32, y as i32, size, size);
+            fill_rect(canvas, rect, painter.color(cell), painter.outline(size));
+        }
+        self.dirty.clear();
+    }
+
+    /// Draw each cell that changed in the last frame's steps `fade` of the
+    /// way from its new color back to its old one, blending over the board
+    /// already drawn.
+    fn draw_fade(&self, canvas: &mut Canvas<Window>, palette: Palette, cell_size: usize, fade: f64) {
+        let painter = Painter { game: &self.game, trail: &self.trail, heat: &self.heat, palette, cell_size };
+        let alpha = (fade.clamp(0.0, 1.0) * 255.0).round() as u8;
+        let grid_line = painter.grid_line();
+        canvas.set_blend_mode(BlendMode::Blend);
+        for (cell, alive) in self.fading.iter() {
+            let old = match alive {
+                true => painter.color_of_dead(),
+                false => painter.newborn_color(),
+            };
+            let color = Color::RGBA(old.r, old.g, old.b, alpha);
+            draw_cell(canvas, *cell, cell_size, grid_line, self.x, color, 0);
+        }
+        canvas.set_blend_mode(BlendMode::None);
+    }
+
+    /// The average change in population per generation over the last
+    /// `generations` generations, or as many as have been stepped; `None`
+    /// before the first step.
+    fn population_change(&self, generations: usize) -> Option<f64> {
+        let recent: Vec<isize> = self.changes.iter().rev().take(generations.max(1)).copied().collect();
+        match recent.is_empty() {
+            true => None,
+            false => Some(recent.iter().sum::<isize>() as f64 / recent.len() as f64),
+        }
+    }
+
+    /// Step the game by one generation, keeping track of the cells that
+    /// change color if `track_dirty` is set, of the heat of each cell if
+    /// `track_heat` is set, and of the cells to cross-fade if `track_fade` is
+    /// set.
+    fn step(&mut self, track_dirty: bool, track_heat: bool, track_fade: bool) {
+        let delta = self.game.step_delta();
+        if track_fade {
+            // A cell that changes back within the frame has nothing to fade.
+            for (cells, alive) in [(&delta.born, true), (&delta.died, false)] {
+                for cell in cells {
+                    if self.fading.remove(cell).is_none() {
+                        self.fading.insert(*cell, alive);
+                    }
+                }
+            }
+        }
+        self.changes.push_back(delta.born.len() as isize - delta.died.len() as isize);
+        if self.changes.len() > MAX_STEPS_PER_FRAME {
+            self.changes.pop_front();
+        }
+        if track_dirty {
+            // Fading and cooling cells change color every step, even if
+            // still dead.
+            self.dirty.extend(self.trail.cells.keys());
+            self.dirty.extend(self.heat.cells.keys());
+        }
+        self.trail.update(&delta);
+        if track_heat {
+            self.heat.update(&delta);
+        }
+        if track_dirty {
+            self.dirty.extend(delta.born);
+            self.dirty.extend(delta.died);
+        }
+    }
+}
+
+/// Fading trail of recently dead cells.
+struct Trail {
+    length: usize,  // generations a dead cell stays visible
+    cells: HashMap<Cell, usize>,  // dead cells and generations left visible
+}
+
+impl Trail {
+    fn new(length: usize) -> Trail {
+        Trail { length, cells: HashMap::new() }
+    }
+
+    /// Fade the trail by one generation and add cells that just died.
+    fn update(&mut self, delta: &StepDelta) {
+        if self.length == 0 {
+            return;
+        }
+        self.cells.retain(|_, left| {
+            *left -= 1;
+            *left > 0
+        });
+        for cell in delta.born.iter() {
+            self.cells.remove(cell);
+        }
+        for cell in delta.died.iter().copied() {
+            self.cells.insert(cell, self.length);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    /// The dimmed color of a cell in the trail, if it is in the trail.
+    fn color(&self, cell: Cell) -> Option<Color> {
+        self.cells.get(&cell).map(|left| {
+            let level = 160 * left / self.length;
+            let level = level.try_into().unwrap();
+            Color::RGB(level, level, level)
+        })
+    }
+}
+
+/// Recent activity of each cell: a cell heats up each time it is born or
+/// dies, and cools by a fixed fraction every generation.
+struct Heatmap {
+    decay: f32,  // fraction of heat lost each generation
+    cells: HashMap<Cell, f32>,  // cells that are not cold, and their heat
+}
+
+impl Heatmap {
+    fn new(decay: f32) -> Heatmap {
+        Heatmap { decay, cells: HashMap::new() }
+    }
+
+    /// Cool every cell by one generation and heat the cells that changed.
+    fn update(&mut self, delta: &StepDelta) {
+        let keep = 1.0 - self.decay;
+        self.cells.retain(|_, heat| {
+            *heat *= keep;
+            *heat >= HEAT_CUTOFF
+        });
+        for cell in delta.born.iter().chain(delta.died.iter()).copied() {
+            *self.cells.entry(cell).or_insert(0.0) += 1.0;
+        }
+    }
+
+    fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    /// The color of a cell's heat, from dark red through red and orange to
+    /// yellow for cells at least `HEAT_COLOR_SPAN` hot, if it is not cold.
+    fn color(&self, cell: Cell) -> Option<Color> {
+        self.cells.get(&cell).map(|heat| {
+            let level = (heat / HEAT_COLOR_SPAN).min(1.0);
+            let red = (80.0 + 175.0 * (level * 2.0).min(1.0)) as u8;
+            let green = (255.0 * (level * 2.0 - 1.0).max(0.0)) as u8;
+            Color::RGB(red, green, 0)
+        })
+    }
+}
+
+/// The colors cells are drawn in.
+#[derive(Clone, Copy)]
+struct Palette<'a> {
+    color_mode: ColorMode,  // how live cells are colored
+    age_bands: &'a AgeBands,  // colors of live cells by age when coloring by bands
+    dead: Color,  // color of dead cells outside the trail and heat
+    high_contrast: bool,  // whether every cell is drawn in one of two strongly contrasting colors
+    outline: bool,  // whether cells other than plain dead ones are drawn as outlines
+}
+
+/// Draws cells of a game on a canvas.
+struct Painter<'a> {
+    game: &'a GameOfLife,  // game to draw
+    trail: &'a Trail,  // trail of recently dead cells to draw
+    heat: &'a Heatmap,  // recent activity of each cell, to draw when coloring by heat
+    palette: Palette<'a>,  // colors to draw cells in
+    cell_size: usize,  // side length of square cell, in pixels
+}
+
+impl Painter<'_> {
+    /// The color to draw a cell with. In high contrast, live cells are white
+    /// and every other cell is black, whatever the color mode.
+    fn color(&self, cell: Cell) -> Color {
+        let palette = self.palette;
+        if palette.high_contrast {
+            return match self.game.is_alive(cell) {
+                true => HIGH_CONTRAST_LIVE_COLOR,
+                false => HIGH_CONTRAST_DEAD_COLOR,
+            };
+        }
+        match (self.game.age(cell), palette.color_mode) {
+            (Some(age), ColorMode::Age) => age_color(age),
+            (Some(age), ColorMode::Bands) => palette.age_bands.color(age),
+            (Some(_), ColorMode::Single | ColorMode::Heat) => Color::WHITE,
+            (None, ColorMode::Heat) => self.heat.color(cell)
+                .or_else(|| self.trail.color(cell))
+                .unwrap_or(palette.dead),
+            (None, _) => self.trail.color(cell).unwrap_or(palette.dead),
+        }
+    }
+
+    /// The width of the grid lines left between cells: one pixel in high
+    /// contrast when cells are large enough, otherwise none.
+    fn grid_line(&self) -> usize {
+        match self.palette.high_contrast && self.cell_size >= GRID_LINE_MIN_CELL_SIZE {
+            true => 1,
+            false => 0,
+        }
+    }
+
+    /// Draw every live, fading, or hot cell of the game, over whatever the
+    /// canvas already shows, e.g. the board texture just cleared.
+    fn draw_board(&self, canvas: &mut Canvas<Window>) {
+        self.draw_cells(canvas, self.trail.cells.keys().copied(), 0);
+        self.draw_cells(canvas, self.heat.cells.keys().copied(), 0);
+        self.draw_cells(canvas, self.game.live_cells().into_iter(), 0);
+    }
+
+    /// Draw the given cells in their current colors, with the board's left
+    /// edge `x` pixels from the left of the canvas.
+    fn draw_cells(&self, canvas: &mut Canvas<Window>, cells: impl Iterator<Item = Cell>, x: usize) {
+        let grid_line = self.grid_line();
+        let outline = self.outline((self.cell_size - grid_line).try_into().unwrap());
+        let dead = self.color_of_dead();
+        for cell in cells {
+            let color = self.color(cell);
+            if outline > 0 && color != dead {
+                // Clear the inside, which may still show the cell's last color.
+                draw_cell(canvas, cell, self.cell_size, grid_line, x, dead, 0);
+                draw_cell(canvas, cell, self.cell_size, grid_line, x, color, outline);
+            } else {
+                draw_cell(canvas, cell, self.cell_size, grid_line, x, color, 0);
+            }
+        }
+    }
+
+    /// The thickness of the outline to draw a cell `size` pixels across
+    /// with, scaling with the cell, or 0 to fill it.
+    fn outline(&self, size: u32) -> u32 {
+        match self.palette.outline {
+            true => (size / OUTLINE_SCALE).max(1),
+            false => 0,
+        }
+    }
+
+    /// The color of a cell that was just born.
+    fn newborn_color(&self) -> Color {
+        match (self.palette.high_contrast, self.palette.color_mode) {
+            (true, _) => HIGH_CONTRAST_LIVE_COLOR,
+            (false, ColorMode::Age) => age_color(1),
+            (false, ColorMode::Bands) => self.palette.age_bands.color(1),
+            (false, ColorMode::Single | ColorMode::Heat) => Color::WHITE,
+        }
+    }
+
+    /// The color of dead cells outside the trail and heat.
+    fn color_of_dead(&self) -> Color {
+        match self.palette.high_contrast {
+            true => HIGH_CONTRAST_DEAD_COLOR,
+            false => self.palette.dead,
+        }
+    }
+}
+
+/// The boundary mode an edge switches to when its wrapping is toggled: edges
+/// that wrap become dead, and any other edge starts wrapping.
+fn toggled_wrap(boundary: BoundaryMode) -> BoundaryMode {
+    match boundary {
+        BoundaryMode::Toroidal => BoundaryMode::Dead,
+        BoundaryMode::Dead | BoundaryMode::Reflect => BoundaryMode::Toroidal,
+    }
+}
+
+/// How a boundary mode is shown in the window title.
+fn boundary_name(boundary: BoundaryMode) -> &'static str {
+    match boundary {
+        BoundaryMode::Toroidal => "wrap",
+        BoundaryMode::Dead => "dead",
+        BoundaryMode::Reflect => "reflect",
+    }
+}
+
+/// The color of a live cell of the given age, fading from white for newborn
+/// cells to blue for cells at least `AGE_COLOR_SPAN` generations old.
+fn age_color(age: u32) -> Color {
+    let old = age.saturating_sub(1).min(AGE_COLOR_SPAN);
+    let fade = |level: u32| u8::try_from(255 - (255 - level) * old / AGE_COLOR_SPAN).unwrap();
+    Color::RGB(fade(60), fade(90), 255)
+}
+
+/// Fill the square of a single cell with a color, on a board whose left edge
+/// is `x` pixels from the left of the canvas, leaving `grid_line` pixels
+/// along its right and bottom edges unfilled. With an `outline` thickness,
+/// only a border that many pixels thick is drawn.
+fn draw_cell(
+    canvas: &mut Canvas<Window>,
+    cell: Cell,
+    cell_size: usize,
+    grid_line: usize,
+    x: usize,
+    color: Color,
+    outline: u32,
+) {
+    let (x, y) = (x + cell.c * cell_size, cell.r * cell_size);
+    let size = cell_size - grid_line;
+    let rect = Rect::new(
+        x.try_into().unwrap(),
+        y.try_into().unwrap(),
+        size.try_into().unwrap(),
+        size.try_into().unwrap(),
+    );
+    fill_rect(canvas, rect, color, outline);
+}
+
+/// Fill a rectangle with a color, or with an `outline` thickness, draw only
+/// a border that many pixels thick, as nested rectangle outlines.
+fn fill_rect(canvas: &mut Canvas<Window>, rect: Rect, color: Color, outline: u32) {
+    canvas.set_draw_color(color);
+    let result = match outline {
+        0 => canvas.fill_rect(rect),
+        _ => (0..outline)
+            .take_while(|inset| rect.width() > 2 * inset && rect.height() > 2 * inset)
+            .try_for_each(|inset| {
+                let (x, y) = (rect.x() + inset as i32, rect.y() + inset as i32);
+                canvas.draw_rect(Rect::new(x, y, rect.width() - 2 * inset, rect.height() - 2 * inset))
+            }),
+    };
+    if let Err(message) = result {
+        eprintln!("failed to draw rect {:?}: {}", rect, message);
     }
 }
@@ -0,0 +1,126 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::{GameOfLife, draw_label, render_to_rgba, write_png};
+
+/// A way of showing a game as it runs, e.g. in a window, as text, or as
+/// image files.
+///
+/// Whoever runs the game owns it and steps it, handing each generation to
+/// `render`; the control methods tell it whether to keep stepping.
+pub trait Renderer {
+    /// Show a generation of a game.
+    fn render(&mut self, game: &GameOfLife) -> io::Result<()>;
+
+    /// Whether the game should be stepped between renders. The game is
+    /// rendered again without stepping while this is false.
+    fn playing(&self) -> bool {
+        true
+    }
+
+    /// Whether the renderer has shown all it will, so the run should end.
+    fn finished(&self) -> bool {
+        false
+    }
+}
+
+/// Writes each generation as text, headed by its number, like
+/// `--headless` mode.
+pub struct TextRender<W: Write> {
+    writer: W,  // destination of the text
+}
+
+impl<W: Write> TextRender<W> {
+    /// Create a renderer that writes to `writer`.
+    pub fn new(writer: W) -> TextRender<W> {
+        TextRender { writer }
+    }
+
+    /// Stop rendering and take back the writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> Renderer for TextRender<W> {
+    fn render(&mut self, game: &GameOfLife) -> io::Result<()> {
+        write!(self.writer, "generation {}\n{}\n\n", game.generation(), game)?;
+        self.writer.flush()
+    }
+}
+
+/// A corner of an image.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Default)]
+pub enum Corner {
+    #[default]
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// Writes each generation to a directory as a PNG image, named like
+/// `frame_0000.png`, until a set number of frames have been written.
+pub struct PngRender {
+    dir: PathBuf,  // directory the images are written to
+    cell_size: usize,  // side length of each cell, in pixels
+    frames: usize,  // number of images to write
+    written: usize,  // number of images written so far
+    recenter: bool,  // whether images are drawn with the live cells wrapped to the center
+    marker: Option<(Corner, usize)>,  // corner each image is labeled with its generation in, and the label's scale, if labeled
+}
+
+impl PngRender {
+    /// Create a renderer that writes `frames` images with cells `cell_size`
+    /// pixels wide, creating the directory if needed.
+    pub fn new(dir: &Path, cell_size: usize, frames: usize) -> io::Result<PngRender> {
+        fs::create_dir_all(dir)?;
+        Ok(PngRender { dir: dir.to_path_buf(), cell_size, frames, written: 0, recenter: false, marker: None })
+    }
+
+    /// Set whether each image is drawn with the live cells wrapped around
+    /// the edges of a toroidal board to the center, see
+    /// `GameOfLife::recentered`, so a pattern crossing the edges is drawn in
+    /// one piece. Only the images are shifted, not the game being run.
+    pub fn set_recenter(&mut self, recenter: bool) {
+        self.recenter = recenter;
+    }
+
+    /// Set a corner to label each image with the number of its generation
+    /// in, with each dot of the label's font `scale` pixels across, see
+    /// `draw_label`; or `None`, the default, to leave images unlabeled.
+    pub fn set_generation_marker(&mut self, marker: Option<(Corner, usize)>) {
+        self.marker = marker;
+    }
+
+    /// The path the next image will be written to. Frame numbers are padded
+    /// to at least 4 digits, and to the same width for every frame.
+    fn next_path(&self) -> PathBuf {
+        let digits = self.frames.saturating_sub(1).to_string().len().max(4);
+        self.dir.join(format!("frame_{:0digits$}.png", self.written, digits = digits))
+    }
+}
+
+impl Renderer for PngRender {
+    fn render(&mut self, game: &GameOfLife) -> io::Result<()> {
+        let path = self.next_path();
+        let (width, height) = (game.cols * self.cell_size, game.rows * self.cell_size);
+        let mut pixels = match self.recenter {
+            true => render_to_rgba(&game.recentered(), self.cell_size),
+            false => render_to_rgba(game, self.cell_size),
+        };
+        if let Some((corner, scale)) = self.marker {
+            draw_label(&mut pixels, width, height, &game.generation().to_string(), corner, scale);
+        }
+        File::create(&path)
+            .and_then(|file| write_png(&mut BufWriter::new(file), width, height, &pixels))
+            .map_err(|error| io::Error::new(error.kind(), format!("`{}`: {}", path.display(), error)))?;
+        self.written += 1;
+        Ok(())
+    }
+
+    fn finished(&self) -> bool {
+        self.written >= self.frames
+    }
+}
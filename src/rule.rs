@@ -0,0 +1,152 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// A Life-like rule in birth/survival notation, such as `B3/S23` for
+/// Conway's Game of Life.
+///
+/// By default a cell's own state is not part of its neighbor count. A rule
+/// can instead count the cell itself, see `set_counts_center`, which is
+/// written with a `/C` suffix, such as `B3/S23/C`.
+///
+/// Rules are serialized as their rule string, which is checked when
+/// deserializing.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct RuleString {
+    birth: [bool; 9],  // birth[n]: dead cell with n live neighbors is born
+    survive: [bool; 9],  // survive[n]: live cell with n live neighbors survives
+    counts_center: bool,  // whether a live cell counts itself as a neighbor
+}
+
+/// Error produced when a rule string cannot be parsed.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ParseRuleError(String);
+
+impl fmt::Display for ParseRuleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid rule: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseRuleError {}
+
+impl RuleString {
+    /// The standard Conway's Game of Life rule, `B3/S23`.
+    pub fn conway() -> RuleString {
+        RuleString::new(&[3], &[2, 3]).unwrap()
+    }
+
+    /// Create a rule from the neighbor counts that cause a birth and the
+    /// neighbor counts that allow a live cell to survive. Counts must be in
+    /// `0..=8`, and birth on 0 neighbors is not supported.
+    pub fn new(birth: &[u8], survive: &[u8]) -> Result<RuleString, ParseRuleError> {
+        let mut rule = RuleString { birth: [false; 9], survive: [false; 9], counts_center: false };
+        for &n in birth {
+            match n {
+                0 => return Err(ParseRuleError(String::from("birth on 0 neighbors is not supported"))),
+                1..=8 => rule.birth[n as usize] = true,
+                _ => return Err(ParseRuleError(format!("neighbor count {} is out of range", n))),
+            }
+        }
+        for &n in survive {
+            match n {
+                0..=8 => rule.survive[n as usize] = true,
+                _ => return Err(ParseRuleError(format!("neighbor count {} is out of range", n))),
+            }
+        }
+        Ok(rule)
+    }
+
+    /// Whether a dead cell with `neighbors` live neighbors comes alive.
+    pub fn born(&self, neighbors: usize) -> bool {
+        neighbors < 9 && self.birth[neighbors]
+    }
+
+    /// Whether a live cell with `neighbors` live neighbors stays alive. When
+    /// the rule counts the center cell, the cell itself is added to the count.
+    pub fn survives(&self, neighbors: usize) -> bool {
+        let count = if self.counts_center { neighbors + 1 } else { neighbors };
+        count < 9 && self.survive[count]
+    }
+
+    /// Whether a live cell counts itself among its live neighbors.
+    pub fn counts_center(&self) -> bool {
+        self.counts_center
+    }
+
+    /// Set whether a live cell counts itself among its live neighbors, so
+    /// that the rule applies to the live cells of the whole 3x3 block around
+    /// each cell. Since counts only go up to 8, a live cell in a full block
+    /// never survives. Dead cells count the same either way.
+    pub fn set_counts_center(&mut self, counts_center: bool) {
+        self.counts_center = counts_center;
+    }
+}
+
+impl Default for RuleString {
+    fn default() -> RuleString {
+        RuleString::conway()
+    }
+}
+
+impl FromStr for RuleString {
+    type Err = ParseRuleError;
+
+    /// Parse a rule in `B{digits}/S{digits}` notation (case insensitive), or
+    /// in the older `{survive}/{birth}` notation used by some pattern files,
+    /// either followed by `/C` if the rule counts the center cell.
+    fn from_str(s: &str) -> Result<RuleString, ParseRuleError> {
+        let invalid = || ParseRuleError(format!("`{}` is not in B/S notation", s));
+        let (rule, counts_center) = match s.trim().rsplit_once('/') {
+            Some((rule, "C" | "c")) => (rule, true),
+            _ => (s.trim(), false),
+        };
+        let (b, s) = rule.split_once('/').ok_or_else(invalid)?;
+        let (b, s) = match (b.chars().next(), s.chars().next()) {
+            (Some('B' | 'b'), Some('S' | 's')) => (&b[1..], &s[1..]),
+            _ if b.chars().chain(s.chars()).all(|ch| ch.is_ascii_digit()) => (s, b),
+            _ => return Err(invalid()),
+        };
+
+        let digits = |part: &str| -> Result<Vec<u8>, ParseRuleError> {
+            part.chars()
+                .map(|ch| ch.to_digit(10).map(|d| d as u8).ok_or_else(invalid))
+                .collect()
+        };
+        let mut rule = RuleString::new(&digits(b)?, &digits(s)?)?;
+        rule.set_counts_center(counts_center);
+        Ok(rule)
+    }
+}
+
+impl TryFrom<String> for RuleString {
+    type Error = ParseRuleError;
+
+    fn try_from(s: String) -> Result<RuleString, ParseRuleError> {
+        s.parse()
+    }
+}
+
+impl From<RuleString> for String {
+    fn from(rule: RuleString) -> String {
+        rule.to_string()
+    }
+}
+
+impl fmt::Display for RuleString {
+    /// Format the rule in canonical `B{digits}/S{digits}` notation, with
+    /// digits in ascending order and a `/C` suffix if it counts the center
+    /// cell.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = |counts: &[bool; 9]| -> String {
+            (0..9).filter(|&n| counts[n]).map(|n| n.to_string()).collect()
+        };
+        write!(f, "B{}/S{}", digits(&self.birth), digits(&self.survive))?;
+        if self.counts_center {
+            write!(f, "/C")?;
+        }
+        Ok(())
+    }
+}
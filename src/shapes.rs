@@ -0,0 +1,100 @@
+use std::fmt;
+
+use crate::Cell;
+
+/// A geometric shape of live cells drawn between two corner cells, e.g. by
+/// dragging on the board.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Default)]
+pub enum ShapeTool {
+    /// The outline of the rectangle between the corners.
+    #[default]
+    Box,
+    /// Every cell of the rectangle between the corners.
+    FilledBox,
+    /// A straight line from one corner to the other.
+    Line,
+    /// The outline of the ellipse that fits in the rectangle between the
+    /// corners.
+    Ellipse,
+}
+
+impl ShapeTool {
+    /// The tool that comes after this one when cycling through tools.
+    pub fn next(self) -> ShapeTool {
+        match self {
+            ShapeTool::Box => ShapeTool::FilledBox,
+            ShapeTool::FilledBox => ShapeTool::Line,
+            ShapeTool::Line => ShapeTool::Ellipse,
+            ShapeTool::Ellipse => ShapeTool::Box,
+        }
+    }
+
+    /// The cells of the shape between `from` and `to`, each cell once, all
+    /// within the rectangle the two cells are opposite corners of.
+    pub fn cells(self, from: Cell, to: Cell) -> Vec<Cell> {
+        let (top, bottom) = (from.r.min(to.r), from.r.max(to.r));
+        let (left, right) = (from.c.min(to.c), from.c.max(to.c));
+        let rect = (top..=bottom).flat_map(|r| (left..=right).map(move |c| Cell { r, c }));
+        match self {
+            ShapeTool::Box => rect
+                .filter(|cell| cell.r == top || cell.r == bottom || cell.c == left || cell.c == right)
+                .collect(),
+            ShapeTool::FilledBox => rect.collect(),
+            ShapeTool::Line => line(from, to),
+            ShapeTool::Ellipse => {
+                // Radii reach the outer edges of the corner cells, so each
+                // corner's row and column has a cell of the ellipse.
+                let (center_r, center_c) = ((top + bottom) as f64 / 2.0, (left + right) as f64 / 2.0);
+                let (radius_r, radius_c) = ((bottom - top) as f64 / 2.0 + 0.5, (right - left) as f64 / 2.0 + 0.5);
+                let inside = |r: f64, c: f64| {
+                    ((r - center_r) / radius_r).powi(2) + ((c - center_c) / radius_c).powi(2) <= 1.0
+                };
+                rect
+                    .filter(|cell| {
+                        let (r, c) = (cell.r as f64, cell.c as f64);
+                        inside(r, c) && [(-1.0, 0.0), (1.0, 0.0), (0.0, -1.0), (0.0, 1.0)]
+                            .iter()
+                            .any(|(dr, dc)| !inside(r + dr, c + dc))
+                    })
+                    .collect()
+            },
+        }
+    }
+}
+
+impl fmt::Display for ShapeTool {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            ShapeTool::Box => "box",
+            ShapeTool::FilledBox => "filled box",
+            ShapeTool::Line => "line",
+            ShapeTool::Ellipse => "ellipse",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The cells of a straight line between two cells, by Bresenham's algorithm.
+fn line(from: Cell, to: Cell) -> Vec<Cell> {
+    let (mut r, mut c) = (from.r as isize, from.c as isize);
+    let (end_r, end_c) = (to.r as isize, to.c as isize);
+    let (dr, dc) = ((end_r - r).abs(), -(end_c - c).abs());
+    let (step_r, step_c) = ((end_r - r).signum(), (end_c - c).signum());
+    let mut error = dr + dc;
+    let mut cells = Vec::new();
+    loop {
+        cells.push(Cell { r: r as usize, c: c as usize });
+        if (r, c) == (end_r, end_c) {
+            return cells;
+        }
+        let doubled = 2 * error;
+        if doubled >= dc {
+            error += dc;
+            r += step_r;
+        }
+        if doubled <= dr {
+            error += dr;
+            c += step_c;
+        }
+    }
+}
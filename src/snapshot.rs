@@ -0,0 +1,247 @@
+use std::ffi::OsString;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use bincode::Options;
+use serde::{Deserialize, Serialize};
+
+use crate::{Backend, BoundaryMode, Cell, GameOfLife, LiveSet, Neighborhood, PatternMeta, Region, RuleString, StepAlgorithm};
+
+/// Bytes every snapshot starts with.
+const MAGIC: &[u8; 4] = b"GOLS";
+
+/// Snapshot format version written by this build.
+const VERSION: u16 = 4;
+
+/// Most bytes of encoded state a snapshot is read to, so that a corrupt
+/// length cannot make loading allocate without bound.
+const MAX_STATE_LEN: u64 = 1 << 32;
+
+/// Errors produced when loading a snapshot.
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// The snapshot could not be read.
+    Io(io::Error),
+    /// The data does not start like a snapshot.
+    NotASnapshot,
+    /// The snapshot was written in a format version this build cannot read.
+    UnsupportedVersion(u16),
+    /// The snapshot is damaged or inconsistent.
+    Corrupt(String),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SnapshotError::Io(error) => write!(f, "error reading snapshot: {}", error),
+            SnapshotError::NotASnapshot => write!(f, "file is not a snapshot"),
+            SnapshotError::UnsupportedVersion(version) => write!(
+                f, "snapshot version {} is not supported (expected version {})", version, VERSION
+            ),
+            SnapshotError::Corrupt(message) => write!(f, "snapshot is corrupt: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<io::Error> for SnapshotError {
+    fn from(error: io::Error) -> SnapshotError {
+        match error.kind() {
+            io::ErrorKind::UnexpectedEof => SnapshotError::Corrupt(String::from("unexpected end of data")),
+            _ => SnapshotError::Io(error),
+        }
+    }
+}
+
+impl From<bincode::Error> for SnapshotError {
+    fn from(error: bincode::Error) -> SnapshotError {
+        match *error {
+            bincode::ErrorKind::Io(error) => error.into(),
+            error => SnapshotError::Corrupt(error.to_string()),
+        }
+    }
+}
+
+/// The bincode settings the state in a snapshot is encoded with.
+fn encoding() -> impl Options {
+    bincode::DefaultOptions::new().with_fixint_encoding()
+}
+
+/// The full state of a game as written to a snapshot, after the header.
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    rows: usize,
+    cols: usize,
+    rule: RuleString,
+    boundary_rows: BoundaryMode,
+    boundary_cols: BoundaryMode,
+    neighborhood: Neighborhood,
+    generation: u128,
+    live: Vec<(Cell, u32)>,  // live cells in sorted order, each with its age
+    frozen: Vec<Cell>,  // frozen cells in sorted order
+    meta: PatternMeta,
+    active_region: Option<Region>,
+    halt_when_extinct: bool,
+    backend: Backend,
+    step_algorithm: StepAlgorithm,
+    live_set: LiveSet,
+}
+
+/// Saves snapshots of a running game every so many generations, so a long
+/// run can be resumed with `load_snapshot` after a crash.
+///
+/// Each checkpoint is written to a temporary file beside the checkpoint file
+/// and only then moved into place, and the checkpoint it replaces is kept
+/// with `.prev` added to its name. The two most recent checkpoints therefore
+/// survive a crash, even one partway through writing a third.
+pub struct Checkpoints {
+    path: PathBuf,  // file the latest checkpoint is written to
+    every: u128,  // generations between checkpoints
+    last: u128,  // generation of the last checkpoint, or of the game when checkpointing started
+}
+
+impl Checkpoints {
+    /// Checkpoint a game to `path` each time its generation reaches a
+    /// multiple of `every`, which must be positive, starting from
+    /// `generation`, usually that of the game being run.
+    pub fn new(path: &Path, every: u128, generation: u128) -> Checkpoints {
+        assert!(every > 0, "checkpoints must be at least one generation apart");
+        Checkpoints { path: path.to_path_buf(), every, last: generation }
+    }
+
+    /// The file the latest checkpoint is written to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The file the checkpoint before the latest is kept in.
+    pub fn previous_path(&self) -> PathBuf {
+        with_suffix(&self.path, ".prev")
+    }
+
+    /// Save a checkpoint of the game if it has reached or passed a multiple
+    /// of the checkpoint interval since the last checkpoint, returning
+    /// whether one was saved. Games stepped many generations at once are
+    /// checkpointed once, at their new generation. A game that went back to
+    /// an earlier generation, e.g. because it was replaced, starts the count
+    /// over from there.
+    pub fn save_if_due(&mut self, game: &GameOfLife) -> io::Result<bool> {
+        let generation = game.generation();
+        if generation < self.last {
+            self.last = generation;
+        }
+        if generation / self.every <= self.last / self.every {
+            return Ok(false);
+        }
+        self.save(game)?;
+        self.last = generation;
+        Ok(true)
+    }
+
+    /// Save a checkpoint of the game now, keeping the one before it.
+    pub fn save(&self, game: &GameOfLife) -> io::Result<()> {
+        let partial = with_suffix(&self.path, ".tmp");
+        let mut writer = BufWriter::new(File::create(&partial)?);
+        game.save_snapshot(&mut writer)?;
+        writer.into_inner().map_err(|error| error.into_error())?.sync_all()?;
+        match fs::rename(&self.path, self.previous_path()) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => return Err(error),
+            _ => {},
+        }
+        fs::rename(&partial, &self.path)
+    }
+}
+
+/// A path with text added to the end of its file name, e.g. `run.bin.prev`.
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+impl GameOfLife {
+    /// Write a snapshot of the full game state: dimensions, rule, boundary,
+    /// neighborhood, generation, live cells and their ages, frozen cells,
+    /// pattern metadata, active region, and stepping settings, so a loaded
+    /// game runs exactly like the saved one.
+    ///
+    /// The snapshot starts with the bytes `GOLS` and a little endian format
+    /// version, followed by the state encoded with bincode. Cells are written
+    /// in sorted order, so the same state always produces the same bytes.
+    /// The version changes whenever the encoded state does, so a snapshot
+    /// written by another build is rejected rather than misread.
+    pub fn save_snapshot<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        let snapshot = Snapshot {
+            rows: self.rows,
+            cols: self.cols,
+            rule: self.rule(),
+            boundary_rows: self.boundary_rows(),
+            boundary_cols: self.boundary_cols(),
+            neighborhood: self.neighborhood(),
+            generation: self.generation(),
+            live: self.live_cells_sorted().into_iter().map(|cell| (cell, self.age(cell).unwrap_or(1))).collect(),
+            frozen: self.frozen_cells_sorted(),
+            meta: self.meta().clone(),
+            active_region: self.active_region(),
+            halt_when_extinct: self.halt_when_extinct(),
+            backend: self.backend(),
+            step_algorithm: self.step_algorithm(),
+            live_set: self.live_set(),
+        };
+        encoding().serialize_into(writer, &snapshot).map_err(|error| match *error {
+            bincode::ErrorKind::Io(error) => error,
+            error => io::Error::other(error),
+        })
+    }
+
+    /// Restore a game from a snapshot written by `save_snapshot`. Snapshots
+    /// written in an unsupported format version are rejected.
+    pub fn load_snapshot<R: Read>(reader: &mut R) -> Result<GameOfLife, SnapshotError> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic).map_err(|_| SnapshotError::NotASnapshot)?;
+        if &magic != MAGIC {
+            return Err(SnapshotError::NotASnapshot);
+        }
+        let mut version = [0; 2];
+        reader.read_exact(&mut version)?;
+        let version = u16::from_le_bytes(version);
+        if version != VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+
+        let snapshot: Snapshot = encoding().with_limit(MAX_STATE_LEN).deserialize_from(reader)?;
+        let (rows, cols) = (snapshot.rows, snapshot.cols);
+        if rows == 0 || cols == 0 {
+            return Err(SnapshotError::Corrupt(format!("invalid dimensions {}x{}", rows, cols)));
+        }
+        let mut cells = snapshot.live.iter().map(|(cell, _)| cell).chain(&snapshot.frozen);
+        if let Some(cell) = cells.find(|cell| cell.r >= rows || cell.c >= cols) {
+            return Err(SnapshotError::Corrupt(format!("cell ({}, {}) is out of bounds", cell.r, cell.c)));
+        }
+
+        let mut game = GameOfLife::with_live(rows, cols, snapshot.live.iter().map(|(cell, _)| *cell).collect());
+        game.set_live_set(snapshot.live_set);
+        for (cell, age) in snapshot.live {
+            game.set_age(cell, age);
+        }
+        for cell in snapshot.frozen {
+            game.freeze(cell);
+        }
+        game.set_meta(snapshot.meta);
+        game.set_active_region(snapshot.active_region);
+        game.set_halt_when_extinct(snapshot.halt_when_extinct);
+        game.set_backend(snapshot.backend);
+        game.set_step_algorithm(snapshot.step_algorithm);
+        game.set_rule(snapshot.rule);
+        game.set_boundary_rows(snapshot.boundary_rows);
+        game.set_boundary_cols(snapshot.boundary_cols);
+        game.set_neighborhood(snapshot.neighborhood);
+        game.set_generation(snapshot.generation);
+        Ok(game)
+    }
+}
@@ -0,0 +1,70 @@
+use crate::GameOfLife;
+
+/// Statistics gathered over a run of a game, e.g. for a summary when the
+/// simulator exits. Call `observe` after each generation; populations
+/// between observations are not seen.
+#[derive(Clone, Debug)]
+pub struct RunStats {
+    start_generation: u128,  // generation the run started at
+    generation: u128,  // generation last observed
+    population: usize,  // population last observed
+    peak_population: usize,  // largest population observed
+    peak_generation: u128,  // generation the largest population was first observed at
+    extinct_at: Option<u128>,  // generation every cell was first observed dead, if the board is empty
+}
+
+impl RunStats {
+    /// Start gathering statistics at the current generation of a game.
+    pub fn new(game: &GameOfLife) -> RunStats {
+        let population = game.population();
+        RunStats {
+            start_generation: game.generation(),
+            generation: game.generation(),
+            population,
+            peak_population: population,
+            peak_generation: game.generation(),
+            extinct_at: game.is_extinct().then(|| game.generation()),
+        }
+    }
+
+    /// Record the current state of the game.
+    pub fn observe(&mut self, game: &GameOfLife) {
+        self.generation = game.generation();
+        self.population = game.population();
+        if self.population > self.peak_population {
+            self.peak_population = self.population;
+            self.peak_generation = self.generation;
+        }
+        self.extinct_at = match self.population {
+            0 => self.extinct_at.or(Some(self.generation)),
+            _ => None,
+        };
+    }
+
+    /// The generation the run started at.
+    pub fn start_generation(&self) -> u128 {
+        self.start_generation
+    }
+
+    /// The number of generations run.
+    pub fn generations(&self) -> u128 {
+        self.generation - self.start_generation
+    }
+
+    /// The population when last observed.
+    pub fn final_population(&self) -> usize {
+        self.population
+    }
+
+    /// The largest population observed, and the generation it was first
+    /// reached at.
+    pub fn peak(&self) -> (usize, u128) {
+        (self.peak_population, self.peak_generation)
+    }
+
+    /// The generation every cell died at, if the board is empty when last
+    /// observed.
+    pub fn extinct_at(&self) -> Option<u128> {
+        self.extinct_at
+    }
+}
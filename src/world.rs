@@ -0,0 +1,123 @@
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use std::thread;
+
+use crate::{GameError, GameOfLife};
+
+/// The state of one game of a `World`.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub struct GameResult {
+    pub index: usize,  // position of the game in the world, in the order added
+    pub seed: Option<u64>,  // seed the game's starting cells were generated from, if generated
+    pub generation: u128,  // generation the game has reached
+    pub population: usize,  // number of live cells
+}
+
+/// A batch of independent games that are stepped together, e.g. to gather
+/// statistics over hundreds of random soups.
+///
+/// Each soup added with `push_soup` gets its own seed, drawn from the seed
+/// the world was created with, so the same world seed always produces the
+/// same soups, and any one soup can be reproduced alone from the seed in its
+/// result.
+///
+/// Games are stepped in parallel on scoped `std::thread`s, each taking an
+/// equal share of the games, rather than with rayon: the games are
+/// independent and similar in size, so a fixed split needs no work stealing,
+/// and the crate gains no dependency for it.
+pub struct World {
+    games: Vec<(GameOfLife, Option<u64>)>,  // games in the order added, with their seeds
+    rng: StdRng,  // source of the seeds of generated games
+    threads: usize,  // number of threads to step games on
+}
+
+impl World {
+    /// Create an empty world, seeding the games it generates from `seed`.
+    pub fn new(seed: u64) -> World {
+        World { games: Vec::new(), rng: StdRng::seed_from_u64(seed), threads: 1 }
+    }
+
+    /// Add a game, returning its index.
+    pub fn push(&mut self, game: GameOfLife) -> usize {
+        self.games.push((game, None));
+        self.games.len() - 1
+    }
+
+    /// Add a random soup, where each cell is alive with probability
+    /// `density`, generated from a new seed. Returns the index of the game,
+    /// or an error if either dimension is 0.
+    pub fn push_soup(&mut self, rows: usize, cols: usize, density: f64) -> Result<usize, GameError> {
+        let seed = self.rng.gen();
+        self.games.push((World::soup(rows, cols, density, seed)?, Some(seed)));
+        Ok(self.games.len() - 1)
+    }
+
+    /// The soup `push_soup` generates from a seed, or an error if either
+    /// dimension is 0.
+    pub fn soup(rows: usize, cols: usize, density: f64, seed: u64) -> Result<GameOfLife, GameError> {
+        let mut game = GameOfLife::from_grid(&vec![vec![false; cols]; rows])?;
+        game.mutate(density, seed);
+        Ok(game)
+    }
+
+    /// The number of games in the world.
+    pub fn len(&self) -> usize {
+        self.games.len()
+    }
+
+    /// Whether the world has no games.
+    pub fn is_empty(&self) -> bool {
+        self.games.is_empty()
+    }
+
+    /// The game at an index, if any.
+    pub fn game(&self, index: usize) -> Option<&GameOfLife> {
+        self.games.get(index).map(|(game, _)| game)
+    }
+
+    /// The number of threads games are stepped on.
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// Step games on up to `threads` threads at once; 1 steps every game on
+    /// the calling thread. Games never affect each other, so the results are
+    /// the same however many threads are used.
+    pub fn set_threads(&mut self, threads: usize) {
+        self.threads = threads.max(1);
+    }
+
+    /// Step every game `generations` times.
+    pub fn step_all(&mut self, generations: usize) {
+        if self.threads == 1 || self.games.len() < 2 {
+            for (game, _) in self.games.iter_mut() {
+                game.step_n(generations);
+            }
+            return;
+        }
+
+        let chunk_size = self.games.len().div_ceil(self.threads);
+        thread::scope(|scope| {
+            for chunk in self.games.chunks_mut(chunk_size) {
+                scope.spawn(move || {
+                    for (game, _) in chunk.iter_mut() {
+                        game.step_n(generations);
+                    }
+                });
+            }
+        });
+    }
+
+    /// The state of every game, in the order added.
+    pub fn results(&self) -> Vec<GameResult> {
+        self.games.iter()
+            .enumerate()
+            .map(|(index, (game, seed))| GameResult {
+                index,
+                seed: *seed,
+                generation: game.generation(),
+                population: game.population(),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,26 @@
+use conway::AgeBands;
+use sdl2::pixels::Color;
+
+#[test]
+fn cells_take_the_color_of_their_band() {
+    let bands: AgeBands = "1:#ffffff, 3:#78aaff, 10:#2846dc".parse().unwrap();
+    assert_eq!(bands, AgeBands::default());
+
+    let (white, light, deep) = (Color::RGB(255, 255, 255), Color::RGB(120, 170, 255), Color::RGB(40, 70, 220));
+    let colors: Vec<_> = [1, 2, 3, 9, 10, 1000].into_iter().map(|age| bands.color(age)).collect();
+    assert_eq!(colors, vec![white, white, light, light, deep, deep]);
+}
+
+#[test]
+fn bands_must_start_at_1_and_increase() {
+    let white = Color::RGB(255, 255, 255);
+    assert!(AgeBands::new(&[]).is_err());
+    assert!(AgeBands::new(&[(2, white)]).is_err());
+    assert!(AgeBands::new(&[(1, white), (5, white), (5, white)]).is_err());
+    assert!(AgeBands::new(&[(1, white), (5, white), (3, white)]).is_err());
+    assert_eq!(AgeBands::new(&[(1, white)]).unwrap().color(u32::MAX), white);
+
+    for invalid in ["", "1:#fff", "1:ffffff", "1=#ffffff", "x:#ffffff", "1:#ffffff,3:#gggggg", "1:#ffffff,3:#000000,2:#000000"] {
+        assert!(invalid.parse::<AgeBands>().is_err(), "{}", invalid);
+    }
+}
@@ -0,0 +1,152 @@
+use conway::{Behavior, BoundaryMode, Fate, Cell, GameOfLife, Region};
+
+/// A game drawn with `O` for live cells and `.` for dead cells.
+fn drawn(rows: &[&str]) -> GameOfLife {
+    let grid: Vec<Vec<bool>> = rows.iter().map(|row| row.chars().map(|char| char == 'O').collect()).collect();
+    GameOfLife::from_grid(&grid).unwrap()
+}
+
+/// Get the names of the objects in a game, in order of position.
+fn names(game: &GameOfLife) -> Vec<String> {
+    game.objects().iter().map(|object| object.to_string()).collect()
+}
+
+#[test]
+fn names_common_objects() {
+    let game = drawn(&[
+        "....................",
+        ".OO.....OOO....O....",
+        ".OO...........O.O...",
+        "..............O.O...",
+        "...............O....",
+        "....................",
+        "..O......OO.........",
+        "...O.....O..........",
+        ".OOO........O.......",
+        "...........OO.......",
+        "....................",
+        "....................",
+        "..............OO....",
+        "..............O.O...",
+        "...............O....",
+        "....................",
+    ]);
+    assert_eq!(names(&game), vec!["block", "blinker", "beehive", "glider", "beacon", "boat"]);
+}
+
+#[test]
+fn unknown_objects_report_size_and_period() {
+    // A pond, a still life that is not in the lexicon.
+    let game = drawn(&[
+        "......",
+        "..OO..",
+        ".O..O.",
+        ".O..O.",
+        "..OO..",
+        "......",
+    ]);
+    assert_eq!(names(&game), vec!["unknown (8 cells, period 1)"]);
+}
+
+#[test]
+fn objects_group_nearby_cells() {
+    let game = drawn(&[
+        "OO.OO",
+        "OO.OO",
+    ]);
+    assert_eq!(game.components().len(), 1);
+}
+
+#[test]
+fn objects_across_edges_of_torus() {
+    let game = drawn(&[
+        "O....O",
+        "......",
+        "......",
+        "O....O",
+    ]);
+    assert_eq!(names(&game), vec!["block"]);
+}
+
+#[test]
+fn classifies_behavior() {
+    let mut block = drawn(&["....", ".OO.", ".OO.", "...."]);
+    assert_eq!(block.classify(10), Behavior::Stable);
+    let mut blinker = drawn(&[".....", ".....", ".OOO.", ".....", "....."]);
+    assert_eq!(blinker.classify(10), Behavior::Oscillating { period: 2 });
+    let mut lonely = drawn(&["...", ".O.", "..."]);
+    assert_eq!(lonely.classify(10), Behavior::Died);
+
+    let mut gun = GameOfLife::from_file("games/gosper.txt", 200, 200).unwrap();
+    gun.set_boundary(BoundaryMode::Dead);
+    assert_eq!(gun.classify(300), Behavior::Growing);
+
+    // Too few generations for the blinker to repeat.
+    assert_eq!(blinker.classify(1), Behavior::Unsettled);
+}
+
+#[test]
+fn fate_finds_when_the_game_settled() {
+    let mut pre_block = drawn(&["....", ".OO.", ".O..", "...."]);
+    assert_eq!(pre_block.fate(10), Fate { behavior: Behavior::Stable, settled_at: Some(1), population: 4 });
+    let mut blinker = drawn(&[".....", ".....", ".OOO.", ".....", "....."]);
+    assert_eq!(
+        blinker.fate(10),
+        Fate { behavior: Behavior::Oscillating { period: 2 }, settled_at: Some(0), population: 3 },
+    );
+    let mut lonely = drawn(&["...", ".O.", "..."]);
+    assert_eq!(lonely.fate(10), Fate { behavior: Behavior::Died, settled_at: Some(1), population: 0 });
+    assert_eq!(blinker.fate(1).settled_at, None);
+    assert_eq!(pre_block.generation(), 0);
+}
+
+#[test]
+fn classify_restores_game() {
+    let mut game = drawn(&[".O...", "..O..", "OOO..", ".....", "....."]);
+    let before = game.live_cells_sorted();
+    game.classify(50);
+    assert_eq!(game.live_cells_sorted(), before);
+    assert_eq!(game.generation(), 0);
+}
+
+#[test]
+fn finds_spaceships_outside_region() {
+    let game = drawn(&[
+        ".O..........",
+        "..O.........",
+        "OOO.........",
+        "............",
+        "......OOO...",
+    ]);
+    let blinker_region = Region { r: 3, c: 5, rows: 2, cols: 4 };
+    let ships = game.escaped_spaceships(blinker_region, None);
+    assert_eq!(ships.len(), 1);
+    assert_eq!(ships[0].name, Some("glider"));
+    assert_eq!((ships[0].period, ships[0].displacement), (Some(4), Some((1, 1))));
+    assert!(ships[0].is_spaceship());
+
+    // The blinker outside the glider's region repeats in place, so is no spaceship.
+    assert!(game.escaped_spaceships(Region { r: 0, c: 0, rows: 3, cols: 3 }, None).is_empty());
+}
+
+#[test]
+fn finds_first_glider_fired_by_gun() {
+    let mut gun = GameOfLife::from_file("games/gosper.txt", 60, 80).unwrap();
+    gun.set_boundary(BoundaryMode::Dead);
+    let region = gun.bounding_box().unwrap();
+    let lwss = [(0, 1), (0, 4), (1, 0), (2, 0), (2, 4), (3, 0), (3, 1), (3, 2), (3, 3)]
+        .map(|(r, c)| Cell { r, c });
+    // Any phase of a glider, flipped, matches the gun's gliders.
+    let glider = [(0, 0), (0, 1), (0, 2), (1, 0), (2, 1)].map(|(r, c)| Cell { r, c });
+
+    let mut emitted = None;
+    while emitted.is_none() && gun.generation() < 100 {
+        assert!(gun.escaped_spaceships(region, Some(&lwss)).is_empty());
+        if !gun.escaped_spaceships(region, Some(&glider)).is_empty() {
+            emitted = Some(gun.generation());
+        }
+        gun.step();
+    }
+    let generation = emitted.expect("no glider left the gun");
+    assert!(generation > 0 && generation < 60, "first glider emitted at generation {}", generation);
+}
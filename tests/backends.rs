@@ -0,0 +1,114 @@
+mod common;
+
+use conway::{Backend, BoundaryMode, GameOfLife, Neighborhood, Region, RuleString, StepAlgorithm};
+use common::live;
+
+/// A random soup of the given size, the same for the same seed.
+fn soup(rows: usize, cols: usize, seed: u64) -> GameOfLife {
+    let mut game = GameOfLife::from_grid(&vec![vec![false; cols]; rows]).unwrap();
+    game.mutate(0.35, seed);
+    game
+}
+
+/// Step a game with both backends, checking that they agree every generation.
+fn assert_backends_agree(game: GameOfLife, generations: usize) {
+    let mut sparse = game.clone();
+    let mut dense = game;
+    sparse.set_backend(Backend::Sparse);
+    dense.set_backend(Backend::Dense);
+    for generation in 1..=generations {
+        sparse.step();
+        dense.step();
+        assert_eq!(
+            live(&dense), live(&sparse),
+            "{}x{} {:?}/{:?} {} differs at generation {}",
+            sparse.rows, sparse.cols, sparse.boundary_rows(), sparse.boundary_cols(), sparse.rule(), generation
+        );
+    }
+}
+
+#[test]
+fn dense_matches_sparse() {
+    let sizes = [(1, 1), (1, 5), (2, 2), (3, 3), (5, 70), (16, 64), (20, 130)];
+    for (seed, (rows, cols)) in sizes.into_iter().enumerate() {
+        for boundary in [BoundaryMode::Toroidal, BoundaryMode::Dead, BoundaryMode::Reflect] {
+            for rule in ["B3/S23", "B36/S23", "B2/S", "B36/S23/C"] {
+                let mut game = soup(rows, cols, seed as u64);
+                game.set_boundary(boundary);
+                game.set_rule(rule.parse::<RuleString>().unwrap());
+                assert_backends_agree(game, 30);
+            }
+        }
+    }
+}
+
+#[test]
+fn dense_matches_sparse_with_mixed_axes() {
+    let sizes = [(1, 5), (2, 7), (5, 2), (9, 70)];
+    for (seed, (rows, cols)) in sizes.into_iter().enumerate() {
+        for boundary_rows in [BoundaryMode::Toroidal, BoundaryMode::Dead, BoundaryMode::Reflect] {
+            for boundary_cols in [BoundaryMode::Toroidal, BoundaryMode::Dead, BoundaryMode::Reflect] {
+                let mut game = soup(rows, cols, seed as u64);
+                game.set_boundary_rows(boundary_rows);
+                game.set_boundary_cols(boundary_cols);
+                let mut scanned = game.clone();
+                scanned.set_step_algorithm(StepAlgorithm::ScanMemo);
+                for _ in 0..20 {
+                    game.step();
+                    scanned.step();
+                    assert_eq!(live(&game), live(&scanned), "{:?} rows, {:?} cols", boundary_rows, boundary_cols);
+                }
+                let mut game = soup(rows, cols, seed as u64);
+                game.set_boundary_rows(boundary_rows);
+                game.set_boundary_cols(boundary_cols);
+                assert_backends_agree(game, 20);
+            }
+        }
+    }
+}
+
+#[test]
+fn dense_honors_active_region() {
+    let mut game = soup(40, 90, 3);
+    game.set_active_region(Some(Region { r: 10, c: 20, rows: 8, cols: 30 }));
+    assert_backends_agree(game, 30);
+}
+
+#[test]
+fn dense_honors_hex_neighborhood() {
+    for boundary in [BoundaryMode::Toroidal, BoundaryMode::Dead, BoundaryMode::Reflect] {
+        let mut game = soup(20, 130, 4);
+        game.set_boundary(boundary);
+        game.set_neighborhood(Neighborhood::Hex);
+        game.set_rule("B2/S34".parse::<RuleString>().unwrap());
+        assert_backends_agree(game, 30);
+    }
+}
+
+#[test]
+fn step_algorithms_agree() {
+    let sizes = [(1, 1), (2, 5), (3, 3), (12, 17)];
+    for (seed, (rows, cols)) in sizes.into_iter().enumerate() {
+        for boundary in [BoundaryMode::Toroidal, BoundaryMode::Dead, BoundaryMode::Reflect] {
+            for neighborhood in [Neighborhood::Moore, Neighborhood::Hex] {
+                let mut counted = soup(rows, cols, seed as u64);
+                counted.set_boundary(boundary);
+                counted.set_neighborhood(neighborhood);
+                counted.set_rule("B36/S23/C".parse::<RuleString>().unwrap());
+                if rows > 10 {
+                    counted.set_active_region(Some(Region { r: 2, c: 3, rows: 6, cols: 8 }));
+                }
+                let mut scanned = counted.clone();
+                scanned.set_step_algorithm(StepAlgorithm::ScanMemo);
+                for generation in 1..=30 {
+                    counted.step();
+                    scanned.step();
+                    assert_eq!(
+                        live(&counted), live(&scanned),
+                        "{}x{} {:?} {:?} differs at generation {}", rows, cols, boundary, neighborhood, generation
+                    );
+                }
+            }
+        }
+    }
+}
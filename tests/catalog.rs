@@ -0,0 +1,55 @@
+use std::path::Path;
+
+use conway::{Catalog, PatternError, PatternOptions};
+
+/// The file names of a catalog's patterns, in browsing order.
+fn names(catalog: &mut Catalog) -> Vec<String> {
+    (0..catalog.len())
+        .map(|_| {
+            let (path, _) = catalog.next_pattern().unwrap();
+            path.file_name().unwrap().to_string_lossy().into_owned()
+        })
+        .collect()
+}
+
+#[test]
+fn catalog_loads_patterns_and_skips_broken_files() {
+    let mut catalog = Catalog::load(Path::new("tests/fixtures"), 12, 12, &PatternOptions::default()).unwrap();
+    assert_eq!(catalog.position(), 0);
+    let (path, game) = catalog.current().unwrap();
+    assert!(path.ends_with("glider.rle"));
+    assert_eq!((game.rows, game.cols), (12, 12));
+    assert_eq!(game.meta().name.as_deref(), Some("Glider"));
+
+    let mut loaded = names(&mut catalog);
+    loaded.rotate_right(1);
+    assert_eq!(loaded, [
+        "glider.rle", "glider_chars.txt", "glider_chars_symbols.txt", "glider_coords.txt", "glider_no_rule.rle",
+        "ragged_chars.txt",
+    ]);
+    assert_eq!(catalog.position(), 0);
+
+    let skipped: Vec<_> = catalog.skipped().iter()
+        .map(|(path, _)| path.file_name().unwrap().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(skipped, [
+        "chars_missing_decl.txt", "coords_overflow.txt", "rle_invalid_rule.rle", "rle_missing_header.rle",
+        "unknown_format.txt",
+    ]);
+    assert!(matches!(catalog.skipped()[0].1, PatternError::MissingChars));
+}
+
+#[test]
+fn catalog_names_patterns_after_files_and_wraps_backwards() {
+    let mut catalog = Catalog::load(Path::new("tests/fixtures"), 12, 12, &PatternOptions::default()).unwrap();
+    let (path, game) = catalog.previous_pattern().unwrap();
+    assert!(path.ends_with("ragged_chars.txt"));
+    assert_eq!(game.meta().name.as_deref(), Some("ragged_chars"));
+}
+
+#[test]
+fn catalog_skips_patterns_too_large_for_the_grid() {
+    let catalog = Catalog::load(Path::new("tests/fixtures"), 3, 3, &PatternOptions::default()).unwrap();
+    assert!(catalog.skipped().iter()
+        .any(|(path, error)| path.ends_with("ragged_chars.txt") && matches!(error, PatternError::TooLarge { .. })));
+}
@@ -0,0 +1,8 @@
+use conway::GameOfLife;
+
+/// Get the live cells of a game as sorted `(row, col)` pairs.
+pub fn live(game: &GameOfLife) -> Vec<(usize, usize)> {
+    let mut cells: Vec<_> = game.live_cells().iter().map(|cell| (cell.r, cell.c)).collect();
+    cells.sort();
+    cells
+}
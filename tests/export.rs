@@ -0,0 +1,45 @@
+use std::fs;
+
+use conway::{GameOfLife, render_to_rgba, write_png};
+
+const GOLDEN_FRAME: &str = "tests/golden/glider_3x5_cell2.png";
+
+/// A glider in a 3x5 game, with an extra cell in the corner. The game is not
+/// square, so drawing rows as columns changes the image.
+fn glider() -> GameOfLife {
+    GameOfLife::from_grid(&[
+        [false, true, false, false, false],
+        [false, false, true, false, false],
+        [true, true, true, false, true],
+    ]).unwrap()
+}
+
+#[test]
+fn frame_matches_golden_image() {
+    let game = glider();
+    let pixels = render_to_rgba(&game, 2);
+    let mut png = Vec::new();
+    write_png(&mut png, 10, 6, &pixels).unwrap();
+
+    // PNG data is stored uncompressed, so this compares every pixel too.
+    let golden = fs::read(GOLDEN_FRAME).unwrap();
+    assert!(png == golden, "frame differs from {}", GOLDEN_FRAME);
+}
+
+#[test]
+fn frame_draws_cells_as_squares() {
+    let pixels = render_to_rgba(&glider(), 2);
+    assert_eq!(pixels.len(), 10 * 6 * 4);
+    let pixel = |x: usize, y: usize| &pixels[(y * 10 + x) * 4..(y * 10 + x + 1) * 4];
+
+    // Cell (0, 1) covers pixels 2..4 across and 0..2 down.
+    for (x, y) in [(2, 0), (3, 0), (2, 1), (3, 1)] {
+        assert_eq!(pixel(x, y), [255, 255, 255, 255]);
+    }
+    assert_eq!(pixel(1, 0), [0, 0, 0, 255]);
+    assert_eq!(pixel(4, 1), [0, 0, 0, 255]);
+    assert_eq!(pixel(2, 2), [0, 0, 0, 255]);
+    // Cell (2, 4) is in the bottom right corner.
+    assert_eq!(pixel(9, 5), [255, 255, 255, 255]);
+    assert_eq!(pixel(9, 3), [0, 0, 0, 255]);
+}
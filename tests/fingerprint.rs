@@ -0,0 +1,37 @@
+use conway::{chain_hash, Cell, GameOfLife, LiveSet};
+
+fn blinker() -> GameOfLife {
+    let mut game = GameOfLife::from_grid(&[[false; 5]; 5]).unwrap();
+    for c in 1..4 {
+        game.set_alive(Cell { r: 2, c }, true);
+    }
+    game
+}
+
+#[test]
+fn state_hash_is_the_same_everywhere() {
+    // Fixed values catch any change to how boards are hashed, which would
+    // make runs on different versions or platforms look different.
+    assert_eq!(blinker().state_hash(), 0xea39_92d0_d807_6207);
+    assert_eq!(chain_hash(0, blinker().state_hash()), 0x231b_5946_8048_7f4e);
+}
+
+#[test]
+fn state_hash_only_depends_on_the_board() {
+    let mut game = blinker();
+    let mut ordered = blinker();
+    ordered.set_live_set(LiveSet::Ordered);
+    assert_eq!(game.state_hash(), ordered.state_hash());
+
+    let start = game.state_hash();
+    game.step();
+    assert_ne!(game.state_hash(), start);
+    game.step();
+    assert_eq!(game.state_hash(), start);
+}
+
+#[test]
+fn chain_hash_depends_on_the_order_of_generations() {
+    let (a, b) = (blinker().state_hash(), blinker().stepped().state_hash());
+    assert_ne!(chain_hash(chain_hash(0, a), b), chain_hash(chain_hash(0, b), a));
+}
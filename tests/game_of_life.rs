@@ -0,0 +1,720 @@
+mod common;
+
+use conway::{
+    Backend, BoundaryMode, Cell, Edge, GameError, GameOfLife, LiveSet, Neighborhood, Region, RuleString, SetOp, StepOutcome,
+    StressKind,
+};
+use common::live;
+
+/// A game of the given size with a glider in its top left corner, traveling
+/// down and to the right.
+fn glider(rows: usize, cols: usize) -> GameOfLife {
+    let mut grid = vec![vec![false; cols]; rows];
+    for (r, c) in [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)] {
+        grid[r][c] = true;
+    }
+    GameOfLife::from_grid(&grid).unwrap()
+}
+
+#[test]
+fn active_region_matches_full_game() {
+    for boundary in [BoundaryMode::Toroidal, BoundaryMode::Dead] {
+        let mut full = glider(30, 30);
+        let mut restricted = glider(30, 30);
+        full.set_boundary(boundary);
+        restricted.set_boundary(boundary);
+        restricted.set_active_region(Some(Region { r: 0, c: 0, rows: 5, cols: 5 }));
+
+        for _ in 0..200 {
+            full.step();
+            restricted.step();
+            assert_eq!(live(&full), live(&restricted));
+        }
+    }
+}
+
+#[test]
+fn active_region_keeps_outside_static() {
+    let mut game = glider(20, 20);
+    game.set_active_region(Some(Region { r: 10, c: 10, rows: 5, cols: 5 }));
+    game.step();
+    assert_eq!(live(&game), vec![(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+    assert!(!game.active_region().unwrap().contains(Cell { r: 0, c: 0 }));
+}
+
+#[test]
+fn stepped_matches_step() {
+    let original = glider(8, 8);
+    let next = original.stepped();
+    assert_eq!(live(&original), vec![(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+    assert_eq!(original.generation(), 0);
+
+    let mut stepped = original.clone();
+    stepped.step();
+    assert_eq!(live(&next), live(&stepped));
+    assert_eq!(next.generation(), 1);
+}
+
+#[test]
+fn births_and_deaths_match_step() {
+    let pairs = |cells: Vec<Cell>| -> Vec<(usize, usize)> { cells.iter().map(|cell| (cell.r, cell.c)).collect() };
+    for boundary in [BoundaryMode::Toroidal, BoundaryMode::Dead, BoundaryMode::Reflect] {
+        for neighborhood in [Neighborhood::Moore, Neighborhood::Hex] {
+            for backend in [Backend::Sparse, Backend::Dense] {
+                let mut game = GameOfLife::from_grid(&vec![vec![false; 12]; 10]).unwrap();
+                game.mutate(0.4, 7);
+                game.set_boundary(boundary);
+                game.set_neighborhood(neighborhood);
+                game.set_backend(backend);
+                game.set_active_region(Some(Region { r: 0, c: 0, rows: 8, cols: 12 }));
+
+                for _ in 0..20 {
+                    let before = live(&game);
+                    let (births, deaths) = game.births_and_deaths();
+                    assert_eq!(live(&game), before);
+                    game.step();
+                    let after = live(&game);
+                    let expected_births: Vec<_> = after.iter().filter(|cell| !before.contains(cell)).copied().collect();
+                    let expected_deaths: Vec<_> = before.iter().filter(|cell| !after.contains(cell)).copied().collect();
+                    assert_eq!(pairs(births), expected_births, "{:?} {:?} {:?}", boundary, neighborhood, backend);
+                    assert_eq!(pairs(deaths), expected_deaths, "{:?} {:?} {:?}", boundary, neighborhood, backend);
+                }
+            }
+        }
+    }
+
+    let empty = GameOfLife::from_grid(&vec![vec![false; 4]; 4]).unwrap();
+    assert_eq!(empty.births_and_deaths(), (Vec::new(), Vec::new()));
+}
+
+#[test]
+fn ages_count_generations_alive() {
+    // A blinker: the center cell survives while the ends are reborn each step.
+    let mut game = GameOfLife::from_grid(&[
+        [false, false, false],
+        [true, true, true],
+        [false, false, false],
+    ]).unwrap();
+    game.set_boundary(BoundaryMode::Dead);
+    assert_eq!(game.age(Cell { r: 1, c: 1 }), Some(1));
+
+    game.step_n(3);
+    assert_eq!(game.age(Cell { r: 1, c: 1 }), Some(4));
+    assert_eq!(game.age(Cell { r: 0, c: 1 }), Some(1));
+    assert_eq!(game.age(Cell { r: 1, c: 0 }), None);
+}
+
+#[test]
+fn set_age_keeps_counting_from_the_set_age() {
+    let mut game = GameOfLife::from_grid(&[
+        [false, false, false],
+        [true, true, true],
+        [false, false, false],
+    ]).unwrap();
+    game.set_boundary(BoundaryMode::Dead);
+    assert!(game.set_age(Cell { r: 1, c: 1 }, 100));
+    game.step();
+    assert_eq!(game.age(Cell { r: 1, c: 1 }), Some(101));
+
+    // Ages start at 1, and dead cells get no age.
+    assert!(game.set_age(Cell { r: 0, c: 1 }, 50));
+    assert!(game.set_age(Cell { r: 0, c: 1 }, 0));
+    assert_eq!(game.age(Cell { r: 0, c: 1 }), Some(1));
+    assert!(!game.set_age(Cell { r: 1, c: 0 }, 5));
+    assert_eq!(game.age(Cell { r: 1, c: 0 }), None);
+}
+
+/// A mask of the cells within `radius` of the center of a game.
+fn circle(rows: usize, cols: usize, radius: f64) -> Vec<Vec<bool>> {
+    let (center_r, center_c) = ((rows - 1) as f64 / 2.0, (cols - 1) as f64 / 2.0);
+    (0..rows)
+        .map(|r| (0..cols).map(|c| (r as f64 - center_r).hypot(c as f64 - center_c) <= radius).collect())
+        .collect()
+}
+
+#[test]
+fn random_masked_seeds_only_masked_cells() {
+    let mask = circle(20, 20, 6.0);
+    let mut game = GameOfLife::from_grid(&vec![vec![false; 20]; 20]).unwrap();
+    game.random_masked(&mask, 0.5, 7).unwrap();
+    assert!(game.population() > 0);
+    assert!(game.live_cells().iter().all(|cell| mask[cell.r][cell.c]));
+
+    let mut again = GameOfLife::from_grid(&vec![vec![false; 20]; 20]).unwrap();
+    again.random_masked(&mask, 0.5, 7).unwrap();
+    assert_eq!(live(&game), live(&again));
+}
+
+#[test]
+fn random_count_places_exact_population() {
+    let game = GameOfLife::random_count(20, 30, 150, 42).unwrap();
+    assert_eq!(game.population(), 150);
+    assert!(game.live_cells().iter().all(|cell| cell.r < 20 && cell.c < 30));
+    assert_eq!(live(&game), live(&GameOfLife::random_count(20, 30, 150, 42).unwrap()));
+    assert_ne!(live(&game), live(&GameOfLife::random_count(20, 30, 150, 43).unwrap()));
+
+    assert_eq!(GameOfLife::random_count(4, 5, 20, 0).unwrap().population(), 20);
+    assert_eq!(GameOfLife::random_count(4, 5, 0, 0).unwrap().population(), 0);
+    assert_eq!(
+        GameOfLife::random_count(4, 5, 21, 0).err(),
+        Some(GameError::TooManyCells { count: 21, cells: 20 })
+    );
+    assert_eq!(GameOfLife::random_count(0, 5, 0, 0).err(), Some(GameError::EmptyGrid));
+}
+
+#[test]
+fn random_masked_rejects_mismatched_mask() {
+    let mut game = GameOfLife::from_grid(&vec![vec![false; 4]; 3]).unwrap();
+    assert_eq!(
+        game.random_masked(&circle(4, 4, 2.0), 0.5, 0),
+        Err(GameError::MaskSize { expected: (3, 4), found: (4, 4) })
+    );
+    let ragged = vec![vec![true; 4], vec![true; 3], vec![true; 4]];
+    assert_eq!(
+        game.random_masked(&ragged, 0.5, 0),
+        Err(GameError::RaggedGrid { row: 1, expected: 4, found: 3 })
+    );
+}
+
+#[test]
+fn stepping_extinct_game() {
+    let mut game = GameOfLife::from_grid(&[[true, false], [false, false]]).unwrap();
+    assert_eq!(game.step(), StepOutcome::Stepped);
+    assert!(game.is_extinct());
+    assert_eq!(game.step(), StepOutcome::Extinct);
+    assert_eq!(game.generation(), 2);
+
+    game.set_halt_when_extinct(true);
+    assert_eq!(game.step(), StepOutcome::Extinct);
+    game.step_n(10);
+    assert_eq!(game.stepped().generation(), 2);
+    assert_eq!(game.generation(), 2);
+}
+
+#[test]
+fn bitvec_bit_order() {
+    // Cells 1 and 64 of a 5x13 grid: (0, 1) and (4, 12).
+    let mut grid = vec![vec![false; 13]; 5];
+    grid[0][1] = true;
+    grid[4][12] = true;
+    let game = GameOfLife::from_grid(&grid).unwrap();
+    assert_eq!(game.to_bitvec(), vec![0b10, 0b1]);
+}
+
+#[test]
+fn bitvec_round_trip() {
+    for (rows, cols) in [(1, 1), (8, 8), (7, 13), (30, 30)] {
+        let game = glider(rows.max(3), cols.max(3));
+        let words = game.to_bitvec();
+        let restored = GameOfLife::from_bitvec(game.rows, game.cols, &words).unwrap();
+        assert_eq!(live(&restored), live(&game));
+    }
+    assert!(matches!(
+        GameOfLife::from_bitvec(8, 9, &[0]),
+        Err(GameError::BitvecLength { expected: 2, found: 1 })
+    ));
+}
+
+#[test]
+fn stress_boards_are_seeded() {
+    for kind in [StressKind::Blinkers, StressKind::Soup] {
+        let game = GameOfLife::stress(40, 50, kind, 3);
+        assert_eq!(live(&game), live(&GameOfLife::stress(40, 50, kind, 3)));
+        assert_ne!(live(&game), live(&GameOfLife::stress(40, 50, kind, 4)));
+    }
+}
+
+#[test]
+fn stress_blinkers_never_settle() {
+    // 10 by 12 whole blinkers, even across the edges of the torus.
+    let mut game = GameOfLife::stress(43, 50, StressKind::Blinkers, 0);
+    assert_eq!(game.population(), 10 * 12 * 3);
+    let start = live(&game);
+    for _ in 0..10 {
+        let before = live(&game);
+        game.step();
+        assert_ne!(live(&game), before);
+        assert_eq!(game.population(), 10 * 12 * 3);
+    }
+    assert_eq!(live(&game), start);
+}
+
+#[test]
+fn center_cell_counting() {
+    // Each cell of a block has 3 live neighbors, or 4 counting itself.
+    let block = || {
+        let mut game = GameOfLife::from_grid(&[
+            [false, false, false, false],
+            [false, true, true, false],
+            [false, true, true, false],
+            [false, false, false, false],
+        ]).unwrap();
+        game.set_boundary(BoundaryMode::Dead);
+        game
+    };
+
+    let mut standard = block();
+    assert!(!standard.rule().counts_center());
+    standard.step();
+    assert_eq!(live(&standard), live(&block()));
+
+    let mut counting = block();
+    let rule = "B3/S23/C".parse::<RuleString>().unwrap();
+    assert!(rule.counts_center());
+    assert_eq!(rule.to_string(), "B3/S23/C");
+    counting.set_rule(rule);
+    counting.step();
+    assert!(counting.is_extinct());
+}
+
+#[test]
+fn index_reads_cells() {
+    let game = glider(4, 5);
+    assert!(game[(0, 1)]);
+    assert!(!game[(0, 0)]);
+    assert!(game[Cell { r: 2, c: 2 }]);
+    assert!(!game[Cell { r: 3, c: 4 }]);
+}
+
+#[test]
+#[should_panic(expected = "cell (4, 0) is outside the 4x5 game")]
+fn index_panics_outside_game() {
+    let _ = glider(4, 5)[(4, 0)];
+}
+
+#[test]
+fn set_alive_edits_cells() {
+    let mut game = glider(5, 5);
+    game.step();
+    game.set_alive(Cell { r: 4, c: 4 }, true);
+    game.set_alive(Cell { r: 3, c: 2 }, true);
+    game.set_alive(Cell { r: 1, c: 0 }, false);
+    assert_eq!(live(&game), vec![(1, 2), (2, 1), (2, 2), (3, 1), (3, 2), (4, 4)]);
+    assert_eq!(game.age(Cell { r: 4, c: 4 }), Some(1));
+    assert_eq!(game.age(Cell { r: 3, c: 2 }), Some(1));
+    assert_eq!(game.age(Cell { r: 1, c: 0 }), None);
+}
+
+#[test]
+fn shift_scrolls_world() {
+    // One step in, the glider is (1, 0) and (3, 1) newborn, and (1, 2),
+    // (2, 1), and (2, 2) surviving.
+    let mut torus = glider(6, 7);
+    torus.step();
+    let mut bounded = torus.clone();
+    bounded.set_boundary(BoundaryMode::Dead);
+
+    torus.shift(-2, 5);
+    assert_eq!(live(&torus), vec![(0, 0), (0, 6), (1, 6), (5, 0), (5, 5)]);
+    assert_eq!(torus.age(Cell { r: 5, c: 0 }), Some(2));
+    assert_eq!(torus.age(Cell { r: 5, c: 5 }), Some(1));
+
+    bounded.shift(-2, 5);
+    assert_eq!(live(&bounded), vec![(0, 6), (1, 6)]);
+}
+
+#[test]
+fn debug_summarizes_game_and_display_is_grid_only() {
+    let mut game = glider(5, 5);
+    game.step();
+    assert_eq!(
+        format!("{:?}", game),
+        "GameOfLife { rows: 5, cols: 5, population: 5, rule: B3/S23, generation: 1 }"
+    );
+
+    let empty = GameOfLife::from_grid(&[[false, false], [false, false]]).unwrap();
+    assert_eq!(empty.to_string(), "  \n  ");
+    assert_eq!(format!("{:?}", empty), "GameOfLife { rows: 2, cols: 2, population: 0, rule: B3/S23, generation: 0 }");
+}
+
+/// Place a pattern, given as `(row, col)` cells, with its top left corner at
+/// `(r, c)` in an empty game.
+fn place(rows: usize, cols: usize, r: usize, c: usize, cells: &[(usize, usize)]) -> GameOfLife {
+    let mut game = GameOfLife::from_grid(&vec![vec![false; cols]; rows]).unwrap();
+    for (dr, dc) in cells {
+        game.set_alive(Cell { r: r + dr, c: c + dc }, true);
+    }
+    game
+}
+
+#[test]
+fn torus_matches_bounded_grid_away_from_edges() {
+    let patterns: [(&str, &[(usize, usize)]); 4] = [
+        ("glider", &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]),
+        ("lwss", &[(0, 1), (0, 4), (1, 0), (2, 0), (2, 4), (3, 0), (3, 1), (3, 2), (3, 3)]),
+        ("r-pentomino", &[(0, 1), (0, 2), (1, 0), (1, 1), (2, 1)]),
+        ("toad", &[(0, 1), (0, 2), (0, 3), (1, 0), (1, 1), (1, 2)]),
+    ];
+    // The pattern starts in the middle of both grids, at (9, 9) on the torus
+    // and (49, 49) on the bounded grid.
+    let offset = 40;
+    for backend in [Backend::Sparse, Backend::Dense] {
+        for (name, cells) in patterns {
+            let mut torus = place(20, 20, 9, 9, cells);
+            let mut bounded = place(100, 100, 9 + offset, 9 + offset, cells);
+            torus.set_backend(backend);
+            bounded.set_backend(backend);
+            bounded.set_boundary(BoundaryMode::Dead);
+
+            // Stop once the pattern comes within a cell of the torus edges,
+            // where its neighbors start to wrap.
+            let away_from_edges = |game: &GameOfLife| {
+                live(game).iter().all(|(r, c)| (1..19).contains(r) && (1..19).contains(c))
+            };
+            let mut generations = 0;
+            while generations < 100 && away_from_edges(&torus) {
+                let shifted: Vec<_> = live(&torus).iter().map(|(r, c)| (r + offset, c + offset)).collect();
+                assert_eq!(shifted, live(&bounded), "{} ({:?}) differs at generation {}", name, backend, generations);
+                torus.step();
+                bounded.step();
+                generations += 1;
+            }
+            assert!(generations >= 5, "{} reached the torus edges after {} generations", name, generations);
+        }
+    }
+}
+
+#[test]
+fn bounding_box_holds_live_cells() {
+    let mut game = glider(8, 10);
+    assert_eq!(game.bounding_box(), Some(Region { r: 0, c: 0, rows: 3, cols: 3 }));
+    game.set_alive(Cell { r: 6, c: 8 }, true);
+    assert_eq!(game.bounding_box(), Some(Region { r: 0, c: 0, rows: 7, cols: 9 }));
+
+    let empty = GameOfLife::from_grid(&[[false; 4]; 4]).unwrap();
+    assert_eq!(empty.bounding_box(), None);
+}
+
+#[test]
+fn ordered_live_set_steps_the_same_in_order() {
+    let mut hashed = GameOfLife::from_file("games/gosper.txt", 60, 60).unwrap();
+    let mut ordered = hashed.clone();
+    ordered.set_live_set(LiveSet::Ordered);
+    assert_eq!(ordered.live_set(), LiveSet::Ordered);
+    for backend in [Backend::Sparse, Backend::Dense] {
+        hashed.set_backend(backend);
+        ordered.set_backend(backend);
+        for _ in 0..40 {
+            hashed.step();
+            ordered.step();
+        }
+        assert_eq!(ordered.live_cells(), hashed.live_cells_sorted());
+    }
+    assert_eq!(ordered.stepped().live_set(), LiveSet::Ordered);
+
+    let region = Region { r: 20, c: 10, rows: 10, cols: 20 };
+    let mut inside = hashed.live_cells_in(region);
+    inside.sort();
+    assert!(!inside.is_empty());
+    assert_eq!(ordered.live_cells_in(region), inside);
+    assert!(inside.iter().all(|cell| region.contains(*cell)));
+}
+
+#[test]
+fn cells_are_ordered_by_row_then_column() {
+    assert!(Cell { r: 0, c: 9 } < Cell { r: 1, c: 0 });
+    assert!(Cell { r: 1, c: 2 } < Cell { r: 1, c: 3 });
+}
+
+#[test]
+fn edge_margin_counts_dead_cells_to_the_nearest_edge() {
+    let mut game = GameOfLife::from_grid(&[[false; 10]; 8]).unwrap();
+    assert_eq!(game.edge_margin(), None);
+    game.set_alive(Cell { r: 3, c: 4 }, true);
+    game.set_alive(Cell { r: 5, c: 6 }, true);
+    assert_eq!(game.edge_margin(), Some(2));
+    game.set_alive(Cell { r: 7, c: 5 }, true);
+    assert_eq!(game.edge_margin(), Some(0));
+}
+
+#[test]
+fn cylinder_wraps_spaceships_around_one_axis() {
+    let lwss = [(0, 1), (0, 4), (1, 0), (2, 0), (2, 4), (3, 0), (3, 1), (3, 2), (3, 3)];
+    // The spaceship travels along the rows at half a cell per generation, so
+    // on a cylinder 12 columns around it comes back after 24 generations.
+    let mut cylinder = place(8, 12, 2, 4, &lwss);
+    cylinder.set_boundary_rows(BoundaryMode::Dead);
+    cylinder.set_boundary_cols(BoundaryMode::Toroidal);
+    assert_eq!(cylinder.boundary(), None);
+    let start = live(&cylinder);
+    cylinder.step_n(24);
+    assert_eq!(live(&cylinder), start);
+
+    // Turned the other way, the spaceship runs into the wall and breaks up.
+    let mut tube = place(8, 12, 2, 4, &lwss);
+    tube.set_boundary_rows(BoundaryMode::Toroidal);
+    tube.set_boundary_cols(BoundaryMode::Dead);
+    tube.step_n(24);
+    assert_ne!(live(&tube), start);
+}
+
+#[test]
+fn recentered_joins_patterns_across_the_edges() {
+    // The glider straddles the corner, so its box spans the board.
+    let mut game = glider(10, 12);
+    game.shift(-2, -2);
+    assert_eq!(game.bounding_box(), Some(Region { r: 0, c: 0, rows: 10, cols: 12 }));
+    let (r, c) = game.centroid().unwrap();
+    assert!(r > 8.5 && c > 10.5, "centroid ({}, {}) is not by the corner", r, c);
+
+    let recentered = game.recentered();
+    assert_eq!(recentered.bounding_box(), Some(Region { r: 4, c: 5, rows: 3, cols: 3 }));
+    assert_eq!(recentered.population(), 5);
+    assert_eq!(live(&game), [(0, 0), (0, 10), (0, 11), (8, 11), (9, 0)]);
+
+    // Bounded boards would lose cells, so are left as they are.
+    let mut bounded = glider(10, 12);
+    bounded.set_boundary(BoundaryMode::Dead);
+    assert_eq!(live(&bounded.recentered()), live(&bounded));
+    assert_eq!(GameOfLife::from_grid(&[[false; 4]; 4]).unwrap().centroid(), None);
+}
+
+#[test]
+fn border_walls_box_in_a_torus() {
+    // A torus walled in by a ring of frozen cells one cell thick runs like the
+    // board inside the walls with dead edges.
+    let soup = GameOfLife::stress(16, 16, StressKind::Soup, 7);
+    for backend in [Backend::Sparse, Backend::Dense] {
+        let mut inside = soup.clone();
+        inside.set_boundary(BoundaryMode::Dead);
+        inside.set_backend(backend);
+        let mut walled = place(18, 18, 1, 1, &live(&soup));
+        walled.set_backend(backend);
+        walled.add_border_walls(1);
+        assert_eq!(walled.frozen_count(), 4 * 17);
+        for generation in 0..60 {
+            let shifted: Vec<_> = live(&inside).iter().map(|(r, c)| (r + 1, c + 1)).collect();
+            assert_eq!(live(&walled), shifted, "{:?} differs at generation {}", backend, generation);
+            inside.step();
+            walled.step();
+        }
+    }
+}
+
+#[test]
+fn frozen_cells_are_never_born() {
+    // The middle of a blinker would bring its ends back to life, but the
+    // frozen end stays dead and the blinker dies out.
+    let mut game = place(5, 5, 2, 1, &[(0, 0), (0, 1), (0, 2)]);
+    game.freeze(Cell { r: 1, c: 2 });
+    game.step();
+    assert_eq!(live(&game), [(2, 2), (3, 2)]);
+    game.set_alive(Cell { r: 1, c: 2 }, true);
+    assert!(!game.is_alive(Cell { r: 1, c: 2 }));
+
+    // Freezing kills a live cell, and thawing lets it be born again.
+    game.freeze(Cell { r: 2, c: 2 });
+    assert!(game.is_frozen(Cell { r: 2, c: 2 }));
+    assert_eq!(live(&game), [(3, 2)]);
+    game.thaw(Cell { r: 1, c: 2 });
+    game.set_alive(Cell { r: 1, c: 2 }, true);
+    assert!(game.is_alive(Cell { r: 1, c: 2 }));
+}
+
+/// Whether any live cell of a game is on its frozen border walls.
+fn alive_in_walls(game: &GameOfLife) -> bool {
+    game.live_cells().iter().any(|cell| game.is_frozen(*cell))
+}
+
+#[test]
+fn mutating_never_flips_frozen_cells() {
+    let mut game = GameOfLife::from_grid(&vec![vec![false; 8]; 8]).unwrap();
+    game.add_border_walls(2);
+    game.mutate(1.0, 3);
+    assert!(!alive_in_walls(&game));
+    assert_eq!(game.population(), 4 * 4);
+}
+
+#[test]
+fn mutation_rates_are_clamped() {
+    let mut full = GameOfLife::from_grid(&vec![vec![false; 8]; 8]).unwrap();
+    full.mutate(2.0, 3);
+    assert_eq!(full.population(), 8 * 8);
+    full.mutate(1.0, 3);
+    assert!(full.is_extinct());
+
+    let mut empty = GameOfLife::from_grid(&vec![vec![false; 8]; 8]).unwrap();
+    empty.mutate(-1.0, 3);
+    empty.mutate(f64::NAN, 3);
+    assert!(empty.is_extinct());
+    empty.random_masked(&vec![vec![true; 8]; 8], 1.5, 3).unwrap();
+    assert_eq!(empty.population(), 8 * 8);
+}
+
+#[test]
+fn masked_soups_leave_frozen_cells_dead() {
+    let mut game = GameOfLife::from_grid(&vec![vec![false; 8]; 8]).unwrap();
+    game.add_border_walls(1);
+    game.random_masked(&vec![vec![true; 8]; 8], 1.0, 3).unwrap();
+    assert!(!alive_in_walls(&game));
+    assert_eq!(game.population(), 6 * 6);
+}
+
+#[test]
+fn shifting_loses_cells_moved_onto_frozen_cells() {
+    let mut game = place(6, 6, 2, 2, &[(0, 0), (0, 1), (1, 0), (1, 1)]);
+    game.add_border_walls(1);
+    game.shift(1, 0);
+    assert_eq!(live(&game), [(3, 2), (3, 3), (4, 2), (4, 3)]);
+    game.shift(1, 0);
+    assert!(!alive_in_walls(&game));
+    assert_eq!(live(&game), [(4, 2), (4, 3)]);
+}
+
+#[test]
+fn from_text_writes_centered_characters() {
+    let game = GameOfLife::from_text("hi", 11, 15).unwrap();
+    // "HI" is 11 columns wide and 7 rows tall, so it starts at (2, 2); the
+    // last column of the I is dead.
+    assert_eq!(game.bounding_box(), Some(Region { r: 2, c: 2, rows: 7, cols: 10 }));
+    assert_eq!(game.population(), 17 + 11);
+    for r in 2..9 {
+        assert!(game.is_alive(Cell { r, c: 2 }) && game.is_alive(Cell { r, c: 6 }));
+        assert!(game.is_alive(Cell { r, c: 10 }));
+    }
+    assert_eq!(GameOfLife::from_text(" ", 7, 5).unwrap().population(), 0);
+
+    assert_eq!(GameOfLife::from_text("H!", 20, 20).err(), Some(GameError::UnsupportedChar('!')));
+    assert_eq!(
+        GameOfLife::from_text("HELLO", 7, 20).err(),
+        Some(GameError::TextTooLarge { size: (7, 29), game: (7, 20) })
+    );
+}
+
+#[test]
+fn combine_merges_live_cells() {
+    let a = place(4, 4, 0, 0, &[(0, 0), (0, 1), (1, 1)]);
+    let b = place(4, 4, 0, 0, &[(1, 1), (2, 2)]);
+    assert_eq!(live(&a.combine(&b, SetOp::Union).unwrap()), [(0, 0), (0, 1), (1, 1), (2, 2)]);
+    assert_eq!(live(&a.combine(&b, SetOp::Intersection).unwrap()), [(1, 1)]);
+    assert_eq!(live(&a.combine(&b, SetOp::Difference).unwrap()), [(0, 0), (0, 1)]);
+    assert_eq!(live(&a.combine(&b, SetOp::Xor).unwrap()), [(0, 0), (0, 1), (2, 2)]);
+
+    assert_eq!(
+        a.combine(&place(4, 5, 0, 0, &[]), SetOp::Union).err(),
+        Some(GameError::SizeMismatch { expected: (4, 4), found: (4, 5) })
+    );
+}
+
+#[test]
+fn diff_lists_cells_alive_on_one_side() {
+    let mut blinker = place(5, 5, 2, 1, &[(0, 0), (0, 1), (0, 2)]);
+    let start = blinker.clone();
+    blinker.step();
+    let delta = start.diff(&blinker).unwrap();
+    assert_eq!(delta.born, [Cell { r: 1, c: 2 }, Cell { r: 3, c: 2 }]);
+    assert_eq!(delta.died, [Cell { r: 2, c: 1 }, Cell { r: 2, c: 3 }]);
+    blinker.step();
+    assert!(start.diff(&blinker).unwrap().born.is_empty());
+    assert!(start.diff(&place(5, 6, 0, 0, &[])).is_err());
+}
+
+#[test]
+fn wrap_demo_gliders_cross_the_chosen_edge() {
+    // Whether any live cell is in the rows or columns along each edge.
+    let along = |game: &GameOfLife, edge: Edge| live(game).iter().any(|&(r, c)| match edge {
+        Edge::Top => r == 0,
+        Edge::Bottom => r == game.rows - 1,
+        Edge::Left => c == 0,
+        Edge::Right => c == game.cols - 1,
+    });
+    let opposite = |edge: Edge| match edge {
+        Edge::Top => Edge::Bottom,
+        Edge::Bottom => Edge::Top,
+        Edge::Left => Edge::Right,
+        Edge::Right => Edge::Left,
+    };
+    for edge in [Edge::Top, Edge::Bottom, Edge::Left, Edge::Right] {
+        let mut torus = GameOfLife::wrap_demo(12, 16, edge).unwrap();
+        assert_eq!(torus.population(), 5);
+        assert!(!along(&torus, edge) && !along(&torus, opposite(edge)), "{:?}", edge);
+        let mut bounded = torus.clone();
+        bounded.set_boundary(BoundaryMode::Dead);
+
+        torus.step_n(8);
+        assert_eq!(torus.population(), 5, "{:?}", edge);
+        assert!(along(&torus, opposite(edge)), "{:?} glider did not wrap", edge);
+        bounded.step_n(8);
+        assert!(!along(&bounded, opposite(edge)), "{:?}", edge);
+    }
+    assert_eq!(
+        GameOfLife::wrap_demo(4, 10, Edge::Top).err(),
+        Some(GameError::TooSmall { min: (5, 5), found: (4, 10) })
+    );
+}
+
+/// Get cropped cells as `(row, col)` pairs, in the order they were cropped.
+fn pairs(cells: Vec<Cell>) -> Vec<(usize, usize)> {
+    cells.iter().map(|cell| (cell.r, cell.c)).collect()
+}
+
+#[test]
+fn crop_copies_cells_relative_to_the_origin() {
+    let glider = [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)];
+    let game = place(10, 10, 4, 5, &glider);
+    assert_eq!(pairs(game.crop(Cell { r: 4, c: 5 }, 3, 3)), glider);
+    assert_eq!(pairs(game.crop(Cell { r: 5, c: 5 }, 2, 2)), [(1, 0), (1, 1)]);
+    assert_eq!(pairs(game.crop_between(Cell { r: 4, c: 5 }, Cell { r: 6, c: 7 })), glider);
+}
+
+#[test]
+fn crop_past_an_edge_wraps_only_on_a_torus() {
+    let mut game = place(10, 10, 0, 0, &[(0, 0), (9, 9)]);
+    game.set_boundary(BoundaryMode::Toroidal);
+    assert_eq!(pairs(game.crop(Cell { r: 9, c: 9 }, 2, 2)), [(0, 0), (1, 1)]);
+    game.set_boundary(BoundaryMode::Dead);
+    assert_eq!(pairs(game.crop(Cell { r: 9, c: 9 }, 2, 2)), [(0, 0)]);
+    assert_eq!(pairs(game.crop(Cell { r: 12, c: 0 }, 3, 3)), []);
+}
+
+#[test]
+fn crop_between_reversed_corners_crosses_the_seam_only_on_a_torus() {
+    let mut game = place(10, 10, 0, 0, &[(0, 0), (5, 5), (9, 9)]);
+    game.set_boundary(BoundaryMode::Toroidal);
+    assert_eq!(pairs(game.crop_between(Cell { r: 9, c: 9 }, Cell { r: 0, c: 0 })), [(0, 0), (1, 1)]);
+    assert_eq!(pairs(game.crop_between(Cell { r: 0, c: 0 }, Cell { r: 9, c: 9 })), [(0, 0), (5, 5), (9, 9)]);
+    game.set_boundary(BoundaryMode::Dead);
+    assert_eq!(pairs(game.crop_between(Cell { r: 9, c: 9 }, Cell { r: 0, c: 0 })), [(0, 0), (5, 5), (9, 9)]);
+    assert_eq!(pairs(game.crop_between(Cell { r: 6, c: 6 }, Cell { r: 4, c: 4 })), [(1, 1)]);
+}
+
+#[test]
+fn crop_larger_than_a_torus_copies_each_cell_once() {
+    let mut game = place(4, 4, 0, 0, &[(0, 0), (3, 3)]);
+    game.set_boundary(BoundaryMode::Toroidal);
+    assert_eq!(pairs(game.crop(Cell { r: 3, c: 3 }, 6, 6)), [(0, 0), (1, 1)]);
+    game.set_boundary(BoundaryMode::Dead);
+    assert_eq!(pairs(game.crop(Cell { r: 0, c: 0 }, 6, 6)), [(0, 0), (3, 3)]);
+}
+
+#[test]
+fn pattern_regions_wrap_around_the_seam() {
+    // The glider straddles the corner, so its region wraps past both edges.
+    let mut game = glider(10, 12);
+    game.shift(-2, -2);
+    let region = Region { r: 8, c: 10, rows: 3, cols: 3 };
+    assert_eq!(game.pattern_regions(), [region]);
+
+    let cropped = game.cropped(region);
+    assert_eq!((cropped.rows, cropped.cols), (3, 3));
+    assert_eq!(live(&cropped), live(&glider(3, 3)));
+
+    // Bounded boards never wrap, so the region is the bounding box.
+    game.set_boundary(BoundaryMode::Dead);
+    assert_eq!(game.pattern_regions(), [Region { r: 0, c: 0, rows: 10, cols: 12 }]);
+    assert_eq!(GameOfLife::from_grid(&[[false; 4]; 4]).unwrap().pattern_regions(), []);
+}
+
+#[test]
+fn pattern_regions_list_each_cut_when_gaps_tie() {
+    let mut game = place(10, 10, 0, 0, &[(0, 0), (5, 5)]);
+    game.set_boundary(BoundaryMode::Toroidal);
+    assert_eq!(game.pattern_regions(), [
+        Region { r: 0, c: 0, rows: 6, cols: 6 },
+        Region { r: 0, c: 5, rows: 6, cols: 6 },
+        Region { r: 5, c: 0, rows: 6, cols: 6 },
+        Region { r: 5, c: 5, rows: 6, cols: 6 },
+    ]);
+    game.set_boundary(BoundaryMode::Dead);
+    assert_eq!(game.pattern_regions(), [Region { r: 0, c: 0, rows: 6, cols: 6 }]);
+}
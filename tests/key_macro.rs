@@ -0,0 +1,66 @@
+use sdl2::keyboard::{Keycode, Mod};
+
+use conway::{KeyPress, MacroError, MacroPlayer, MacroRecorder};
+
+/// Play every key press of a macro, as if the game had reached each one's
+/// generation.
+fn presses(player: &mut MacroPlayer) -> Vec<KeyPress> {
+    let mut presses = Vec::new();
+    while let Some(press) = player.next_due(u128::MAX) {
+        presses.push(press);
+    }
+    presses
+}
+
+#[test]
+fn macro_round_trips() {
+    let presses_in = vec![
+        KeyPress::new(0, Keycode::Space, Mod::NOMOD).unwrap(),
+        KeyPress::new(57, Keycode::Num2, Mod::NOMOD).unwrap(),
+        KeyPress::new(57, Keycode::Left, Mod::RSHIFTMOD).unwrap(),
+        KeyPress::new(60, Keycode::Z, Mod::LCTRLMOD | Mod::LSHIFTMOD).unwrap(),
+    ];
+    let mut recorder = MacroRecorder::new(Vec::new()).unwrap();
+    for press in presses_in.iter() {
+        recorder.record(press).unwrap();
+    }
+    let bytes = recorder.into_inner().unwrap();
+    assert_eq!(
+        String::from_utf8(bytes.clone()).unwrap(),
+        "# conway key macro\n0 Space\n57 2\n57 Shift+Left\n60 Ctrl+Shift+Z\n"
+    );
+
+    let mut player = MacroPlayer::new(bytes.as_slice()).unwrap();
+    assert_eq!(presses(&mut player), presses_in);
+    assert!(player.finished());
+}
+
+#[test]
+fn keys_play_once_their_generation_is_reached() {
+    let text = "# conway key macro\n\n# start playing\n0 Space\n10 Space\n10 N\n";
+    let mut player = MacroPlayer::new(text.as_bytes()).unwrap();
+    assert_eq!(player.next_due(0).map(|press| press.keycode), Some(Keycode::Space));
+    assert_eq!(player.next_due(9), None);
+    assert_eq!(player.next_due(12).map(|press| press.keycode), Some(Keycode::Space));
+    assert_eq!(player.next_due(12).map(|press| press.keycode), Some(Keycode::N));
+    assert_eq!(player.next_due(12), None);
+    assert!(player.finished());
+}
+
+#[test]
+fn unused_keys_are_not_recorded() {
+    assert_eq!(KeyPress::new(0, Keycode::F5, Mod::NOMOD), None);
+}
+
+#[test]
+fn invalid_macros_are_rejected() {
+    assert!(matches!(MacroPlayer::new("0 Space\n".as_bytes()), Err(MacroError::NotAMacro)));
+    assert!(matches!(
+        MacroPlayer::new("# conway key macro\n0 Space\n0 F5\n".as_bytes()),
+        Err(MacroError::Invalid { line: 3, .. })
+    ));
+    assert!(matches!(
+        MacroPlayer::new("# conway key macro\n10 Space\n5 Space\n".as_bytes()),
+        Err(MacroError::Invalid { line: 3, .. })
+    ));
+}
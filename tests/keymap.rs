@@ -0,0 +1,51 @@
+use sdl2::keyboard::Keycode;
+
+use conway::{KeyAction, Keymap, KeymapError, MAX_KEY_COUNT, push_count_digit};
+
+#[test]
+fn default_keymap_matches_documented_keys() {
+    let keymap = Keymap::default();
+    assert_eq!(keymap.keys(KeyAction::Quit), [Keycode::Escape, Keycode::Q]);
+    assert_eq!(keymap.action(Keycode::Space), Some(KeyAction::PlayPause));
+    assert_eq!(keymap.action(Keycode::Left), Some(KeyAction::FewerSteps));
+    assert_eq!(keymap.action(Keycode::Z), Some(KeyAction::Undo));
+    assert_eq!(keymap.action(Keycode::R), Some(KeyAction::WrapRows));
+    assert_eq!(keymap.action(Keycode::C), Some(KeyAction::WrapCols));
+    assert_eq!(keymap.action(Keycode::S), Some(KeyAction::CycleShape));
+    assert_eq!(keymap.action(Keycode::A), None);
+}
+
+#[test]
+fn keymap_file_rebinds_and_disables_actions() {
+    let text = "# no accidental exits during demos\nquit = Q\n\nplay_pause = Return, Space\nmutate =\nstep = M\n";
+    let keymap = Keymap::load(text.as_bytes()).unwrap();
+    assert_eq!(keymap.keys(KeyAction::Quit), [Keycode::Q]);
+    assert_eq!(keymap.action(Keycode::Escape), None);
+    assert_eq!(keymap.keys(KeyAction::PlayPause), [Keycode::Return, Keycode::Space]);
+    assert_eq!(keymap.action(Keycode::M), Some(KeyAction::Step));
+    assert_eq!(keymap.keys(KeyAction::Mutate), []);
+    assert_eq!(keymap.action(Keycode::N), None);
+    assert_eq!(keymap.keys(KeyAction::CycleColor), [Keycode::V]);
+}
+
+#[test]
+fn invalid_keymaps_are_rejected() {
+    let line = |text: &str| match Keymap::load(text.as_bytes()) {
+        Err(KeymapError::Invalid { line, .. }) => Some(line),
+        _ => None,
+    };
+    assert_eq!(line("quit Q\n"), Some(1));
+    assert_eq!(line("quit = Q\nexplode = X\n"), Some(2));
+    assert_eq!(line("\nquit = F5\n"), Some(2));
+    assert_eq!(line("quit = Q\nmutate = Q\n"), Some(2));
+}
+
+#[test]
+fn typed_counts_accumulate_up_to_the_cap() {
+    let count = [1, 2, 0].iter().fold(None, |count, digit| Some(push_count_digit(count, *digit)));
+    assert_eq!(count, Some(120));
+    assert_eq!(push_count_digit(None, 0), 0);
+    assert_eq!(push_count_digit(Some(MAX_KEY_COUNT / 10), 9), MAX_KEY_COUNT);
+    assert_eq!(push_count_digit(Some(MAX_KEY_COUNT), 5), MAX_KEY_COUNT);
+    assert_eq!(push_count_digit(Some(usize::MAX), 9), MAX_KEY_COUNT);
+}
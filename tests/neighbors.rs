@@ -0,0 +1,174 @@
+use std::collections::BTreeSet;
+
+use conway::{BoundaryMode, Cell, GameOfLife, Neighborhood};
+
+const BOUNDARIES: [BoundaryMode; 3] = [BoundaryMode::Toroidal, BoundaryMode::Dead, BoundaryMode::Reflect];
+
+/// Get the neighbors of a cell as reported by the game, as sorted `(row, col)`
+/// pairs.
+fn neighbors(game: &GameOfLife, r: usize, c: usize) -> Vec<(usize, usize)> {
+    let mut neighbors: Vec<_> = game.neighbors(Cell { r, c }).iter().map(|cell| (cell.r, cell.c)).collect();
+    neighbors.sort();
+    neighbors
+}
+
+/// Brute force the neighbors of a cell by trying every offset, wrapping,
+/// dropping, or mirroring offsets that cross an edge. Cells reached by
+/// wrapping more than once are only counted once, while mirrored cells are
+/// counted once for each offset that reaches them.
+fn reference(boundary: BoundaryMode, rows: usize, cols: usize, r: usize, c: usize) -> Vec<(usize, usize)> {
+    let mirror = |i: isize, len: isize| match i {
+        -1 if len > 1 => Some(1),
+        i if i == len && len > 1 => Some(len - 2),
+        i if (0..len).contains(&i) => Some(i),
+        _ => None,
+    };
+    let (rows, cols) = (rows as isize, cols as isize);
+    let mut expected = Vec::new();
+    for dr in -1..=1 {
+        for dc in -1..=1 {
+            if (dr, dc) == (0, 0) {
+                continue;
+            }
+            let (nr, nc) = (r as isize + dr, c as isize + dc);
+            let neighbor = match boundary {
+                BoundaryMode::Toroidal => (nr.rem_euclid(rows), nc.rem_euclid(cols)),
+                BoundaryMode::Dead if (0..rows).contains(&nr) && (0..cols).contains(&nc) => (nr, nc),
+                BoundaryMode::Dead => continue,
+                BoundaryMode::Reflect => match (mirror(nr, rows), mirror(nc, cols)) {
+                    (Some(nr), Some(nc)) => (nr, nc),
+                    _ => continue,
+                },
+            };
+            expected.push((neighbor.0 as usize, neighbor.1 as usize));
+        }
+    }
+    if boundary == BoundaryMode::Toroidal {
+        expected = expected.into_iter().collect::<BTreeSet<_>>().into_iter().collect();
+    }
+    expected.retain(|neighbor| *neighbor != (r, c));
+    expected.sort();
+    expected
+}
+
+#[test]
+fn neighbors_match_reference_on_small_grids() {
+    for boundary in BOUNDARIES {
+        for rows in 1..=4 {
+            for cols in 1..=4 {
+                let mut game = GameOfLife::from_grid(&vec![vec![false; cols]; rows]).unwrap();
+                game.set_boundary(boundary);
+                for r in 0..rows {
+                    for c in 0..cols {
+                        assert_eq!(
+                            neighbors(&game, r, c), reference(boundary, rows, cols, r, c),
+                            "{:?} boundary, ({}, {}) in {}x{}", boundary, r, c, rows, cols
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn cylinder_wraps_only_one_axis() {
+    let mut game = GameOfLife::from_grid(&vec![vec![false; 5]; 4]).unwrap();
+    game.set_boundary_rows(BoundaryMode::Dead);
+    game.set_boundary_cols(BoundaryMode::Toroidal);
+    assert_eq!(neighbors(&game, 0, 0), vec![(0, 1), (0, 4), (1, 0), (1, 1), (1, 4)]);
+    assert_eq!(neighbors(&game, 3, 4), vec![(2, 0), (2, 3), (2, 4), (3, 0), (3, 3)]);
+    assert_eq!(neighbors(&game, 1, 2).len(), 8);
+
+    game.set_boundary_rows(BoundaryMode::Toroidal);
+    game.set_boundary_cols(BoundaryMode::Dead);
+    assert_eq!(neighbors(&game, 0, 0), vec![(0, 1), (1, 0), (1, 1), (3, 0), (3, 1)]);
+    assert_eq!(neighbors(&game, 3, 4), vec![(0, 3), (0, 4), (2, 3), (2, 4), (3, 3)]);
+}
+
+#[test]
+fn narrow_torus_counts_each_neighbor_once() {
+    // On a 2x2 torus, every cell has each other cell as a neighbor exactly
+    // once, so three live cells give the dead cell three neighbors.
+    let mut game = GameOfLife::from_grid(&[[true, true], [true, false]]).unwrap();
+    game.step();
+    assert!(game.is_alive(Cell { r: 1, c: 1 }));
+}
+
+#[test]
+fn reflected_neighbors_at_corner() {
+    let mut game = GameOfLife::from_grid(&vec![vec![false; 4]; 4]).unwrap();
+    game.set_boundary(BoundaryMode::Reflect);
+    // Three of the corner's neighbors lie beyond the edges and mirror back to
+    // the diagonal cell, and the others beyond the edges mirror the cells
+    // beside and below.
+    assert_eq!(neighbors(&game, 0, 0), vec![(0, 1), (0, 1), (1, 0), (1, 0), (1, 1), (1, 1), (1, 1), (1, 1)]);
+}
+
+#[test]
+fn reflected_neighbors_at_edge() {
+    let mut game = GameOfLife::from_grid(&vec![vec![false; 4]; 4]).unwrap();
+    game.set_boundary(BoundaryMode::Reflect);
+    assert_eq!(neighbors(&game, 3, 2), vec![(2, 1), (2, 1), (2, 2), (2, 2), (2, 3), (2, 3), (3, 1), (3, 3)]);
+}
+
+#[test]
+fn reflecting_edge_counts_mirrored_cells() {
+    // The cell beyond the top edge above the corner mirrors the live cell
+    // below it, so the corner counts it twice. With two live neighbors, the
+    // corner survives where it would die on a dead board.
+    let mut game = GameOfLife::from_grid(&[
+        [true, false, false],
+        [true, false, false],
+        [false, false, false],
+    ]).unwrap();
+    game.set_boundary(BoundaryMode::Reflect);
+    let live_neighbors = game.neighbors(Cell { r: 0, c: 0 }).iter().filter(|cell| game.is_alive(**cell)).count();
+    assert_eq!(live_neighbors, 2);
+    game.step();
+    assert!(game.is_alive(Cell { r: 0, c: 0 }));
+}
+
+#[test]
+fn hex_neighborhood_drops_up_right_and_down_left() {
+    let mut game = GameOfLife::from_grid(&vec![vec![false; 5]; 5]).unwrap();
+    game.set_neighborhood(Neighborhood::Hex);
+    assert_eq!(neighbors(&game, 2, 2), vec![(1, 1), (1, 2), (2, 1), (2, 3), (3, 2), (3, 3)]);
+
+    // Only one of the three live diagonals is a neighbor, so the center is
+    // not born as it would be with all eight neighbors.
+    game.set_alive(Cell { r: 1, c: 1 }, true);
+    game.set_alive(Cell { r: 1, c: 3 }, true);
+    game.set_alive(Cell { r: 3, c: 1 }, true);
+    let mut moore = game.clone();
+    moore.set_neighborhood(Neighborhood::Moore);
+    game.step();
+    moore.step();
+    assert!(!game.is_alive(Cell { r: 2, c: 2 }));
+    assert!(moore.is_alive(Cell { r: 2, c: 2 }));
+}
+
+#[test]
+fn count_live_neighbors_follows_boundary_and_neighborhood() {
+    // Live cells in the top left corner and along the bottom and right edges.
+    let mut game = GameOfLife::from_grid(&[
+        [true, true, false, false],
+        [true, false, false, true],
+        [false, false, false, true],
+        [true, false, false, true],
+    ]).unwrap();
+    let corner = Cell { r: 0, c: 0 };
+    // Wrapping reaches (1, 3), (3, 0), and (3, 3) as well.
+    assert_eq!(game.count_live_neighbors(corner), 5);
+    game.set_boundary(BoundaryMode::Dead);
+    assert_eq!(game.count_live_neighbors(corner), 2);
+    game.set_boundary(BoundaryMode::Reflect);
+    // The cells beside and below the corner are each mirrored once more.
+    assert_eq!(game.count_live_neighbors(corner), 4);
+
+    game.set_boundary(BoundaryMode::Toroidal);
+    game.set_neighborhood(Neighborhood::Hex);
+    // The dropped diagonals of the corner wrap to (1, 3), which is alive,
+    // and (3, 1).
+    assert_eq!(game.count_live_neighbors(corner), 4);
+}
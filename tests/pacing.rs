@@ -0,0 +1,188 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use conway::{Clock, FramePacer, SpeedMeter};
+
+/// A clock that only moves when told to, or when slept on, and remembers
+/// every sleep.
+#[derive(Clone)]
+struct FakeClock {
+    now: Rc<RefCell<Instant>>,  // current fake time
+    sleeps: Rc<RefCell<Vec<Duration>>>,  // every sleep, oldest first
+}
+
+impl FakeClock {
+    fn new() -> FakeClock {
+        FakeClock { now: Rc::new(RefCell::new(Instant::now())), sleeps: Rc::new(RefCell::new(Vec::new())) }
+    }
+
+    fn advance(&self, duration: Duration) {
+        *self.now.borrow_mut() += duration;
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Instant {
+        *self.now.borrow()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.sleeps.borrow_mut().push(duration);
+        self.advance(duration);
+    }
+}
+
+/// Run one frame that takes `work` to draw, returning how long the pacer
+/// slept after it.
+fn frame(pacer: &mut FramePacer, clock: &FakeClock, work: Duration) -> Duration {
+    pacer.start_frame();
+    clock.advance(work);
+    pacer.finish_frame()
+}
+
+#[test]
+fn sleeps_off_rest_of_frame() {
+    let clock = FakeClock::new();
+    let mut pacer = FramePacer::new(Box::new(clock.clone()));
+    assert_eq!(pacer.framerate(), Some(24));
+
+    let budget = Duration::from_nanos(1_000_000_000 / 24);
+    assert_eq!(frame(&mut pacer, &clock, Duration::from_millis(10)), budget - Duration::from_millis(10));
+    assert_eq!(frame(&mut pacer, &clock, Duration::ZERO), budget);
+    assert_eq!(*clock.sleeps.borrow(), vec![budget - Duration::from_millis(10), budget]);
+}
+
+#[test]
+fn slow_frames_do_not_sleep() {
+    let clock = FakeClock::new();
+    let mut pacer = FramePacer::new(Box::new(clock.clone()));
+    assert_eq!(frame(&mut pacer, &clock, Duration::from_millis(100)), Duration::ZERO);
+    assert!(clock.sleeps.borrow().is_empty());
+}
+
+#[test]
+fn framerate_changes_frame_time() {
+    let clock = FakeClock::new();
+    let mut pacer = FramePacer::new(Box::new(clock.clone()));
+    for _ in 0..20 {
+        pacer.dec_framerate();
+    }
+    assert_eq!(pacer.framerate(), Some(4));
+    assert_eq!(frame(&mut pacer, &clock, Duration::from_millis(50)), Duration::from_millis(200));
+
+    for _ in 0..200 {
+        pacer.inc_framerate();
+    }
+    assert_eq!(pacer.framerate(), None);
+    assert_eq!(frame(&mut pacer, &clock, Duration::ZERO), Duration::ZERO);
+}
+
+#[test]
+fn framerate_crosses_max_both_ways() {
+    let clock = FakeClock::new();
+    let mut pacer = FramePacer::new(Box::new(clock.clone()));
+    let max_frame = Duration::from_nanos(1_000_000_000 / 120);
+    while pacer.framerate() != Some(120) {
+        pacer.inc_framerate();
+    }
+    assert_eq!(pacer.min_frame_time(), max_frame);
+
+    pacer.inc_framerate();
+    assert_eq!(pacer.framerate(), None);
+    assert_eq!(pacer.min_frame_time(), Duration::ZERO);
+    pacer.inc_framerate();
+    assert_eq!(pacer.framerate(), None);
+
+    // One step down from no limit is the max framerate, with its frame time.
+    pacer.dec_framerate();
+    assert_eq!(pacer.framerate(), Some(120));
+    assert_eq!(pacer.min_frame_time(), max_frame);
+    assert_eq!(frame(&mut pacer, &clock, Duration::ZERO), max_frame);
+    pacer.dec_framerate();
+    assert_eq!(pacer.framerate(), Some(119));
+    assert_eq!(pacer.min_frame_time(), Duration::from_nanos(1_000_000_000 / 119));
+}
+
+#[test]
+fn framerate_stops_at_1() {
+    let mut pacer = FramePacer::default();
+    for _ in 0..100 {
+        pacer.dec_framerate();
+    }
+    assert_eq!(pacer.framerate(), Some(1));
+    assert_eq!(pacer.min_frame_time(), Duration::from_secs(1));
+}
+
+#[test]
+fn speed_meter_averages_over_rolling_window() {
+    let start = Instant::now();
+    let at = |millis: u64| start + Duration::from_millis(millis);
+    let mut meter = SpeedMeter::new(Duration::from_secs(1));
+    meter.record(at(0), 0);
+    assert_eq!(meter.generations_per_second(), None);
+
+    // 10 generations every 100ms.
+    for i in 1..=10 {
+        meter.record(at(i * 100), i as u128 * 10);
+    }
+    assert_eq!(meter.generations_per_second(), Some(100.0));
+
+    // Slowing to 1 generation every 100ms is measured once the fast samples
+    // leave the window.
+    for i in 11..=20 {
+        meter.record(at(i * 100), 100 + (i as u128 - 10));
+    }
+    assert_eq!(meter.generations_per_second(), Some(10.0));
+
+    // Going back a generation, e.g. after replacing the game, starts over.
+    meter.record(at(2100), 5);
+    assert_eq!(meter.generations_per_second(), None);
+}
+
+#[test]
+fn max_steps_per_second_spreads_steps_over_frames() {
+    let clock = FakeClock::new();
+    let mut pacer = FramePacer::new(Box::new(clock.clone()));
+    assert_eq!(pacer.allowed_steps(50), 50);
+
+    // At 20 FPS and 50 generations per frame, a limit of 10 generations per
+    // second steps once every other frame.
+    pacer.set_max_steps_per_second(Some(10));
+    let mut steps = Vec::new();
+    for _ in 0..6 {
+        steps.push(pacer.allowed_steps(50));
+        clock.advance(Duration::from_millis(50));
+    }
+    assert_eq!(steps, [1, 0, 1, 0, 1, 0]);
+
+    // A long wait earns no more than one second's worth of steps, even when
+    // a frame wants more.
+    clock.advance(Duration::from_secs(60));
+    assert_eq!(pacer.allowed_steps(50), 10);
+    assert_eq!(pacer.allowed_steps(50), 0);
+
+    // Nor more than one frame's worth, when the limit is higher.
+    pacer.set_max_steps_per_second(Some(1000));
+    pacer.allowed_steps(50);
+    clock.advance(Duration::from_secs(60));
+    assert_eq!(pacer.allowed_steps(50), 50);
+    assert_eq!(pacer.allowed_steps(50), 0);
+
+    pacer.set_max_steps_per_second(None);
+    assert_eq!(pacer.allowed_steps(50), 50);
+}
+
+#[test]
+fn time_left_counts_down_the_frame() {
+    let clock = FakeClock::new();
+    let mut pacer = FramePacer::new(Box::new(clock.clone()));
+    assert_eq!(pacer.time_left(), Duration::ZERO);
+
+    let budget = Duration::from_nanos(1_000_000_000 / 24);
+    pacer.start_frame();
+    clock.advance(Duration::from_millis(10));
+    assert_eq!(pacer.time_left(), budget - Duration::from_millis(10));
+    clock.advance(budget);
+    assert_eq!(pacer.time_left(), Duration::ZERO);
+}
@@ -0,0 +1,263 @@
+mod common;
+
+use std::env;
+use std::fs;
+use std::process;
+
+use std::path::Path;
+
+use conway::{
+    Cell, CoordOrder, GameOfLife, PatternError, PatternOptions, RuleString, pattern_format_for_path, pattern_formats,
+    pattern_info,
+};
+
+const FIXTURES: &str = "tests/fixtures";
+
+/// Load a fixture file into a game of the given size.
+fn load(name: &str, rows: usize, cols: usize) -> Result<GameOfLife, PatternError> {
+    GameOfLife::from_file(&format!("{}/{}", FIXTURES, name), rows, cols)
+}
+use common::live;
+
+/// A glider centered in a 7x7 game.
+const GLIDER_7X7: [(usize, usize); 5] = [(2, 3), (3, 4), (4, 2), (4, 3), (4, 4)];
+
+#[test]
+fn chars_file_is_centered() {
+    let game = load("glider_chars.txt", 7, 7).unwrap();
+    assert_eq!((game.rows, game.cols), (7, 7));
+    assert_eq!(live(&game), GLIDER_7X7);
+}
+
+#[test]
+fn chars_file_with_regex_symbols() {
+    let game = load("glider_chars_symbols.txt", 7, 7).unwrap();
+    assert_eq!(live(&game), GLIDER_7X7);
+}
+
+#[test]
+fn chars_file_with_ragged_rows() {
+    // Four rows, the longest five cells wide; the blank third row and the
+    // short rows are dead cells.
+    let game = load("ragged_chars.txt", 8, 9).unwrap();
+    assert_eq!(live(&game), vec![(2, 3), (3, 2), (5, 4)]);
+    assert!(matches!(load("ragged_chars.txt", 3, 9), Err(PatternError::TooLarge { rows: 4, cols: 5 })));
+}
+
+#[test]
+fn coords_file_is_centered() {
+    let game = load("glider_coords.txt", 7, 7).unwrap();
+    assert_eq!(live(&game), GLIDER_7X7);
+}
+
+#[test]
+fn coords_file_in_xy_order() {
+    let options = PatternOptions { coord_order: CoordOrder::XY, ..PatternOptions::default() };
+    let game = GameOfLife::from_file_with(&format!("{}/glider_coords.txt", FIXTURES), 7, 7, &options).unwrap();
+    let mut expected: Vec<_> = GLIDER_7X7.iter().map(|(r, c)| (*c, *r)).collect();
+    expected.sort();
+    assert_eq!(live(&game), expected);
+}
+
+#[test]
+fn rle_file_is_centered() {
+    let game = load("glider_no_rule.rle", 7, 7).unwrap();
+    assert_eq!(live(&game), GLIDER_7X7);
+    assert_eq!(game.rule(), RuleString::conway());
+}
+
+#[test]
+fn rle_file_sets_rule_and_metadata() {
+    let game = load("glider.rle", 7, 7).unwrap();
+    assert_eq!(live(&game), GLIDER_7X7);
+    assert_eq!(game.rule().to_string(), "B36/S23");
+
+    let meta = game.meta();
+    assert_eq!(meta.name.as_deref(), Some("Glider"));
+    assert_eq!(meta.author.as_deref(), Some("Richard K. Guy"));
+    assert_eq!(meta.comments, vec![
+        "The smallest, most common, and first discovered spaceship.",
+        "Travels diagonally at c/4.",
+    ]);
+}
+
+#[test]
+fn info_summarizes_without_loading() {
+    let options = PatternOptions::default();
+    let info = pattern_info(&format!("{}/glider.rle", FIXTURES), &options).unwrap();
+    assert_eq!(info.format.name, "rle");
+    assert_eq!(info.rule.map(|rule| rule.to_string()).as_deref(), Some("B36/S23"));
+    assert_eq!((info.rows, info.cols, info.population), (3, 3, 5));
+    assert_eq!(info.meta.name.as_deref(), Some("Glider"));
+
+    let info = pattern_info(&format!("{}/glider_no_rule.rle", FIXTURES), &options).unwrap();
+    assert_eq!(info.rule, None);
+    assert_eq!(info.meta.name, None);
+
+    let info = pattern_info(&format!("{}/glider_chars.txt", FIXTURES), &options).unwrap();
+    assert_eq!(info.format.name, "chars");
+    assert_eq!((info.rows, info.cols, info.population), (3, 3, 5));
+    assert!(matches!(
+        pattern_info(&format!("{}/unknown_format.txt", FIXTURES), &options),
+        Err(PatternError::UnknownFormat)
+    ));
+}
+
+#[test]
+fn centering_in_uneven_game() {
+    let game = load("glider_coords.txt", 4, 10).unwrap();
+    assert_eq!(live(&game), vec![(1, 5), (2, 6), (3, 4), (3, 5), (3, 6)]);
+}
+
+#[test]
+fn formats_agree() {
+    let chars = load("glider_chars.txt", 12, 9).unwrap();
+    let coords = load("glider_coords.txt", 12, 9).unwrap();
+    let rle = load("glider.rle", 12, 9).unwrap();
+    assert_eq!(live(&chars), live(&coords));
+    assert_eq!(live(&chars), live(&rle));
+}
+
+#[test]
+fn missing_file() {
+    assert!(matches!(load("does_not_exist.txt", 7, 7), Err(PatternError::Io(_))));
+}
+
+#[test]
+fn unknown_format() {
+    assert!(matches!(load("unknown_format.txt", 7, 7), Err(PatternError::UnknownFormat)));
+}
+
+#[test]
+fn chars_missing_declaration() {
+    assert!(matches!(load("chars_missing_decl.txt", 7, 7), Err(PatternError::MissingChars)));
+}
+
+#[test]
+fn coords_out_of_range() {
+    assert!(matches!(load("coords_overflow.txt", 7, 7), Err(PatternError::InvalidCoord(_))));
+}
+
+#[test]
+fn rle_missing_header() {
+    assert!(matches!(load("rle_missing_header.rle", 7, 7), Err(PatternError::MissingHeader)));
+}
+
+#[test]
+fn rle_invalid_rule() {
+    assert!(matches!(load("rle_invalid_rule.rle", 7, 7), Err(PatternError::InvalidRule(_))));
+}
+
+#[test]
+fn pattern_too_large() {
+    assert!(matches!(
+        load("glider_coords.txt", 2, 7),
+        Err(PatternError::TooLarge { rows: 3, cols: 3 })
+    ));
+}
+
+#[test]
+fn autofit_grows_to_fit_pattern() {
+    let game = GameOfLife::from_file_autofit(&format!("{}/glider_coords.txt", FIXTURES), 2, 30, 100, 100).unwrap();
+    assert_eq!((game.rows, game.cols), (19, 30));
+    assert_eq!(live(&game), vec![(8, 15), (9, 16), (10, 14), (10, 15), (10, 16)]);
+}
+
+#[test]
+fn scaled_pattern_cells_become_squares() {
+    let options = PatternOptions { scale: Some(2), ..PatternOptions::default() };
+    let path = format!("{}/glider_coords.txt", FIXTURES);
+    let game = GameOfLife::from_file_with(&path, 6, 6, &options).unwrap();
+    assert_eq!(live(&game), vec![
+        (0, 2), (0, 3), (1, 2), (1, 3),
+        (2, 4), (2, 5), (3, 4), (3, 5),
+        (4, 0), (4, 1), (4, 2), (4, 3), (4, 4), (4, 5),
+        (5, 0), (5, 1), (5, 2), (5, 3), (5, 4), (5, 5),
+    ]);
+    assert!(matches!(
+        GameOfLife::from_file_with(&path, 5, 8, &options),
+        Err(PatternError::TooLarge { rows: 6, cols: 6 })
+    ));
+
+    let options = PatternOptions { autofit: Some((20, 20)), ..options };
+    let game = GameOfLife::from_file_with(&path, 5, 5, &options).unwrap();
+    assert_eq!((game.rows, game.cols), (20, 20));
+    assert_eq!(game.population(), 20);
+}
+
+#[test]
+fn autofit_growth_is_capped() {
+    let game = GameOfLife::from_file_autofit(&format!("{}/glider_coords.txt", FIXTURES), 2, 2, 5, 4).unwrap();
+    assert_eq!((game.rows, game.cols), (5, 4));
+    assert!(matches!(
+        GameOfLife::from_file_autofit(&format!("{}/glider_coords.txt", FIXTURES), 2, 2, 2, 10),
+        Err(PatternError::TooLarge { rows: 3, cols: 3 })
+    ));
+}
+
+#[test]
+fn format_examples_load_as_gliders() {
+    let names: Vec<_> = pattern_formats().iter().map(|format| format.name).collect();
+    assert_eq!(names, ["chars", "coords", "rle", "cells"]);
+    for format in pattern_formats() {
+        let path = env::temp_dir().join(format!("conway_format_example_{}_{}", process::id(), format.name));
+        fs::write(&path, format.example).unwrap();
+        let game = GameOfLife::from_file(path.to_str().unwrap(), 7, 7).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(live(&game), GLIDER_7X7, "{}", format.name);
+    }
+}
+
+#[test]
+fn every_format_round_trips() {
+    let glider = GameOfLife::from_file_fitted(&format!("{}/glider.rle", FIXTURES), &PatternOptions::default()).unwrap();
+    assert_eq!((glider.rows, glider.cols), (3, 3));
+    for format in pattern_formats() {
+        let path = env::temp_dir().join(format!("conway_round_trip_{}.{}", process::id(), format.extensions[0]));
+        assert_eq!(pattern_format_for_path(&path).map(|found| found.name), Some(format.name));
+        let mut file = fs::File::create(&path).unwrap();
+        format.write(&glider, &mut file).unwrap();
+        drop(file);
+
+        let game = GameOfLife::from_file_fitted(path.to_str().unwrap(), &PatternOptions::default()).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(live(&game), live(&glider), "{}", format.name);
+        if format.name == "rle" || format.name == "cells" {
+            assert_eq!(game.meta(), glider.meta(), "{}", format.name);
+        }
+    }
+    assert!(pattern_format_for_path(Path::new("glider.PNG")).is_none());
+    assert_eq!(pattern_format_for_path(Path::new("glider.RLE")).map(|format| format.name), Some("rle"));
+}
+
+#[test]
+fn rle_is_written_in_runs() {
+    // Two blocks with two empty rows between them, away from the origin.
+    let mut game = GameOfLife::from_grid(&vec![vec![false; 10]; 10]).unwrap();
+    for (r, c) in [(1, 3), (1, 4), (2, 3), (2, 4), (5, 6), (5, 7), (6, 6), (6, 7)] {
+        game.set_alive(Cell { r, c }, true);
+    }
+    let rle = pattern_formats().iter().find(|format| format.name == "rle").unwrap();
+    let mut text = Vec::new();
+    rle.write(&game, &mut text).unwrap();
+    assert_eq!(String::from_utf8(text).unwrap(), "x = 5, y = 6, rule = B3/S23\n2o$2o3$3b2o$3b2o!\n");
+}
+
+#[test]
+fn inline_coords_are_centered_and_validated() {
+    let options = PatternOptions::default();
+    let blinker = GameOfLife::from_coords("0,1 1,1  2,1\n", 5, 5, &options).unwrap();
+    assert_eq!(live(&blinker), [(1, 3), (2, 3), (3, 3)]);
+
+    let xy = PatternOptions { coord_order: CoordOrder::XY, ..PatternOptions::default() };
+    assert_eq!(live(&GameOfLife::from_coords("1,0 1,1 1,2", 5, 5, &xy).unwrap()), live(&blinker));
+
+    assert!(matches!(
+        GameOfLife::from_coords("0,1 1;1", 5, 5, &options),
+        Err(PatternError::InvalidCoord(coord)) if coord == "1;1"
+    ));
+    assert!(matches!(
+        GameOfLife::from_coords("0,0 5,0", 5, 5, &options),
+        Err(PatternError::TooLarge { rows: 6, cols: 1 })
+    ));
+}
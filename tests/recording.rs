@@ -0,0 +1,82 @@
+mod common;
+
+use conway::{DeltaPlayer, DeltaRecorder, GameOfLife, RecordingError};
+use common::live;
+
+/// Record `generations` generations of a game, returning the recording and
+/// each generation recorded.
+fn recorded(mut game: GameOfLife, generations: usize) -> (Vec<u8>, Vec<GameOfLife>) {
+    let mut recorder = DeltaRecorder::new(Vec::new(), &game).unwrap();
+    let mut history = vec![game.clone()];
+    for _ in 0..generations {
+        recorder.record(&game.step_delta()).unwrap();
+        history.push(game.clone());
+    }
+    (recorder.into_inner().unwrap(), history)
+}
+
+#[test]
+fn round_trip() {
+    let mut game = GameOfLife::from_file("tests/fixtures/glider_coords.txt", 12, 10).unwrap();
+    game.mutate(0.05, 4);
+    let (bytes, history) = recorded(game, 40);
+
+    let mut player = DeltaPlayer::new(bytes.as_slice()).unwrap();
+    for expected in history.iter() {
+        assert_eq!(player.game().generation(), expected.generation());
+        assert_eq!(live(player.game()), live(expected));
+        for cell in expected.live_cells() {
+            assert_eq!(player.game().age(cell), expected.age(cell));
+        }
+        if expected.generation() < 40 {
+            assert!(player.next_frame().unwrap().is_some());
+        }
+    }
+    assert!(player.next_frame().unwrap().is_none());
+}
+
+#[test]
+fn frames_hold_only_changes() {
+    // A blinker changes four cells each generation, however large the board.
+    let mut grid = vec![vec![false; 100]; 100];
+    grid[50][49..52].fill(true);
+    let (bytes, _) = recorded(GameOfLife::from_grid(&grid).unwrap(), 10);
+    let (start, _) = recorded(GameOfLife::from_grid(&grid).unwrap(), 0);
+    assert_eq!(bytes.len() - start.len(), 10 * (2 * 8 + 4 * 8));
+}
+
+#[test]
+fn rejects_other_data() {
+    let result = DeltaPlayer::new("coords\n0,1\n".as_bytes());
+    assert!(matches!(result, Err(RecordingError::NotARecording)));
+}
+
+#[test]
+fn rejects_damaged_frames() {
+    let (bytes, _) = recorded(GameOfLife::from_file("tests/fixtures/glider_coords.txt", 8, 8).unwrap(), 3);
+
+    let truncated = &bytes[..bytes.len() - 3];
+    let mut player = DeltaPlayer::new(truncated).unwrap();
+    assert!(player.next_frame().unwrap().is_some());
+    assert!(player.next_frame().unwrap().is_some());
+    assert!(matches!(player.next_frame(), Err(RecordingError::Corrupt(_))));
+
+    // Playing the first frame twice kills cells that are already dead.
+    let (start, _) = recorded(GameOfLife::from_file("tests/fixtures/glider_coords.txt", 8, 8).unwrap(), 0);
+    let (one, _) = recorded(GameOfLife::from_file("tests/fixtures/glider_coords.txt", 8, 8).unwrap(), 1);
+    let mut repeated = one.clone();
+    repeated.extend_from_slice(&one[start.len()..]);
+    let mut player = DeltaPlayer::new(repeated.as_slice()).unwrap();
+    assert!(player.next_frame().unwrap().is_some());
+    assert!(matches!(player.next_frame(), Err(RecordingError::Corrupt(_))));
+}
+
+#[test]
+fn recordings_are_reproducible() {
+    let soup = || {
+        let mut game = GameOfLife::from_grid(&vec![vec![false; 30]; 30]).unwrap();
+        game.mutate(0.3, 9);
+        game
+    };
+    assert_eq!(recorded(soup(), 20).0, recorded(soup(), 20).0);
+}
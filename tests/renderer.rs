@@ -0,0 +1,81 @@
+use std::env;
+use std::fs;
+use std::process;
+
+use conway::{Cell, Corner, CountFormat, GameOfLife, PngRender, Renderer, TextRender, draw_label};
+
+/// A vertical blinker in the middle of a 3x3 game.
+fn blinker() -> GameOfLife {
+    let mut game = GameOfLife::from_grid(&vec![vec![false; 3]; 3]).unwrap();
+    for r in 0..3 {
+        game.set_alive(Cell { r, c: 1 }, true);
+    }
+    game
+}
+
+#[test]
+fn text_render_writes_numbered_generations() {
+    let mut game = blinker();
+    let mut renderer = TextRender::new(Vec::new());
+    renderer.render(&game).unwrap();
+    game.step();
+    renderer.render(&game).unwrap();
+    assert!(renderer.playing() && !renderer.finished());
+
+    let text = String::from_utf8(renderer.into_inner()).unwrap();
+    let expected = format!("generation 0\n{}\n\n", blinker()) + &format!("generation 1\n{}\n\n", game);
+    assert_eq!(text, expected);
+}
+
+#[test]
+fn png_render_finishes_after_its_frames() {
+    let dir = env::temp_dir().join(format!("conway_png_render_{}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let mut renderer = PngRender::new(&dir, 2, 2).unwrap();
+    let mut game = blinker();
+    while !renderer.finished() {
+        renderer.render(&game).unwrap();
+        game.step();
+    }
+
+    let mut names: Vec<_> = fs::read_dir(&dir).unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["frame_0000.png", "frame_0001.png"]);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn labels_are_drawn_in_corners_and_clipped() {
+    let (width, height) = (20, 12);
+    let pixel = |rgba: &[u8], x: usize, y: usize| rgba[(y * width + x) * 4..(y * width + x + 1) * 4].to_vec();
+    let yellow = vec![255, 200, 0, 255];
+    let blank = vec![7; 4];
+
+    // The 1 has a single dot in the middle of its top row, inside a dot of padding.
+    let mut rgba = vec![7; width * height * 4];
+    draw_label(&mut rgba, width, height, "1", Corner::TopLeft, 1);
+    assert_eq!(pixel(&rgba, 3, 1), yellow);
+    assert_eq!(pixel(&rgba, 0, 0), vec![0, 0, 0, 255]);
+    assert_eq!((pixel(&rgba, 7, 0), pixel(&rgba, 0, 9)), (blank.clone(), blank.clone()));
+
+    let mut rgba = vec![7; width * height * 4];
+    draw_label(&mut rgba, width, height, "1", Corner::BottomRight, 1);
+    assert_eq!(pixel(&rgba, 16, 4), yellow);
+    assert_eq!(pixel(&rgba, 12, 11), blank);
+
+    let mut rgba = vec![7; width * height * 4];
+    draw_label(&mut rgba, width, height, "12345", Corner::TopRight, 3);
+    assert!(rgba.chunks(4).all(|pixel| pixel != blank.as_slice()));
+}
+
+#[test]
+fn count_formats() {
+    assert_eq!(CountFormat::Plain.format(1234567), "1234567");
+    assert_eq!(CountFormat::Grouped.format(1234567), "1,234,567");
+    assert_eq!(CountFormat::Grouped.format(123456), "123,456");
+    assert_eq!(CountFormat::Grouped.format(12), "12");
+    assert_eq!(CountFormat::Scientific.format(1234567), "1.23e6");
+    assert_eq!(CountFormat::Scientific.format(999), "999");
+}
@@ -0,0 +1,46 @@
+use conway::{Cell, ShapeTool};
+
+fn cells(tool: ShapeTool, from: (usize, usize), to: (usize, usize)) -> Vec<(usize, usize)> {
+    let mut cells: Vec<_> = tool.cells(Cell { r: from.0, c: from.1 }, Cell { r: to.0, c: to.1 })
+        .into_iter()
+        .map(|cell| (cell.r, cell.c))
+        .collect();
+    cells.sort();
+    cells
+}
+
+#[test]
+fn boxes_cover_the_rectangle_between_the_corners() {
+    assert_eq!(
+        cells(ShapeTool::Box, (3, 2), (1, 4)),
+        [(1, 2), (1, 3), (1, 4), (2, 2), (2, 4), (3, 2), (3, 3), (3, 4)],
+    );
+    assert_eq!(cells(ShapeTool::FilledBox, (1, 2), (3, 4)).len(), 9);
+    assert_eq!(cells(ShapeTool::Box, (2, 2), (2, 2)), [(2, 2)]);
+}
+
+#[test]
+fn lines_join_the_corners_without_gaps() {
+    assert_eq!(cells(ShapeTool::Line, (0, 0), (0, 3)), [(0, 0), (0, 1), (0, 2), (0, 3)]);
+    assert_eq!(cells(ShapeTool::Line, (3, 3), (0, 0)), [(0, 0), (1, 1), (2, 2), (3, 3)]);
+    let shallow = cells(ShapeTool::Line, (0, 0), (2, 6));
+    assert_eq!(shallow.len(), 7);
+    assert!(shallow.windows(2).all(|pair| pair[1].1 == pair[0].1 + 1 && pair[1].0 - pair[0].0 <= 1));
+}
+
+#[test]
+fn ellipses_are_outlines_touching_each_side() {
+    let ellipse = cells(ShapeTool::Ellipse, (0, 0), (6, 10));
+    assert!(ellipse.contains(&(0, 5)) && ellipse.contains(&(6, 5)));
+    assert!(ellipse.contains(&(3, 0)) && ellipse.contains(&(3, 10)));
+    assert!(!ellipse.contains(&(0, 0)) && !ellipse.contains(&(3, 5)));
+}
+
+#[test]
+fn tools_cycle_back_to_the_box() {
+    let mut tool = ShapeTool::default();
+    for _ in 0..4 {
+        tool = tool.next();
+    }
+    assert_eq!(tool, ShapeTool::Box);
+}
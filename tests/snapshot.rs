@@ -0,0 +1,169 @@
+mod common;
+
+use std::env;
+use std::fs::{self, File};
+use std::process;
+
+use conway::{
+    Backend, BoundaryMode, Cell, Checkpoints, GameOfLife, LiveSet, Neighborhood, PatternMeta, Region, RuleString,
+    SnapshotError, StepAlgorithm,
+};
+use common::live;
+
+fn saved(game: &GameOfLife) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    game.save_snapshot(&mut bytes).unwrap();
+    bytes
+}
+
+#[test]
+fn round_trip() {
+    let mut game = GameOfLife::from_file("tests/fixtures/glider_coords.txt", 9, 11).unwrap();
+    game.set_rule("B36/S23".parse::<RuleString>().unwrap());
+    game.set_boundary(BoundaryMode::Dead);
+    game.step_n(7);
+
+    let loaded = GameOfLife::load_snapshot(&mut saved(&game).as_slice()).unwrap();
+    assert_eq!((loaded.rows, loaded.cols), (9, 11));
+    assert_eq!(loaded.rule(), game.rule());
+    assert_eq!(loaded.boundary(), Some(BoundaryMode::Dead));
+    assert_eq!(loaded.generation(), 7);
+    assert_eq!(live(&loaded), live(&game));
+}
+
+#[test]
+fn round_trip_keeps_hex_neighborhood() {
+    let mut game = GameOfLife::from_file("tests/fixtures/glider_coords.txt", 9, 11).unwrap();
+    game.set_neighborhood(Neighborhood::Hex);
+    let mut loaded = GameOfLife::load_snapshot(&mut saved(&game).as_slice()).unwrap();
+    assert_eq!(loaded.neighborhood(), Neighborhood::Hex);
+
+    // The reloaded game evolves like the original, not like a Moore game.
+    game.step_n(5);
+    loaded.step_n(5);
+    assert_eq!(live(&loaded), live(&game));
+}
+
+#[test]
+fn round_trip_keeps_each_axis_boundary() {
+    let mut game = GameOfLife::from_file("tests/fixtures/glider_coords.txt", 9, 11).unwrap();
+    game.set_boundary_rows(BoundaryMode::Reflect);
+    game.set_boundary_cols(BoundaryMode::Toroidal);
+    let loaded = GameOfLife::load_snapshot(&mut saved(&game).as_slice()).unwrap();
+    assert_eq!((loaded.boundary_rows(), loaded.boundary_cols()), (BoundaryMode::Reflect, BoundaryMode::Toroidal));
+    assert_eq!(loaded.boundary(), None);
+
+    // The reloaded cylinder evolves like the original.
+    game.set_boundary_rows(BoundaryMode::Dead);
+    let mut cylinder = GameOfLife::load_snapshot(&mut saved(&game).as_slice()).unwrap();
+    game.step_n(20);
+    cylinder.step_n(20);
+    assert_eq!(live(&cylinder), live(&game));
+}
+
+#[test]
+fn round_trip_keeps_frozen_walls() {
+    let mut game = GameOfLife::from_file("tests/fixtures/glider_coords.txt", 9, 11).unwrap();
+    game.add_border_walls(1);
+    let mut loaded = GameOfLife::load_snapshot(&mut saved(&game).as_slice()).unwrap();
+    assert_eq!(loaded.frozen_count(), game.frozen_count());
+    assert_eq!(loaded.frozen_cells_sorted(), game.frozen_cells_sorted());
+    assert!(loaded.is_frozen(Cell { r: 0, c: 10 }) && !loaded.is_frozen(Cell { r: 1, c: 1 }));
+
+    game.step_n(30);
+    loaded.step_n(30);
+    assert_eq!(live(&loaded), live(&game));
+}
+
+#[test]
+fn round_trip_keeps_every_setting() {
+    let mut game = GameOfLife::from_file("tests/fixtures/glider.rle", 12, 12).unwrap();
+    game.step_n(3);
+    let oldest = game.live_cells()[0];
+    assert!(game.set_age(oldest, 40));
+    game.set_active_region(Some(Region { r: 2, c: 2, rows: 8, cols: 8 }));
+    game.set_halt_when_extinct(true);
+    game.set_backend(Backend::Dense);
+    game.set_step_algorithm(StepAlgorithm::ScanMemo);
+    game.set_live_set(LiveSet::Ordered);
+
+    let loaded = GameOfLife::load_snapshot(&mut saved(&game).as_slice()).unwrap();
+    for cell in game.live_cells() {
+        assert_eq!(loaded.age(cell), game.age(cell));
+    }
+    let meta: &PatternMeta = loaded.meta();
+    assert_eq!(meta.name.as_deref(), Some("Glider"));
+    assert_eq!(meta.author.as_deref(), Some("Richard K. Guy"));
+    assert_eq!(meta.comments, game.meta().comments);
+    assert_eq!(loaded.active_region(), game.active_region());
+    assert!(loaded.halt_when_extinct());
+    assert_eq!(loaded.backend(), Backend::Dense);
+    assert_eq!(loaded.step_algorithm(), StepAlgorithm::ScanMemo);
+    assert_eq!(loaded.live_set(), LiveSet::Ordered);
+    assert_eq!(saved(&loaded), saved(&game));
+}
+
+#[test]
+fn checkpoints_keep_the_two_most_recent() {
+    let dir = env::temp_dir().join(format!("conway_checkpoints_{}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let mut game = GameOfLife::from_file("tests/fixtures/glider_coords.txt", 9, 11).unwrap();
+    let mut checkpoints = Checkpoints::new(&dir.join("run.bin"), 4, game.generation());
+    let load = |path: &std::path::Path| GameOfLife::load_snapshot(&mut File::open(path).unwrap()).unwrap();
+
+    let mut saved_at = Vec::new();
+    for _ in 0..10 {
+        game.step();
+        if checkpoints.save_if_due(&game).unwrap() {
+            saved_at.push(game.generation());
+        }
+    }
+    assert_eq!(saved_at, [4, 8]);
+    assert_eq!(load(checkpoints.path()).generation(), 8);
+    assert_eq!(load(&checkpoints.previous_path()).generation(), 4);
+    assert_eq!(checkpoints.previous_path(), dir.join("run.bin.prev"));
+
+    // A game stepped past several checkpoints at once is saved once.
+    for _ in 0..9 {
+        game.step();
+    }
+    assert!(checkpoints.save_if_due(&game).unwrap());
+    assert!(!checkpoints.save_if_due(&game).unwrap());
+    assert_eq!(load(checkpoints.path()).generation(), 19);
+    assert_eq!(load(&checkpoints.previous_path()).generation(), 8);
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+}
+
+#[test]
+fn rejects_other_data() {
+    let result = GameOfLife::load_snapshot(&mut "coords\n0,1\n".as_bytes());
+    assert!(matches!(result, Err(SnapshotError::NotASnapshot)));
+}
+
+#[test]
+fn rejects_incompatible_version() {
+    let game = GameOfLife::random(4, 4);
+    let mut bytes = saved(&game);
+    bytes[4..6].copy_from_slice(&99u16.to_le_bytes());
+    let result = GameOfLife::load_snapshot(&mut bytes.as_slice());
+    assert!(matches!(result, Err(SnapshotError::UnsupportedVersion(99))));
+}
+
+#[test]
+fn rejects_truncated_data() {
+    let game = GameOfLife::from_file("tests/fixtures/glider_coords.txt", 5, 5).unwrap();
+    let bytes = saved(&game);
+    let result = GameOfLife::load_snapshot(&mut &bytes[..bytes.len() - 3]);
+    assert!(matches!(result, Err(SnapshotError::Corrupt(_))));
+}
+
+#[test]
+fn rejects_invalid_rule() {
+    let game = GameOfLife::from_file("tests/fixtures/glider_coords.txt", 5, 5).unwrap();
+    let mut bytes = saved(&game);
+    let at = bytes.windows(6).position(|window| window == b"B3/S23").unwrap();
+    bytes[at + 5] = b'X';
+    let result = GameOfLife::load_snapshot(&mut bytes.as_slice());
+    assert!(matches!(result, Err(SnapshotError::Corrupt(_))));
+}
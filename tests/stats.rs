@@ -0,0 +1,36 @@
+use conway::{Cell, GameOfLife, RunStats};
+
+#[test]
+fn stats_track_peak_and_extinction() {
+    // A diagonal of three cells: the middle survives one generation, then
+    // dies alone.
+    let mut game = GameOfLife::from_grid(&vec![vec![false; 6]; 6]).unwrap();
+    for i in 1..4 {
+        game.set_alive(Cell { r: i, c: i }, true);
+    }
+    let mut stats = RunStats::new(&game);
+    assert_eq!(stats.peak(), (3, 0));
+
+    game.step();
+    stats.observe(&game);
+    assert_eq!(stats.final_population(), 1);
+    assert_eq!(stats.extinct_at(), None);
+
+    for _ in 0..3 {
+        game.step();
+        stats.observe(&game);
+    }
+    assert_eq!(stats.generations(), 4);
+    assert_eq!(stats.final_population(), 0);
+    assert_eq!(stats.peak(), (3, 0));
+    assert_eq!(stats.extinct_at(), Some(2));
+
+    // Cells drawn onto an empty board bring it back to life, and a new peak
+    // is reached.
+    for c in 0..5 {
+        game.set_alive(Cell { r: 0, c }, true);
+    }
+    stats.observe(&game);
+    assert_eq!(stats.extinct_at(), None);
+    assert_eq!(stats.peak(), (5, 4));
+}
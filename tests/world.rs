@@ -0,0 +1,60 @@
+mod common;
+
+use conway::{GameError, GameOfLife, World};
+use common::live;
+
+/// A world of soups of different sizes, stepped 40 generations on `threads`
+/// threads.
+fn stepped_world(threads: usize) -> World {
+    let mut world = World::new(7);
+    world.set_threads(threads);
+    for i in 0..10 {
+        world.push_soup(10 + i, 20, 0.35).unwrap();
+    }
+    let mut blinker = vec![vec![false; 5]; 5];
+    for row in blinker[1..4].iter_mut() {
+        row[2] = true;
+    }
+    world.push(GameOfLife::from_grid(&blinker).unwrap());
+    world.step_all(40);
+    world
+}
+
+#[test]
+fn soups_are_seeded_independently_and_reproducibly() {
+    let mut world = World::new(1);
+    world.push_soup(30, 30, 0.5).unwrap();
+    world.push_soup(30, 30, 0.5).unwrap();
+    let results = world.results();
+    let (seed_a, seed_b) = (results[0].seed.unwrap(), results[1].seed.unwrap());
+    assert_ne!(seed_a, seed_b);
+    assert_ne!(live(world.game(0).unwrap()), live(world.game(1).unwrap()));
+    assert_eq!(live(world.game(1).unwrap()), live(&World::soup(30, 30, 0.5, seed_b).unwrap()));
+
+    let mut again = World::new(1);
+    again.push_soup(30, 30, 0.5).unwrap();
+    assert_eq!(again.results()[0].seed, Some(seed_a));
+}
+
+#[test]
+fn parallel_stepping_matches_sequential() {
+    let (sequential, parallel) = (stepped_world(1), stepped_world(4));
+    assert_eq!(parallel.results(), sequential.results());
+    for index in 0..sequential.len() {
+        assert_eq!(live(parallel.game(index).unwrap()), live(sequential.game(index).unwrap()));
+    }
+
+    let results = sequential.results();
+    assert_eq!(results.len(), 11);
+    assert!(results.iter().all(|result| result.generation == 40));
+    assert_eq!(results[10].seed, None);
+    assert_eq!(results[10].population, 3);
+}
+
+#[test]
+fn empty_soups_are_rejected() {
+    assert!(matches!(World::soup(0, 5, 0.5, 1), Err(GameError::EmptyGrid)));
+    let mut world = World::new(1);
+    assert!(matches!(world.push_soup(5, 0, 0.5), Err(GameError::EmptyGrid)));
+    assert!(world.is_empty());
+}